@@ -0,0 +1,110 @@
+// Compares the heaps in `util::heap` by running Dijkstra over a large grid of pseudo-random step
+// costs. Run with `cargo bench --bench heap`
+
+use aoc_2025::util::{
+    bench::{Stats, sample},
+    heap::{IndexedMinHeap, MinHeap, RadixHeap},
+};
+
+const SIZE: usize = 400;
+
+// A small linear congruential generator, so every run sees the same grid
+fn costs() -> Vec<u32> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..SIZE * SIZE)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 60) as u32 + 1
+        })
+        .collect()
+}
+
+fn neighbors(idx: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (idx % SIZE, idx / SIZE);
+    [
+        (x > 0).then(|| idx - 1),
+        (x + 1 < SIZE).then(|| idx + 1),
+        (y > 0).then(|| idx - SIZE),
+        (y + 1 < SIZE).then(|| idx + SIZE),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+// Pushes a duplicate whenever a cheaper route turns up and skips stale entries when popped
+fn lazy_deletion(costs: &[u32]) -> u32 {
+    let mut best = vec![u32::MAX; costs.len()];
+    let mut queue = MinHeap::default();
+    best[0] = 0;
+    queue.push(0, 0);
+    while let Some((cost, idx)) = queue.pop() {
+        if cost > best[idx] {
+            continue;
+        }
+        for next in neighbors(idx) {
+            let next_cost = cost + costs[next];
+            if next_cost < best[next] {
+                best[next] = next_cost;
+                queue.push(next_cost, next);
+            }
+        }
+    }
+    best[costs.len() - 1]
+}
+
+// The same as `lazy_deletion`, since a radix heap can't lower keys either
+fn radix(costs: &[u32]) -> u32 {
+    let mut best = vec![u32::MAX; costs.len()];
+    let mut queue = RadixHeap::default();
+    best[0] = 0;
+    queue.push(0, 0);
+    while let Some((cost, idx)) = queue.pop() {
+        if cost > best[idx] {
+            continue;
+        }
+        for next in neighbors(idx) {
+            let next_cost = cost + costs[next];
+            if next_cost < best[next] {
+                best[next] = next_cost;
+                queue.push(next_cost, next);
+            }
+        }
+    }
+    best[costs.len() - 1]
+}
+
+fn decrease_key(costs: &[u32]) -> u32 {
+    let mut best = vec![u32::MAX; costs.len()];
+    let mut queue = IndexedMinHeap::default();
+    best[0] = 0;
+    queue.push(0, 0);
+    while let Some((cost, idx)) = queue.pop() {
+        for next in neighbors(idx) {
+            let next_cost = cost + costs[next];
+            if next_cost < best[next] {
+                best[next] = next_cost;
+                queue.push(next_cost, next);
+            }
+        }
+    }
+    best[costs.len() - 1]
+}
+
+fn report(name: &str, stats: &Stats) {
+    println!(
+        "{name:<16} min {:>10.3?}  median {:>10.3?}  mean {:>10.3?}",
+        stats.min, stats.median, stats.mean
+    );
+}
+
+fn main() {
+    let costs = costs();
+    assert_eq!(lazy_deletion(&costs), decrease_key(&costs));
+    assert_eq!(lazy_deletion(&costs), radix(&costs));
+
+    report("MinHeap", &sample(2, 10, || lazy_deletion(&costs)));
+    report("IndexedMinHeap", &sample(2, 10, || decrease_key(&costs)));
+    report("RadixHeap", &sample(2, 10, || radix(&costs)));
+}
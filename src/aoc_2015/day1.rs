@@ -1,3 +1,12 @@
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
+
 fn to_floor(input: &[u8]) -> isize {
     let mut floor = 0;
     input.iter().for_each(|byte| match *byte {
@@ -9,7 +18,7 @@ fn to_floor(input: &[u8]) -> isize {
         }
         _ => unreachable!(),
     });
-    return floor;
+    floor
 }
 
 fn first_basement_index(input: &[u8]) -> usize {
@@ -28,21 +37,49 @@ fn first_basement_index(input: &[u8]) -> usize {
             return index + 1;
         }
     }
-    return 0;
+    0
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let directions = parse_chars(input, "`(` or `)`", |ch| ch == '(' || ch == ')')?;
+        Ok(directions.as_bytes().to_vec())
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        to_floor(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        first_basement_index(input)
+    }
+}
+
+#[cfg(test)]
 const SAMPLE_1: &[u8] = b"(()(()(";
+#[cfg(test)]
 const SAMPLE_2: &[u8] = b"()())";
-const INPUT: &[u8] = include_bytes!("./inputs/day1.txt");
 
 #[test]
 fn part_1() {
     assert_eq!(3, to_floor(SAMPLE_1), "sample");
-    assert_eq!(74, to_floor(INPUT), "input");
+
+    let Some(input) = test_input(2015, 1) else {
+        return;
+    };
+    assert_eq!(74, to_floor(input.as_bytes()), "input");
 }
 
 #[test]
 fn part_2() {
     assert_eq!(5, first_basement_index(SAMPLE_2), "sample");
-    assert_eq!(1795, first_basement_index(INPUT), "input");
+
+    let Some(input) = test_input(2015, 1) else {
+        return;
+    };
+    assert_eq!(1795, first_basement_index(input.as_bytes()), "input");
 }
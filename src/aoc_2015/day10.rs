@@ -1,3 +1,10 @@
+use std::fmt::Display;
+
+use crate::util::{
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
+
 fn expand(sequence: Vec<u8>) -> Vec<u8> {
     let mut last: Option<u8> = None;
     let mut count = 1;
@@ -29,9 +36,36 @@ fn expand_n_times(n: u8, mut seq: Vec<u8>) -> usize {
     seq.len()
 }
 
+fn parse_sequence(input: &str) -> Result<Vec<u8>, ParseError> {
+    let digits = parse_chars(input, "a digit", |ch| ch.is_ascii_digit())?;
+    Ok(digits.bytes().map(|digit| digit - b'0').collect())
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<u8>;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_sequence(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        expand_n_times(40, input.clone())
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        expand_n_times(50, input.clone())
+    }
+}
+
+pub const INPUT: &str = "1321131112";
+
 #[test]
 fn both_parts() {
-    let input: Vec<u8> = vec![1, 3, 2, 1, 1, 3, 1, 1, 1, 2];
+    let input: Vec<u8> = parse_sequence(INPUT).unwrap();
     assert_eq!(492982, expand_n_times(40, input.clone()));
     assert_eq!(6989950, expand_n_times(50, input));
 }
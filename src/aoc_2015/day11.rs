@@ -1,3 +1,10 @@
+use std::fmt::Display;
+
+use crate::util::{
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
+
 enum Inc {
     Normal(u8),
     Carry(u8),
@@ -17,7 +24,7 @@ fn increment_single_value(value: u8) -> Inc {
     Inc::Normal(value + 1)
 }
 
-fn increment(password: &mut Vec<u8>) {
+fn increment(password: &mut [u8]) {
     for letter in password.iter_mut().rev() {
         match increment_single_value(*letter) {
             Inc::Normal(value) => {
@@ -31,15 +38,24 @@ fn increment(password: &mut Vec<u8>) {
     }
 }
 
-fn parse_input(input: &str) -> Vec<u8> {
-    input.chars().map(|ch| (ch as u8) - 96).collect()
+fn parse_input(input: &str) -> Result<Vec<u8>, ParseError> {
+    let password = parse_chars(input, "a lowercase letter", |ch| ch.is_ascii_lowercase())?;
+    // Anything shorter can't fit two pairs and a straight, so the search would never end
+    if password.len() != 8 {
+        return Err(ParseError::at(
+            input,
+            password,
+            "a password of eight letters",
+        ));
+    }
+    Ok(password.bytes().map(|ch| ch - 96).collect())
 }
 
-fn pass_string(vec: &Vec<u8>) -> String {
-    vec.into_iter().map(|ch| (ch + 96) as char).collect()
+fn pass_string(vec: &[u8]) -> String {
+    vec.iter().map(|ch| (ch + 96) as char).collect()
 }
 
-fn valid_password(password: &Vec<u8>) -> bool {
+fn valid_password(password: &[u8]) -> bool {
     let mut last: Option<u8> = None;
 
     let mut increment_chain = 0;
@@ -76,16 +92,54 @@ fn valid_password(password: &Vec<u8>) -> bool {
     }
 }
 
+fn next_valid_password(password: &mut [u8]) {
+    increment(password);
+    while !valid_password(password) {
+        increment(password);
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<u8>;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        let mut pass = input.clone();
+        next_valid_password(&mut pass);
+        pass_string(&pass)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        let mut pass = input.clone();
+        next_valid_password(&mut pass);
+        next_valid_password(&mut pass);
+        pass_string(&pass)
+    }
+}
+
+pub const INPUT: &str = "vzbxkghb";
+
 #[test]
 fn both_parts() {
-    let mut pass = parse_input("vzbxkghb");
-    while !valid_password(&pass) {
-        increment(&mut pass);
-    }
+    let mut pass = parse_input(INPUT).unwrap();
+    next_valid_password(&mut pass);
     assert_eq!("vzbxxyzz", pass_string(&pass), "part 1");
-    increment(&mut pass);
-    while !valid_password(&pass) {
-        increment(&mut pass);
-    }
+    next_valid_password(&mut pass);
     assert_eq!("vzcaabcc", pass_string(&pass), "part 2");
 }
+
+#[test]
+fn bad_input() {
+    assert!(parse_input("").is_err());
+    let err = parse_input("abcdefg").err().unwrap();
+    assert_eq!("a password of eight letters", err.expected);
+    let err = parse_input("abcDefgh").err().unwrap();
+    assert_eq!((1, 4), (err.line, err.column));
+}
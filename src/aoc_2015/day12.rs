@@ -1,9 +1,18 @@
-use logos::{Lexer, Logos};
+use std::{fmt::Display, slice::Iter};
+
+use logos::Logos;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, next_token_or_end},
+    solution::Solution,
+};
 
 #[derive(Logos)]
 #[logos(skip r"[^0-9\-\{\}]")]
-enum Token {
-    #[regex("-?[0-9]+", |lex| lex.slice().parse::<i32>().unwrap())]
+pub enum Token {
+    #[regex("-?[0-9]+", |lex| lex.slice().parse::<i32>().ok())]
     Number(i32),
 
     #[token("{")]
@@ -16,12 +25,43 @@ enum Token {
     RedValue,
 }
 
-fn extract_values(lex: &mut Lexer<'_, Token>) -> i32 {
+// Everything but the numbers, braces and red values is skipped, so the braces are checked to
+// match up for the nesting to mean anything
+fn parse_document(input: &str) -> Result<Vec<Token>, ParseError> {
+    let document = input.trim();
+    if !document.starts_with(['{', '[']) {
+        return Err(match document.chars().next() {
+            Some(ch) => ParseError::at(input, &document[..ch.len_utf8()], "a JSON object or array"),
+            None => ParseError::end_of_input(input, "a JSON object or array"),
+        });
+    }
+
+    let mut lex = Token::lexer(input);
+    let mut tokens = Vec::new();
+    let mut depth: usize = 0;
+    while let Some(token) = next_token_or_end(&mut lex, "a number")? {
+        match token {
+            Token::OpenBrace => depth += 1,
+            Token::CloseBrace => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| ParseError::new(input, lex.span(), "a `{` before this `}`"))?;
+            }
+            _ => {}
+        }
+        tokens.push(token);
+    }
+    if depth > 0 {
+        return Err(ParseError::end_of_input(input, "a `}` for every `{`"));
+    }
+    Ok(tokens)
+}
+
+fn extract_values(tokens: &mut Iter<'_, Token>) -> i32 {
     let mut block_total = 0;
 
     let mut found_red = false;
-    while let Some(token_res) = lex.next() {
-        let token = token_res.unwrap();
+    while let Some(token) = tokens.next() {
         match token {
             Token::RedValue => {
                 found_red = true;
@@ -29,7 +69,7 @@ fn extract_values(lex: &mut Lexer<'_, Token>) -> i32 {
             Token::Number(val) => block_total += val,
             Token::OpenBrace => {
                 // Wasted work if we've already found "red", but helps to keep track of depth.
-                block_total += extract_values(lex);
+                block_total += extract_values(tokens);
             }
             Token::CloseBrace => {
                 if found_red {
@@ -44,21 +84,60 @@ fn extract_values(lex: &mut Lexer<'_, Token>) -> i32 {
     block_total
 }
 
-const INPUT: &str = include_str!("./inputs/day12.txt");
-
-#[test]
-fn part_1() {
+fn sum_all_values(tokens: &[Token]) -> i32 {
     let mut total = 0;
-    for token in Token::lexer(INPUT) {
-        if let Ok(Token::Number(val)) = token {
+    for token in tokens {
+        if let Token::Number(val) = token {
             total += val
         }
     }
-    assert_eq!(111754, total);
+    total
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Token>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_document(input)
+    }
+
+    fn part_1(tokens: &Self::Input) -> impl Display {
+        sum_all_values(tokens)
+    }
+
+    fn part_2(tokens: &Self::Input) -> impl Display {
+        extract_values(&mut tokens.iter())
+    }
+}
+
+#[test]
+fn part_1() {
+    let Some(input) = test_input(2015, 12) else {
+        return;
+    };
+    assert_eq!(111754, sum_all_values(&parse_document(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    let mut lex = Token::lexer(INPUT);
-    assert_eq!(65402, extract_values(&mut lex));
+    let Some(input) = test_input(2015, 12) else {
+        return;
+    };
+    let tokens = parse_document(&input).unwrap();
+    assert_eq!(65402, extract_values(&mut tokens.iter()));
+}
+
+#[test]
+fn bad_input() {
+    assert!(parse_document("").is_err());
+    assert_eq!(
+        "a JSON object or array",
+        parse_document("garbage").err().unwrap().expected
+    );
+    let err = parse_document(r#"{"a":[1,2]}}"#).err().unwrap();
+    assert_eq!((1, 12), (err.line, err.column));
+    let err = parse_document(r#"{"a":{"b":4}"#).err().unwrap();
+    assert_eq!(None, err.found);
 }
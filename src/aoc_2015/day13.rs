@@ -1,13 +1,36 @@
-use std::collections::HashMap;
+use std::fmt::Display;
 
-use itertools::Itertools;
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    intern::Interner,
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+    tsp::{self, Goal, MAX_PLACES, Route},
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
+Alice would gain 54 happiness units by sitting next to Bob.
+Alice would lose 79 happiness units by sitting next to Carol.
+Alice would lose 2 happiness units by sitting next to David.
+Bob would gain 83 happiness units by sitting next to Alice.
+Bob would lose 7 happiness units by sitting next to Carol.
+Bob would lose 63 happiness units by sitting next to David.
+Carol would lose 62 happiness units by sitting next to Alice.
+Carol would gain 60 happiness units by sitting next to Bob.
+Carol would gain 55 happiness units by sitting next to David.
+David would gain 46 happiness units by sitting next to Alice.
+David would lose 7 happiness units by sitting next to Bob.
+David would gain 41 happiness units by sitting next to Carol.";
+
 #[derive(Logos)]
 #[logos(skip r"[\n. ]", skip r"happiness units by sitting next to")]
-enum Token {
-    #[regex("[A-Z][a-z]+", |lex| lex.slice().to_string())]
-    Name(String),
+enum Token<'s> {
+    #[regex("[A-Z][a-z]+")]
+    Name(&'s str),
 
     #[token("would gain")]
     Gain,
@@ -15,171 +38,207 @@ enum Token {
     #[token("would lose")]
     Lose,
 
-    #[regex("[0-9]+", |lex| lex.slice().parse::<i32>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<i32>().ok())]
     Value(i32),
 }
 
-impl Token {
-    fn take_name(self) -> Name {
+impl<'s> Token<'s> {
+    fn take_name(self) -> Option<&'s str> {
         match self {
-            Self::Name(name) => name.into(),
-            _ => panic!("Not a name"),
+            Self::Name(name) => Some(name),
+            _ => None,
         }
     }
 
-    fn take_value(self) -> i32 {
+    fn take_sign(self) -> Option<i32> {
         match self {
-            Self::Value(val) => val,
-            _ => panic!("Not a value"),
+            Self::Gain => Some(1),
+            Self::Lose => Some(-1),
+            _ => None,
+        }
+    }
+
+    fn take_value(self) -> Option<i32> {
+        match self {
+            Self::Value(val) => Some(val),
+            _ => None,
         }
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
-enum Name {
-    Alice,
-    Bob,
-    Carol,
-    David,
-    Eric,
-    Frank,
-    George,
-    Mallory,
-
-    // It's me, I'm the dud
-    Dud,
+// Every guest named in the input, with `happiness[a][b]` being how much happier guest `a` gets
+// from sitting next to guest `b`
+pub struct HappinessMatrix {
+    pub names: Vec<String>,
+    pub happiness: Vec<Vec<i32>>,
 }
 
-impl Name {
-    // Unlike Day 9, the permutations also require us to start and end on the same name,
-    // therefore it forms a perfect loop. That means we can reduce the total number of
-    // permutations by 8 (the count of elements) by keeping one name positioned statically
-    // (Alice) and only caring about the relative permutations of the remaining 7 names.
-    fn permutations() -> Vec<Vec<Name>> {
-        let base = vec![
-            Self::Bob,
-            Self::Carol,
-            Self::David,
-            Self::Eric,
-            Self::Frank,
-            Self::George,
-            Self::Mallory,
-        ];
-
-        base.into_iter()
-            .permutations(7)
-            .map(|mut v| {
-                v.push(Self::Alice);
-                v
-            })
-            .collect()
+fn parse_happiness_matrix(input: &str) -> Result<HappinessMatrix, ParseError> {
+    let mut names = Interner::new();
+    let mut feelings = Vec::new();
+
+    // Seating gets too slow to bother with past `MAX_PLACES`, and part 2 needs a seat for me
+    let mut intern = |name| match names.intern(name) {
+        idx if idx < MAX_PLACES - 1 => Ok(idx),
+        _ => Err(ParseError::at(
+            input,
+            name,
+            "one of the 15 guests already named",
+        )),
+    };
+    let mut lex = Token::lexer(input);
+    while let Some(first) = expect_token_or_end(&mut lex, "a name", Token::take_name)? {
+        let first = intern(first)?;
+        let mult = expect_token(&mut lex, "'would gain' or 'would lose'", Token::take_sign)?;
+        let value = mult * expect_token(&mut lex, "a happiness value", Token::take_value)?;
+        let second = intern(expect_token(&mut lex, "a name", Token::take_name)?)?;
+        feelings.push((first, second, value));
     }
+    let feelings = non_empty(input, feelings, "a name")?;
 
-    fn permutations_with_dud() -> Vec<Vec<Name>> {
-        let base = vec![
-            Self::Alice,
-            Self::Bob,
-            Self::Carol,
-            Self::David,
-            Self::Eric,
-            Self::Frank,
-            Self::George,
-            Self::Mallory,
-        ];
-
-        base.into_iter()
-            .permutations(8)
-            .map(|mut v| {
-                v.push(Self::Dud);
-                v
-            })
-            .collect()
+    let mut happiness = vec![vec![None; names.len()]; names.len()];
+    for (first, second, value) in feelings {
+        happiness[first][second] = Some(value);
     }
+    let happiness = happiness
+        .into_iter()
+        .enumerate()
+        .map(|(first, row)| {
+            row.into_iter()
+                .enumerate()
+                .map(|(second, value)| match value {
+                    Some(value) => Ok(value),
+                    None if first == second => Ok(0),
+                    None => Err(ParseError::end_of_input(
+                        input,
+                        "a happiness value for every pair of guests",
+                    )),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(HappinessMatrix {
+        names: names.into_names(),
+        happiness,
+    })
 }
 
-impl From<String> for Name {
-    fn from(value: String) -> Self {
-        match value.as_ref() {
-            "Alice" => Self::Alice,
-            "Bob" => Self::Bob,
-            "Carol" => Self::Carol,
-            "David" => Self::David,
-            "Eric" => Self::Eric,
-            "Frank" => Self::Frank,
-            "George" => Self::George,
-            "Mallory" => Self::Mallory,
-            _ => panic!("'{value}' is not a registered name"),
-        }
-    }
+#[derive(Debug, PartialEq, Eq)]
+pub enum Guest<'a> {
+    Named(&'a str),
+    // Doesn't mind who they sit next to, and nobody minds sitting next to them
+    Neutral,
 }
 
-type NameMap = HashMap<(Name, Name), i32>;
-
-fn parse_happiness_map(input: &str) -> NameMap {
-    let mut map: NameMap = HashMap::new();
-
-    let mut lex = Token::lexer(input);
-    while let Some(token) = lex.next() {
-        let first = token.unwrap().take_name();
-
-        let mult = match lex.next().unwrap().unwrap() {
-            Token::Gain => 1,
-            Token::Lose => -1,
-            _ => panic!("Expected 'gain' or 'lose' section"),
-        };
-
-        let value = mult * lex.next().unwrap().unwrap().take_value();
-        let second = lex.next().unwrap().unwrap().take_name();
-
-        map.insert((first, second), value);
-    }
-
-    map
+// The happiest seating around the table, starting from the first named guest and going round
+pub struct Seating<'a> {
+    pub happiness: i32,
+    pub order: Vec<Guest<'a>>,
 }
 
-// Just don't use this with the dud and you're gucci
-fn get_happiness(name_1: &Name, name_2: &Name, map: &NameMap) -> i32 {
-    let x = *map.get(&(name_1.clone(), name_2.clone())).unwrap();
-    let y = *map.get(&(name_2.clone(), name_1.clone())).unwrap();
-    x + y
+// Neutral guests are added after the named ones, so they sit at the indices past the matrix
+fn optimal_seating(matrix: &HappinessMatrix, neutral_guests: usize) -> Seating<'_> {
+    let named = matrix.names.len();
+    let size = named + neutral_guests;
+    // Both neighbours' feelings count, so a pair is worth the same whichever way round it is
+    let pairs: Vec<Vec<i32>> = (0..size)
+        .map(|a| {
+            (0..size)
+                .map(|b| {
+                    if a < named && b < named {
+                        matrix.happiness[a][b] + matrix.happiness[b][a]
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    // The table is round, so the seating is a cycle
+    let (happiness, order) = tsp::solve(&pairs, Route::Cycle, Goal::Longest, None)
+        .expect("there's at least one guest to seat");
+    let order = order
+        .into_iter()
+        .map(|guest| match matrix.names.get(guest) {
+            Some(name) => Guest::Named(name),
+            None => Guest::Neutral,
+        })
+        .collect();
+    Seating { happiness, order }
 }
 
-fn total_happiness(mut table: Vec<Name>, map: &NameMap, with_dud: bool) -> i32 {
-    let init = if with_dud {
-        0
-    } else {
-        get_happiness(table.first().unwrap(), table.last().unwrap(), map)
-    };
+pub struct Day13;
 
-    if with_dud {
-        table.pop();
+impl Solution for Day13 {
+    type Input = HappinessMatrix;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_happiness_matrix(input)
     }
 
-    let total = table
-        .windows(2)
-        .fold(init, |sum, win| sum + get_happiness(&win[0], &win[1], map));
+    fn part_1(matrix: &Self::Input) -> impl Display {
+        optimal_seating(matrix, 0).happiness
+    }
 
-    total
+    // I'm the extra guest, and I don't care who I sit next to
+    fn part_2(matrix: &Self::Input) -> impl Display {
+        optimal_seating(matrix, 1).happiness
+    }
 }
 
-const INPUT: &str = include_str!("./inputs/day13.txt");
+#[test]
+fn sample() {
+    use Guest::{Named, Neutral};
+
+    let matrix = parse_happiness_matrix(SAMPLE).unwrap();
+    assert_eq!(vec!["Alice", "Bob", "Carol", "David"], matrix.names);
+    assert_eq!(-79, matrix.happiness[0][2]);
+
+    // Going round either way is just as happy
+    let seating = optimal_seating(&matrix, 0);
+    assert_eq!(330, seating.happiness);
+    let clockwise = [Named("Alice"), Named("Bob"), Named("Carol"), Named("David")];
+    let anticlockwise = [Named("Alice"), Named("David"), Named("Carol"), Named("Bob")];
+    assert!(seating.order == clockwise || seating.order == anticlockwise);
+
+    assert_eq!(286, optimal_seating(&matrix, 1).happiness);
+    let seating = optimal_seating(&matrix, 2);
+    assert_eq!(4 + 2, seating.order.len());
+    assert_eq!(
+        2,
+        seating
+            .order
+            .iter()
+            .filter(|&guest| *guest == Neutral)
+            .count()
+    );
+
+    let too_many = ('b'..='p')
+        .map(|guest| {
+            format!("Guesta would gain 1 happiness units by sitting next to Guest{guest}.\n")
+        })
+        .collect::<String>();
+    let error = parse_happiness_matrix(&too_many).err().unwrap();
+    assert_eq!((15, 56), (error.line, error.column));
+    assert_eq!("one of the 15 guests already named", error.expected);
+}
 
 #[test]
 fn part_1() {
-    let map = parse_happiness_map(INPUT);
-    let mut optimal_happiness = 0;
-    for perm in Name::permutations() {
-        optimal_happiness = total_happiness(perm, &map, false).max(optimal_happiness);
-    }
-    assert_eq!(709, optimal_happiness);
+    let Some(input) = test_input(2015, 13) else {
+        return;
+    };
+    let matrix = parse_happiness_matrix(&input).unwrap();
+    assert_eq!(709, optimal_seating(&matrix, 0).happiness);
 }
 
 #[test]
 fn part_2() {
-    let map = parse_happiness_map(INPUT);
-    let mut optimal_happiness = 0;
-    for perm in Name::permutations_with_dud() {
-        optimal_happiness = total_happiness(perm, &map, true).max(optimal_happiness);
-    }
-    assert_eq!(668, optimal_happiness);
+    let Some(input) = test_input(2015, 13) else {
+        return;
+    };
+    let matrix = parse_happiness_matrix(&input).unwrap();
+    assert_eq!(668, optimal_seating(&matrix, 1).happiness);
 }
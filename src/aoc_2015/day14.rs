@@ -1,34 +1,46 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip "[A-Za-z\n ,/.]")]
 enum Token {
-    #[regex("[0-9]+", |lex| lex.slice().parse::<usize>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<usize>().ok())]
     Number(usize),
 }
 
 impl Token {
-    fn take_num(self) -> usize {
+    fn take_num(self) -> Option<usize> {
         match self {
-            Self::Number(val) => val,
+            Self::Number(val) => Some(val),
         }
     }
 }
 
-struct Reindeer {
+pub struct Reindeer {
     flight_speed: usize,
     flight_time: usize,
     rest_time: usize,
 }
 
-fn parse_reindeer(input: &str) -> Vec<Reindeer> {
+fn parse_reindeer(input: &str) -> Result<Vec<Reindeer>, ParseError> {
     let mut lex = Token::lexer(input);
 
     let mut reindeer = Vec::new();
-    while let Some(first_token) = lex.next() {
-        let flight_speed = first_token.unwrap().take_num();
-        let flight_time = lex.next().unwrap().unwrap().take_num();
-        let rest_time = lex.next().unwrap().unwrap().take_num();
+    while let Some(flight_speed) = expect_token_or_end(&mut lex, "a speed", Token::take_num)? {
+        let flight_time = expect_token(&mut lex, "a flight time", Token::take_num)?;
+        // Reindeer that never fly would never finish resting either
+        if flight_time == 0 {
+            return Err(ParseError::new(input, lex.span(), "a flight time above 0"));
+        }
+        let rest_time = expect_token(&mut lex, "a rest time", Token::take_num)?;
 
         reindeer.push(Reindeer {
             flight_speed,
@@ -37,7 +49,7 @@ fn parse_reindeer(input: &str) -> Vec<Reindeer> {
         });
     }
 
-    reindeer
+    non_empty(input, reindeer, "a speed")
 }
 
 fn get_distance(reindeer: &Reindeer, total_time: usize) -> usize {
@@ -50,23 +62,20 @@ fn get_distance(reindeer: &Reindeer, total_time: usize) -> usize {
     total_flight_time * reindeer.flight_speed
 }
 
-const INPUT: &str = include_str!("./inputs/day14.txt");
+const RACE_TIME: usize = 2503;
 
-#[test]
-fn part_1() {
+fn furthest_distance(reindeer: &[Reindeer], total_time: usize) -> usize {
     let mut max_dist = 0;
-    for reindeer in parse_reindeer(INPUT) {
-        max_dist = get_distance(&reindeer, 2503).max(max_dist);
+    for reindeer in reindeer {
+        max_dist = get_distance(reindeer, total_time).max(max_dist);
     }
-    assert_eq!(2655, max_dist);
+    max_dist
 }
 
-#[test]
-fn part_2() {
-    let reindeer = parse_reindeer(INPUT);
-    let mut scores: Vec<u16> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0];
+fn highest_score(reindeer: &[Reindeer], total_time: usize) -> u16 {
+    let mut scores: Vec<u16> = vec![0; reindeer.len()];
 
-    for s in 0..2503 {
+    for s in 0..total_time {
         let mut max_dist = 0;
         let mut winner_idx = 0;
 
@@ -81,5 +90,45 @@ fn part_2() {
         scores[winner_idx] += 1;
     }
 
-    assert_eq!(Some(1059), scores.into_iter().max());
+    scores.into_iter().max().unwrap_or(0)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Reindeer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_reindeer(input)
+    }
+
+    fn part_1(reindeer: &Self::Input) -> impl Display {
+        furthest_distance(reindeer, RACE_TIME)
+    }
+
+    fn part_2(reindeer: &Self::Input) -> impl Display {
+        highest_score(reindeer, RACE_TIME)
+    }
+}
+
+#[test]
+fn part_1() {
+    let Some(input) = test_input(2015, 14) else {
+        return;
+    };
+    assert_eq!(
+        2655,
+        furthest_distance(&parse_reindeer(&input).unwrap(), RACE_TIME)
+    );
+}
+
+#[test]
+fn part_2() {
+    let Some(input) = test_input(2015, 14) else {
+        return;
+    };
+    assert_eq!(
+        1059,
+        highest_score(&parse_reindeer(&input).unwrap(), RACE_TIME)
+    );
 }
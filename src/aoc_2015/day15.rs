@@ -1,15 +1,69 @@
-use std::ops::Add;
-
-struct Behavior {
-    capacity: i32,
-    durability: i32,
-    flavor: i32,
-    texture: i32,
-    calories: i32,
+use std::{fmt::Display, ops::Add};
+
+use logos::Logos;
+
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
+Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3";
+
+#[derive(Logos)]
+#[logos(skip r"[ \n,:]+")]
+enum Token<'s> {
+    #[regex("[A-Za-z]+")]
+    Name(&'s str),
+
+    #[token("capacity")]
+    Capacity,
+
+    #[token("durability")]
+    Durability,
+
+    #[token("flavor")]
+    Flavor,
+
+    #[token("texture")]
+    Texture,
+
+    #[token("calories")]
+    Calories,
+
+    #[regex("-?[0-9]+", |lex| lex.slice().parse::<i64>().ok())]
+    Number(i64),
+}
+
+impl<'s> Token<'s> {
+    fn take_name(self) -> Option<&'s str> {
+        match self {
+            Self::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn take_number(self) -> Option<i64> {
+        match self {
+            Self::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Behavior {
+    capacity: i64,
+    durability: i64,
+    flavor: i64,
+    texture: i64,
+    calories: i64,
 }
 
 impl Behavior {
-    fn total(&self, count: i32) -> Behavior {
+    fn total(self, count: i64) -> Behavior {
         Behavior {
             capacity: self.capacity * count,
             durability: self.durability * count,
@@ -19,7 +73,7 @@ impl Behavior {
         }
     }
 
-    fn to_score(&self) -> i32 {
+    fn to_score(self) -> i64 {
         self.capacity.max(0) * self.durability.max(0) * self.flavor.max(0) * self.texture.max(0)
     }
 }
@@ -38,87 +92,188 @@ impl Add for Behavior {
     }
 }
 
-/* It's just 4 lines, so it's definitely faster to encode direction than to parse it.
- * The input:
+pub struct Ingredient {
+    pub name: String,
+    behavior: Behavior,
+}
+
+fn parse_ingredients(input: &str) -> Result<Vec<Ingredient>, ParseError> {
+    let mut ingredients = Vec::new();
+
+    let mut lex = Token::lexer(input);
+    while let Some(name) = expect_token_or_end(&mut lex, "an ingredient", Token::take_name)? {
+        // Every property is there, in the same order, on every line
+        let mut property = |expected, take: fn(Token) -> bool| {
+            expect_token(&mut lex, expected, |token| take(token).then_some(()))?;
+            expect_token(&mut lex, "a number", Token::take_number)
+        };
+        let behavior = Behavior {
+            capacity: property("`capacity`", |token| matches!(token, Token::Capacity))?,
+            durability: property("`durability`", |token| matches!(token, Token::Durability))?,
+            flavor: property("`flavor`", |token| matches!(token, Token::Flavor))?,
+            texture: property("`texture`", |token| matches!(token, Token::Texture))?,
+            calories: property("`calories`", |token| matches!(token, Token::Calories))?,
+        };
+        ingredients.push(Ingredient {
+            name: name.to_string(),
+            behavior,
+        });
+    }
+
+    non_empty(input, ingredients, "an ingredient")
+}
+
+const TEASPOONS: i64 = 100;
+
+// How many teaspoons of each ingredient to use, in the same order as the ingredients
+#[derive(Debug, PartialEq, Eq)]
+pub struct Recipe {
+    pub amounts: Vec<i64>,
+    pub score: i64,
+}
+
+// Tries every way of splitting what's left between the ingredients from `index` onwards, with the
+// last one taking whatever remains
+fn search_recipes(
+    ingredients: &[Ingredient],
+    calories: Option<i64>,
+    index: usize,
+    remaining: i64,
+    mixed: Behavior,
+    amounts: &mut Vec<i64>,
+    best: &mut Option<Recipe>,
+) {
+    let behavior = ingredients[index].behavior;
+    let counts = if index + 1 == ingredients.len() {
+        remaining..=remaining
+    } else {
+        0..=remaining
+    };
+
+    for count in counts {
+        let mixed = mixed + behavior.total(count);
+        amounts.push(count);
+        if index + 1 < ingredients.len() {
+            search_recipes(
+                ingredients,
+                calories,
+                index + 1,
+                remaining - count,
+                mixed,
+                amounts,
+                best,
+            );
+        } else if calories.is_none_or(|calories| mixed.calories == calories) {
+            let score = mixed.to_score();
+            if best.as_ref().is_none_or(|best| score > best.score) {
+                *best = Some(Recipe {
+                    amounts: amounts.clone(),
+                    score,
+                });
+            }
+        }
+        amounts.pop();
+    }
+}
+
+// The highest scoring recipe using exactly `TEASPOONS` teaspoons in total, or `None` if there are
+// no ingredients or no recipe has exactly `calories` calories
+fn best_recipe(ingredients: &[Ingredient], calories: Option<i64>) -> Option<Recipe> {
+    if ingredients.is_empty() {
+        return None;
+    }
+    let mut best = None;
+    search_recipes(
+        ingredients,
+        calories,
+        0,
+        TEASPOONS,
+        Behavior::default(),
+        &mut Vec::with_capacity(ingredients.len()),
+        &mut best,
+    );
+    best
+}
 
+fn highest_score(ingredients: &[Ingredient], calories: Option<i64>) -> i64 {
+    best_recipe(ingredients, calories)
+        .expect("no recipe has the right number of calories")
+        .score
+}
+
+pub const INPUT: &str = "
 Frosting: capacity 4, durability -2, flavor 0, texture 0, calories 5
 Candy: capacity 0, durability 5, flavor -1, texture 0, calories 8
 Butterscotch: capacity -1, durability 0, flavor 5, texture 0, calories 6
-Sugar: capacity 0, durability 0, flavor -2, texture 2, calories 1
+Sugar: capacity 0, durability 0, flavor -2, texture 2, calories 1";
 
- */
+pub struct Day15;
 
-const FROSTING: Behavior = Behavior {
-    capacity: 4,
-    durability: -2,
-    flavor: 0,
-    texture: 0,
-    calories: 5,
-};
+impl Solution for Day15 {
+    type Input = Vec<Ingredient>;
 
-const CANDY: Behavior = Behavior {
-    capacity: 0,
-    durability: 5,
-    flavor: -1,
-    texture: 0,
-    calories: 8,
-};
+    const INPUT: Option<&'static str> = Some(INPUT);
 
-const BUTTERSCOTCH: Behavior = Behavior {
-    capacity: -1,
-    durability: 0,
-    flavor: 5,
-    texture: 0,
-    calories: 6,
-};
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_ingredients(input)
+    }
 
-const SUGAR: Behavior = Behavior {
-    capacity: 0,
-    durability: 0,
-    flavor: -2,
-    texture: 2,
-    calories: 1,
-};
+    fn part_1(ingredients: &Self::Input) -> impl Display {
+        highest_score(ingredients, None)
+    }
 
-const LIMIT: i32 = 101;
+    fn part_2(ingredients: &Self::Input) -> impl Display {
+        highest_score(ingredients, Some(500))
+    }
+}
 
 #[test]
-fn part_1() {
-    let mut high_score = 0;
-    for a in 0..LIMIT {
-        for b in 0..(LIMIT - a) {
-            for c in 0..(LIMIT - a - b) {
-                let d = LIMIT - a - b - c - 1;
+fn samples() {
+    let ingredients = parse_ingredients(SAMPLE).unwrap();
+    assert_eq!("Cinnamon", ingredients[1].name);
+    assert_eq!(
+        Some(Recipe {
+            amounts: vec![44, 56],
+            score: 62842880
+        }),
+        best_recipe(&ingredients, None)
+    );
+    assert_eq!(
+        Some(Recipe {
+            amounts: vec![40, 60],
+            score: 57600000
+        }),
+        best_recipe(&ingredients, Some(500))
+    );
+    assert_eq!(None, best_recipe(&ingredients, Some(1)));
+    assert_eq!(None, best_recipe(&[], None));
 
-                let cur_score =
-                    (FROSTING.total(a) + CANDY.total(b) + BUTTERSCOTCH.total(c) + SUGAR.total(d))
-                        .to_score();
+    // 1000 of every property multiplies out to far more than fits in 32 bits
+    let ingredients =
+        parse_ingredients("Lard: capacity 10, durability 10, flavor 10, texture 10, calories 1")
+            .unwrap();
+    assert_eq!(1_000_000_000_000, highest_score(&ingredients, None));
 
-                high_score = high_score.max(cur_score);
-            }
-        }
-    }
+    let ingredients = parse_ingredients(
+        "PeanutButter: capacity 5, durability -1, flavor 0, texture 0, calories 5",
+    )
+    .unwrap();
+    assert_eq!("PeanutButter", ingredients[0].name);
 
-    assert_eq!(18965440, high_score);
+    let error = parse_ingredients("Sugar: capacity 0, flavor -2")
+        .err()
+        .unwrap();
+    assert_eq!("`durability`", error.expected);
 }
 
 #[test]
-fn part_2() {
-    let mut high_score = 0;
-    for a in 0..LIMIT {
-        for b in 0..(LIMIT - a) {
-            for c in 0..(LIMIT - a - b) {
-                let d = LIMIT - a - b - c - 1;
-
-                let cur_behavior =
-                    FROSTING.total(a) + CANDY.total(b) + BUTTERSCOTCH.total(c) + SUGAR.total(d);
-
-                if cur_behavior.calories == 500 {
-                    high_score = high_score.max(cur_behavior.to_score());
-                }
-            }
-        }
-    }
+fn part_1() {
+    let ingredients = parse_ingredients(INPUT).unwrap();
+    assert_eq!(18965440, highest_score(&ingredients, None));
+}
 
-    assert_eq!(15862900, high_score);
+#[test]
+fn part_2() {
+    let ingredients = parse_ingredients(INPUT).unwrap();
+    assert_eq!(15862900, highest_score(&ingredients, Some(500)));
 }
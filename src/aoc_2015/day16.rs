@@ -1,9 +1,18 @@
+use std::{cmp::Reverse, fmt::Display};
+
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip "[ :\n,]", skip "Sue")]
 enum Token {
-    #[regex("[0-9]+", |lex| lex.slice().parse::<u16>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<u16>().ok())]
     Count(u16),
 
     #[regex("[a-z]+", |lex| lex.slice().to_owned())]
@@ -11,120 +20,318 @@ enum Token {
 }
 
 impl Token {
-    fn to_compound(self, count: u16) -> Compound {
+    fn take_compound(self) -> Option<Compound> {
         match self {
-            Self::Compound(s) => Compound::from_string(s, count),
-            _ => panic!("This is a number, numb-nuts!"),
+            Self::Compound(s) => Compound::from_name(&s),
+            _ => None,
         }
     }
 
-    fn take_count(self) -> u16 {
+    fn take_count(self) -> Option<u16> {
         match self {
-            Self::Count(v) => v,
-            _ => panic!("This is not a number, silly-pants!"),
+            Self::Count(v) => Some(v),
+            _ => None,
         }
     }
 }
 
-enum Compound {
-    Children(u16),
-    Cats(u16),
-    Samoyeds(u16),
-    Pomeranians(u16),
-    Akitas(u16),
-    Vizslas(u16),
-    Goldfish(u16),
-    Trees(u16),
-    Cars(u16),
-    Perfumes(u16),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compound {
+    Children,
+    Cats,
+    Samoyeds,
+    Pomeranians,
+    Akitas,
+    Vizslas,
+    Goldfish,
+    Trees,
+    Cars,
+    Perfumes,
 }
 
 impl Compound {
-    fn from_string(value: String, count: u16) -> Self {
-        match value.as_ref() {
-            "children" => Self::Children(count),
-            "cats" => Self::Cats(count),
-            "samoyeds" => Self::Samoyeds(count),
-            "pomeranians" => Self::Pomeranians(count),
-            "akitas" => Self::Akitas(count),
-            "vizslas" => Self::Vizslas(count),
-            "goldfish" => Self::Goldfish(count),
-            "trees" => Self::Trees(count),
-            "cars" => Self::Cars(count),
-            "perfumes" => Self::Perfumes(count),
-            _ => panic!("'{value}' is not a known compount"),
+    fn from_name(value: &str) -> Option<Self> {
+        match value {
+            "children" => Some(Self::Children),
+            "cats" => Some(Self::Cats),
+            "samoyeds" => Some(Self::Samoyeds),
+            "pomeranians" => Some(Self::Pomeranians),
+            "akitas" => Some(Self::Akitas),
+            "vizslas" => Some(Self::Vizslas),
+            "goldfish" => Some(Self::Goldfish),
+            "trees" => Some(Self::Trees),
+            "cars" => Some(Self::Cars),
+            "perfumes" => Some(Self::Perfumes),
+            _ => None,
         }
     }
+}
+
+// How an aunt's remembered count has to compare to the ticker's reading to agree with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Greater,
+    Less,
+}
 
-    fn matches_exact(&self) -> bool {
+impl Comparison {
+    fn holds(self, remembered: u16, reading: u16) -> bool {
         match self {
-            Self::Children(v) => *v == 3,
-            Self::Cats(v) => *v == 7,
-            Self::Samoyeds(v) => *v == 2,
-            Self::Pomeranians(v) => *v == 3,
-            Self::Akitas(v) => *v == 0,
-            Self::Vizslas(v) => *v == 0,
-            Self::Goldfish(v) => *v == 5,
-            Self::Trees(v) => *v == 3,
-            Self::Cars(v) => *v == 2,
-            Self::Perfumes(v) => *v == 1,
+            Self::Equal => remembered == reading,
+            Self::Greater => remembered > reading,
+            Self::Less => remembered < reading,
         }
     }
+}
 
-    fn matches_with_ranges(&self) -> bool {
-        match self {
-            Self::Children(v) => *v == 3,
-            Self::Cats(v) => *v > 7,
-            Self::Samoyeds(v) => *v == 2,
-            Self::Pomeranians(v) => *v < 3,
-            Self::Akitas(v) => *v == 0,
-            Self::Vizslas(v) => *v == 0,
-            Self::Goldfish(v) => *v < 5,
-            Self::Trees(v) => *v > 3,
-            Self::Cars(v) => *v == 2,
-            Self::Perfumes(v) => *v == 1,
+// What the ticker tape read for each compound, and how to compare it. Compounds without a
+// reading can't agree or disagree with anything
+pub struct Ticker {
+    readings: [Option<(u16, Comparison)>; 10],
+}
+
+impl Ticker {
+    // Every reading starts off needing an exact match
+    fn new(readings: &[(Compound, u16)]) -> Self {
+        let mut ticker = Self {
+            readings: [None; 10],
+        };
+        for &(compound, count) in readings {
+            ticker.readings[compound as usize] = Some((count, Comparison::Equal));
+        }
+        ticker
+    }
+
+    // A rule for a compound the ticker didn't read would never be checked, so the compound is
+    // handed back instead of quietly dropping the rule
+    fn with_rules(mut self, rules: &[(Compound, Comparison)]) -> Result<Self, Compound> {
+        for &(compound, rule) in rules {
+            let Some((_, comparison)) = &mut self.readings[compound as usize] else {
+                return Err(compound);
+            };
+            *comparison = rule;
         }
+        Ok(self)
+    }
+
+    fn agrees(&self, compound: Compound, remembered: u16) -> Option<bool> {
+        let (reading, comparison) = self.readings[compound as usize]?;
+        Some(comparison.holds(remembered, reading))
     }
 }
 
-// What a silly name for a struct
-// struct AuntSue {
-//     aunt_id: u16,
-//     known_compounds: [Compound; 3],
-// }
+pub const TICKER_TAPE: &str = "
+children: 3
+cats: 7
+samoyeds: 2
+pomeranians: 3
+akitas: 0
+vizslas: 0
+goldfish: 5
+trees: 3
+cars: 2
+perfumes: 1";
 
-fn find_valid_aunt(input: &str, matcher: fn(&Compound) -> bool) -> Option<u16> {
+// Part 2's outdated retroencabulator gives ranges for these instead of exact counts
+const RANGE_RULES: [(Compound, Comparison); 4] = [
+    (Compound::Cats, Comparison::Greater),
+    (Compound::Trees, Comparison::Greater),
+    (Compound::Pomeranians, Comparison::Less),
+    (Compound::Goldfish, Comparison::Less),
+];
+
+fn parse_ticker(input: &str) -> Result<Ticker, ParseError> {
     let mut lex = Token::lexer(input);
+    let mut readings = Vec::new();
+    while let Some(compound) =
+        expect_token_or_end(&mut lex, "a known compound", Token::take_compound)?
+    {
+        readings.push((
+            compound,
+            expect_token(&mut lex, "a count", Token::take_count)?,
+        ));
+    }
+    Ok(Ticker::new(&readings))
+}
+
+// What a silly name for a struct
+pub struct AuntSue {
+    aunt_id: u16,
+    known_compounds: Vec<(Compound, u16)>,
+}
 
-    while let Some(tok_res) = lex.next() {
-        let aunt_id = tok_res.unwrap().take_count();
+fn parse_aunts(input: &str) -> Result<Vec<AuntSue>, ParseError> {
+    let mut lex = Token::lexer(input);
+    let mut aunts = Vec::new();
 
+    while let Some(aunt_id) = expect_token_or_end(&mut lex, "an aunt's number", Token::take_count)?
+    {
         // Every aunt has 3 pairs of compound/count
-        let mut still_valid = true;
+        let mut known_compounds = Vec::with_capacity(3);
         for _ in 0..3 {
-            let compound_token = lex.next().unwrap().unwrap();
-            let count = lex.next().unwrap().unwrap().take_count();
+            let compound = expect_token(&mut lex, "a known compound", Token::take_compound)?;
+            let count = expect_token(&mut lex, "a count", Token::take_count)?;
+            known_compounds.push((compound, count));
+        }
+
+        aunts.push(AuntSue {
+            aunt_id,
+            known_compounds,
+        });
+    }
+
+    if aunts.is_empty() {
+        return Err(ParseError::end_of_input(input, "an aunt's number"));
+    }
+    Ok(aunts)
+}
+
+// How many of an aunt's remembered compounds agree with the ticker, and how many don't
+#[derive(Debug, PartialEq, Eq)]
+pub struct Candidate {
+    pub aunt_id: u16,
+    pub matches: usize,
+    pub mismatches: usize,
+}
 
-            if still_valid && !matcher(&compound_token.to_compound(count)) {
-                still_valid = false;
+impl Candidate {
+    fn is_consistent(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+// Every aunt, most likely first: fewest mismatches, then most matches, then lowest number
+fn find_valid_aunt(aunts: &[AuntSue], ticker: &Ticker) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = aunts
+        .iter()
+        .map(|aunt| {
+            let (mut matches, mut mismatches) = (0, 0);
+            for &(compound, remembered) in &aunt.known_compounds {
+                match ticker.agrees(compound, remembered) {
+                    Some(true) => matches += 1,
+                    Some(false) => mismatches += 1,
+                    None => {}
+                }
             }
-        }
+            Candidate {
+                aunt_id: aunt.aunt_id,
+                matches,
+                mismatches,
+            }
+        })
+        .collect();
+    candidates.sort_by_key(|candidate| {
+        (
+            candidate.mismatches,
+            Reverse(candidate.matches),
+            candidate.aunt_id,
+        )
+    });
+    candidates
+}
 
-        if still_valid {
-            return Some(aunt_id);
-        }
+fn best_aunt(aunts: &[AuntSue], ticker: &Ticker) -> Option<u16> {
+    find_valid_aunt(aunts, ticker)
+        .into_iter()
+        .find(Candidate::is_consistent)
+        .map(|candidate| candidate.aunt_id)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<AuntSue>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_aunts(input)
     }
 
-    None
+    fn part_1(input: &Self::Input) -> impl Display {
+        let ticker = parse_ticker(TICKER_TAPE).unwrap();
+        best_aunt(input, &ticker).expect("no aunt agrees with the ticker tape")
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        let ticker = parse_ticker(TICKER_TAPE)
+            .unwrap()
+            .with_rules(&RANGE_RULES)
+            .expect("the ticker tape reads every compound with a range");
+        best_aunt(input, &ticker).expect("no aunt agrees with the ticker tape")
+    }
 }
 
-const INPUT: &str = include_str!("./inputs/day16.txt");
+#[test]
+fn ranking() {
+    let aunts = parse_aunts(
+        "Sue 1: cats: 7, trees: 3, cars: 9
+Sue 2: cats: 8, trees: 4, goldfish: 4
+Sue 3: cats: 7, trees: 3, cars: 2
+Sue 4: perfumes: 1, akitas: 0, cars: 2",
+    )
+    .unwrap();
+    let exact = parse_ticker(TICKER_TAPE).unwrap();
+    let candidates = find_valid_aunt(&aunts, &exact);
+    assert_eq!(
+        vec![3, 4, 1, 2],
+        candidates
+            .iter()
+            .map(|candidate| candidate.aunt_id)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Candidate {
+            aunt_id: 1,
+            matches: 2,
+            mismatches: 1
+        },
+        candidates[2]
+    );
+    assert_eq!(Some(3), best_aunt(&aunts, &exact));
+
+    let ranged = parse_ticker(TICKER_TAPE)
+        .unwrap()
+        .with_rules(&RANGE_RULES)
+        .unwrap();
+    assert_eq!(Some(2), best_aunt(&aunts, &ranged));
+
+    // Compounds the ticker didn't read don't count either way
+    let partial = parse_ticker("cats: 7").unwrap();
+    assert_eq!(
+        Candidate {
+            aunt_id: 4,
+            matches: 0,
+            mismatches: 0
+        },
+        find_valid_aunt(&aunts, &partial)[2]
+    );
+    assert_eq!(
+        Compound::Trees,
+        partial.with_rules(&RANGE_RULES).err().unwrap()
+    );
+}
 
 #[test]
 fn both_parts() {
-    assert_eq!(Some(40), find_valid_aunt(INPUT, Compound::matches_exact));
+    let Some(input) = test_input(2015, 16) else {
+        return;
+    };
+    let aunts = parse_aunts(&input).unwrap();
+    let ticker = parse_ticker(TICKER_TAPE).unwrap();
+    assert_eq!(Some(40), best_aunt(&aunts, &ticker));
     assert_eq!(
         Some(241),
-        find_valid_aunt(INPUT, Compound::matches_with_ranges)
+        best_aunt(&aunts, &ticker.with_rules(&RANGE_RULES).unwrap())
     );
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_aunts("Sue 1: cars: 9, akitas: 3, goldfish: 0\nSue 2: dogs: 1")
+        .err()
+        .unwrap();
+    assert_eq!((2, 8), (err.line, err.column));
+    assert_eq!("a known compound", err.expected);
+    let err = parse_aunts("\n").err().unwrap();
+    assert_eq!("an aunt's number", err.expected);
+}
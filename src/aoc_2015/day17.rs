@@ -1,12 +1,21 @@
-fn parse_containers(input: &str) -> Vec<usize> {
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+fn parse_containers(input: &str) -> Result<Vec<usize>, ParseError> {
     let mut v: Vec<usize> = input
         .trim()
         .split('\n')
-        .map(|seg| seg.parse::<usize>().unwrap())
-        .collect();
+        .map(|seg| parse_at(input, seg.trim(), "a container size"))
+        .collect::<Result<_, _>>()?;
     v.sort();
     v.reverse();
-    v
+    Ok(v)
 }
 
 fn count_containers(
@@ -46,11 +55,36 @@ fn count_containers(
     total
 }
 
-const INPUT: &str = include_str!("./inputs/day17.txt");
+fn count_combinations(containers: &[usize]) -> (usize, usize) {
+    let mut tracker = (usize::MAX, 0);
+    let total = count_containers(150, 0, containers, &mut tracker);
+    (total, tracker.1)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_containers(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_combinations(input).0
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        count_combinations(input).1
+    }
+}
 
 #[test]
 fn both_parts() {
-    let containers = parse_containers(INPUT);
+    let Some(input) = test_input(2015, 17) else {
+        return;
+    };
+    let containers = parse_containers(&input).unwrap();
     let mut tracker = (usize::MAX, 0);
     assert_eq!(654, count_containers(150, 0, &containers, &mut tracker));
     assert_eq!(57, tracker.1);
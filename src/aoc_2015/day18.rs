@@ -1,110 +1,80 @@
 // Didn't have to read far to see I was going to implement Conway's Game of Life
 // Rest in Piece, hero
 
-use std::collections::HashMap;
+use std::fmt::Display;
 
-use crate::util::Pair;
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{Pair, grid::Grid, parse::ParseError, solution::Solution};
 
-type BoardState = [[bool; 100]; 100];
+pub type BoardState = Grid<bool>;
 
-impl Pair {
-    // They won't all fit on the board, but we can just ignore the
-    // out-of-bounds cells tha are calculated
-    pub fn neighbors(self) -> [Pair; 8] {
-        let Pair(x, y) = self;
-        [
-            Pair(x + 1, y - 1),
-            Pair(x + 1, y),
-            Pair(x + 1, y + 1),
-            Pair(x, y + 1),
-            Pair(x - 1, y + 1),
-            Pair(x - 1, y),
-            Pair(x - 1, y - 1),
-            Pair(x, y - 1),
-        ]
-    }
+fn parse_config(input: &str) -> Result<BoardState, ParseError> {
+    Grid::parse(input, "`.` or `#`", |ch| match ch {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })
 }
 
-fn parse_config(input: &str) -> BoardState {
-    let mut b: BoardState = [[false; 100]; 100];
-    // let mut cur_line = Vec::with_capacity(100);
-    let mut line_num = 0;
-    let mut col_num = 0;
+fn next_state(b: &BoardState, broken: bool) -> BoardState {
+    let mut next_board = Grid::from_fn(b.width(), b.height(), |p| {
+        let n_count = b.neighbors8(p).filter(|&n| b[n]).count();
+        if b[p] {
+            n_count == 2 || n_count == 3
+        } else {
+            n_count == 3
+        }
+    });
 
-    for char in input.trim().chars() {
-        match char {
-            '.' => b[line_num][col_num] = false,
-            '#' => b[line_num][col_num] = true,
-            '\n' => {
-                col_num = 0;
-                line_num += 1;
-                continue;
-            }
-            _ => unreachable!(),
+    if broken {
+        let (right, bottom) = (b.width() as i64 - 1, b.height() as i64 - 1);
+        for corner in [
+            Pair(0, 0),
+            Pair(right, 0),
+            Pair(0, bottom),
+            Pair(right, bottom),
+        ] {
+            next_board[corner] = true;
         }
-        col_num += 1;
     }
 
-    b
+    next_board
 }
 
-fn next_state(b: BoardState, broken: bool) -> BoardState {
-    let mut neighbor_map = HashMap::<Pair, u8>::new();
-
-    for (x, line) in b.iter().enumerate() {
-        for (y, cell) in line.iter().enumerate() {
-            if *cell {
-                let p = Pair(x as i64, y as i64);
-                for neighbor in p.neighbors() {
-                    neighbor_map
-                        .entry(neighbor)
-                        .and_modify(|x| *x += 1)
-                        .or_insert(1);
-                }
-            }
-        }
+fn get_total_lights(config: &BoardState, broken: bool) -> usize {
+    let mut b = config.clone();
+    for _ in 0..100 {
+        b = next_state(&b, broken);
     }
 
-    let mut next_board = [[false; 100]; 100];
-
-    for (x, line) in b.into_iter().enumerate() {
-        for (y, cell) in line.into_iter().enumerate() {
-            let key = Pair(x as i64, y as i64);
-            let n_count = *neighbor_map.get(&key).unwrap_or(&0);
-            if cell {
-                next_board[x][y] = n_count == 2 || n_count == 3;
-            } else {
-                next_board[x][y] = n_count == 3;
-            }
-        }
-    }
+    b.values().filter(|&&lit| lit).count()
+}
 
-    if broken {
-        next_board[0][0] = true;
-        next_board[0][99] = true;
-        next_board[99][0] = true;
-        next_board[99][99] = true;
-    }
+pub struct Day18;
 
-    next_board
-}
+impl Solution for Day18 {
+    type Input = BoardState;
 
-const INPUT: &str = include_str!("./inputs/day18.txt");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_config(input)
+    }
 
-fn get_total_lights(broken: bool) -> usize {
-    let mut b = parse_config(INPUT);
-    for _ in 0..100 {
-        b = next_state(b, broken);
+    fn part_1(input: &Self::Input) -> impl Display {
+        get_total_lights(input, false)
     }
 
-    b.into_iter().fold(0, |outer_sum, line| {
-        line.into_iter()
-            .fold(outer_sum, |sum, v| if v { sum + 1 } else { sum })
-    })
+    fn part_2(input: &Self::Input) -> impl Display {
+        get_total_lights(input, true)
+    }
 }
 
 #[test]
 fn both_parts() {
-    assert_eq!(1061, get_total_lights(false));
-    assert_eq!(1006, get_total_lights(true));
+    let Some(input) = test_input(2015, 18) else {
+        return;
+    };
+    let config = parse_config(&input).unwrap();
+    assert_eq!(1061, get_total_lights(&config, false));
+    assert_eq!(1006, get_total_lights(&config, true));
 }
@@ -1,10 +1,17 @@
+use std::fmt::Display;
+
 use itertools::Itertools;
 use logos::Logos;
-use std::{
-    collections::{HashMap, HashSet, hash_map::Entry},
-    usize,
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, next_token, next_token_or_end},
+    solution::Solution,
 };
 
+#[cfg(test)]
 const SAMPLE: &str = "
 e => H
 e => O
@@ -14,9 +21,7 @@ O => HH
 
 HOHOHO";
 
-const INPUT: &str = include_str!("./inputs/day19.txt");
-
-type TransformMap = HashMap<String, Vec<String>>;
+pub type TransformMap = HashMap<String, Vec<String>>;
 
 #[derive(Logos)]
 #[logos(skip "[ =>\n]")]
@@ -32,28 +37,29 @@ impl Token {
     }
 }
 
-fn build_transform_map(input: &str) -> (String, TransformMap) {
+// The starting sequence is the only word left without a replacement after it
+fn build_transform_map(input: &str) -> Result<(String, TransformMap), ParseError> {
     let mut h: TransformMap = HashMap::new();
 
-    let mut parts = input.trim().split("\n\n");
-    let mappings = parts.next().unwrap();
-    let init_sequence = parts.next().unwrap();
-
-    let mut lex = Token::lexer(mappings);
-    while let Some(tok) = lex.next() {
-        let key = tok.unwrap().take();
-        let val = lex.next().unwrap().unwrap().take();
+    let mut lex = Token::lexer(input);
+    loop {
+        let key = next_token(&mut lex, "a molecule")?.take();
+        let Some(val) = next_token_or_end(&mut lex, "a replacement")? else {
+            // Fabricating the medicine starts from a single electron
+            if !h.contains_key("e") {
+                return Err(ParseError::end_of_input(input, "a replacement for `e`"));
+            }
+            return Ok((key, h));
+        };
         match h.entry(key) {
             Entry::Occupied(mut e) => {
-                e.get_mut().push(val);
+                e.get_mut().push(val.take());
             }
             Entry::Vacant(e) => {
-                e.insert(vec![val]);
+                e.insert(vec![val.take()]);
             }
         }
     }
-
-    (init_sequence.to_owned(), h)
 }
 
 fn transformations(sequence: String, map: &TransformMap) -> HashSet<String> {
@@ -85,22 +91,20 @@ fn transformations(sequence: String, map: &TransformMap) -> HashSet<String> {
     s
 }
 
-fn calibrate(input: &str) -> usize {
-    let (sequence, map) = build_transform_map(input);
-    let results = transformations(sequence, &map);
+fn calibrate((sequence, map): &(String, TransformMap)) -> usize {
+    let results = transformations(sequence.clone(), map);
     results.len()
 }
 
-fn fabricate(input: &str) -> usize {
-    let (sequence, map) = build_transform_map(input);
+fn fabricate((sequence, map): &(String, TransformMap)) -> usize {
     let mut inverse_map = HashMap::<String, String>::new();
-    for (reduced_molecule, original_molecules) in map.into_iter() {
+    for (reduced_molecule, original_molecules) in map.iter() {
         for original_molecule in original_molecules {
-            inverse_map.insert(original_molecule, reduced_molecule.clone());
+            inverse_map.insert(original_molecule.clone(), reduced_molecule.clone());
         }
     }
 
-    let (steps, molecule) = reduce_compound(&sequence, &inverse_map);
+    let (steps, molecule) = reduce_compound(sequence, &inverse_map);
     let (last_steps, ..) = reduce_molecule(&molecule, &inverse_map, |m| m == "e");
 
     steps + last_steps
@@ -172,14 +176,48 @@ where
     }
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (String, TransformMap);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        build_transform_map(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        calibrate(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        fabricate(input)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(7, calibrate(SAMPLE));
-    assert_eq!(518, calibrate(INPUT));
+    assert_eq!(7, calibrate(&build_transform_map(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2015, 19) else {
+        return;
+    };
+    assert_eq!(518, calibrate(&build_transform_map(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(6, fabricate(SAMPLE));
-    assert_eq!(200, fabricate(INPUT));
+    assert_eq!(6, fabricate(&build_transform_map(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2015, 19) else {
+        return;
+    };
+    assert_eq!(200, fabricate(&build_transform_map(&input).unwrap()));
+}
+
+#[test]
+fn bad_input() {
+    let err = build_transform_map("garbage\n").err().unwrap();
+    assert_eq!("a replacement for `e`", err.expected);
+    let err = build_transform_map("e => H\nH => HO\n").err().unwrap();
+    assert_eq!("a molecule", err.expected);
 }
@@ -1,46 +1,88 @@
-use std::str;
+use std::fmt::Display;
 
-fn dimension_to_int(part: Option<&[u8]>) -> usize {
-    str::parse(str::from_utf8(part.unwrap()).unwrap()).unwrap_or(0)
-}
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, non_empty, parse_at},
+    solution::Solution,
+};
+
+type Present = [usize; 3];
 
-fn total_materials(input: &[u8]) -> (usize, usize) {
-    input
-        .trim_ascii()
-        .split(|b| *b == b'\n')
-        .fold((0, 0), |(paper, ribbon), line| {
-            let mut dimension_iter = line.trim_ascii().split(|b| *b == b'x');
-            let l = dimension_to_int(dimension_iter.next());
-            let w = dimension_to_int(dimension_iter.next());
-            let h = dimension_to_int(dimension_iter.next());
-
-            let mut dimensions = vec![l, w, h];
-            dimensions.sort();
-            let first_smallest = dimensions.get(0).unwrap();
-            let second_smallest = dimensions.get(1).unwrap();
-            let smallest_area = first_smallest * second_smallest;
-            let smallest_perimeter = 2 * (first_smallest + second_smallest);
-
-            (
-                paper + (l * w * 2) + (l * h * 2) + (h * w * 2) + smallest_area,
-                ribbon + smallest_perimeter + (l * w * h),
-            )
+fn parse_presents(input: &str) -> Result<Vec<Present>, ParseError> {
+    let presents = input
+        .trim()
+        .lines()
+        .map(|line| {
+            let mut dimension_iter = line.trim().split('x');
+            let mut present = [0; 3];
+            for dimension in present.iter_mut() {
+                let part = dimension_iter
+                    .next()
+                    .ok_or_else(|| ParseError::at(input, &line[line.len()..], "a dimension"))?;
+                *dimension = parse_at(input, part, "a dimension")?;
+            }
+            Ok(present)
         })
+        .collect::<Result<_, _>>()?;
+    non_empty(input, presents, "a present like `2x3x4`")
+}
+
+fn total_materials(presents: &[Present]) -> (usize, usize) {
+    presents.iter().fold((0, 0), |(paper, ribbon), &[l, w, h]| {
+        let mut dimensions = [l, w, h];
+        dimensions.sort();
+        let first_smallest = dimensions.first().unwrap();
+        let second_smallest = dimensions.get(1).unwrap();
+        let smallest_area = first_smallest * second_smallest;
+        let smallest_perimeter = 2 * (first_smallest + second_smallest);
+
+        (
+            paper + (l * w * 2) + (l * h * 2) + (h * w * 2) + smallest_area,
+            ribbon + smallest_perimeter + (l * w * h),
+        )
+    })
 }
 
-const SAMPLE: &[u8] = b"2x3x4\n1x1x10\n";
-const INPUT: &[u8] = include_bytes!("./inputs/day2.txt");
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Present>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_presents(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        total_materials(input).0
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        total_materials(input).1
+    }
+}
+
+#[cfg(test)]
+const SAMPLE: &str = "2x3x4\n1x1x10\n";
 
 #[test]
 fn both_parts() {
-    let (sample_paper, sample_ribbon) = total_materials(SAMPLE);
-    let (input_paper, input_ribbon) = total_materials(INPUT);
-
-    // Part 1
+    let (sample_paper, sample_ribbon) = total_materials(&parse_presents(SAMPLE).unwrap());
     assert_eq!(101, sample_paper);
     assert_eq!(48, sample_ribbon);
 
-    // Part 2
+    let Some(input) = test_input(2015, 2) else {
+        return;
+    };
+    let (input_paper, input_ribbon) = total_materials(&parse_presents(&input).unwrap());
     assert_eq!(1588178, input_paper);
     assert_eq!(3783758, input_ribbon);
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_presents("2x3x4\n1x1\n").unwrap_err();
+    assert_eq!((2, 4), (err.line, err.column));
+    let err = parse_presents("2x3x4\n1xax1\n").unwrap_err();
+    assert_eq!(Some(String::from("a")), err.found);
+}
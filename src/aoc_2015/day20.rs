@@ -1,6 +1,13 @@
+use std::fmt::Display;
+
 use itertools::Itertools;
 
-fn first_idx_over_value(elements: &Vec<usize>, value: usize) -> usize {
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+fn first_idx_over_value(elements: &[usize], value: usize) -> usize {
     elements
         .iter()
         .enumerate()
@@ -31,12 +38,34 @@ fn calculate_present(value: usize) -> (usize, usize) {
     )
 }
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = usize;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_at(input, input.trim(), "a number of presents")
+    }
+
+    fn part_1(value: &Self::Input) -> impl Display {
+        calculate_present(*value).0
+    }
+
+    fn part_2(value: &Self::Input) -> impl Display {
+        calculate_present(*value).1
+    }
+}
+
+pub const INPUT: &str = "33100000";
+
 #[test]
 fn both_parts() {
     let (sample_part_1, sample_part_2) = calculate_present(150);
     assert_eq!(8, sample_part_1);
     assert_eq!(8, sample_part_2);
-    let (input_part_1, input_part_2) = calculate_present(33100000);
+    let (input_part_1, input_part_2) = calculate_present(INPUT.parse().unwrap());
     assert_eq!(776160, input_part_1);
     assert_eq!(786240, input_part_2);
 }
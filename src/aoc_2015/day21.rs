@@ -1,138 +1,161 @@
-use std::i64;
-
-use fxhash::FxHashSet;
-
-#[derive(Hash, Eq, PartialEq)]
-struct Item {
-    cost: i64,
-    attack: i64,
-    defense: i64,
-}
-
-// (cost, damage)
-const WEAPON_STORE: [Item; 5] = [
-    Item {
-        cost: 8,
-        attack: 4,
-        defense: 0,
-    },
-    Item {
-        cost: 10,
-        attack: 5,
-        defense: 0,
-    },
-    Item {
-        cost: 25,
-        attack: 6,
-        defense: 0,
-    },
-    Item {
-        cost: 40,
-        attack: 7,
-        defense: 0,
-    },
-    Item {
-        cost: 74,
-        attack: 8,
-        defense: 0,
-    },
-];
-
-// (cost, armor)
-const ARMOR_STORE: [Item; 6] = [
-    Item {
-        cost: 0,
-        attack: 0,
-        defense: 0,
-    }, // The NONE option
-    Item {
-        cost: 13,
-        attack: 0,
-        defense: 1,
-    },
-    Item {
-        cost: 31,
-        attack: 0,
-        defense: 2,
-    },
-    Item {
-        cost: 53,
-        attack: 0,
-        defense: 3,
-    },
-    Item {
-        cost: 75,
-        attack: 0,
-        defense: 4,
-    },
-    Item {
-        cost: 102,
-        attack: 0,
-        defense: 5,
-    },
-];
-
-// (cost, damage, armor)
-const RING_STORE: [Item; 7] = [
-    Item {
-        cost: 0,
-        attack: 0,
-        defense: 0,
-    }, // The NONE option
-    Item {
-        cost: 25,
-        attack: 1,
-        defense: 0,
-    },
-    Item {
-        cost: 50,
-        attack: 2,
-        defense: 0,
-    },
-    Item {
-        cost: 100,
-        attack: 3,
-        defense: 0,
-    },
-    Item {
-        cost: 20,
-        attack: 0,
-        defense: 1,
-    },
-    Item {
-        cost: 40,
-        attack: 0,
-        defense: 2,
-    },
-    Item {
-        cost: 80,
-        attack: 0,
-        defense: 3,
-    },
-];
-
-struct Build {
+use std::fmt::Display;
+
+use itertools::Itertools;
+use logos::Logos;
+
+use crate::util::{
+    parse::{ParseError, expect_token, next_token_or_end, parse_at},
+    solution::Solution,
+};
+
+const PLAYER_HEALTH: i64 = 100;
+
+// The shop from the puzzle, used unless the input brings its own table after the boss's stats
+pub const SHOP: &str = "
+Weapons:    Cost  Damage  Armor
+Dagger        8     4       0
+Shortsword   10     5       0
+Warhammer    25     6       0
+Longsword    40     7       0
+Greataxe     74     8       0
+
+Armor:      Cost  Damage  Armor
+Leather      13     0       1
+Chainmail    31     0       2
+Splintmail   53     0       3
+Bandedmail   75     0       4
+Platemail   102     0       5
+
+Rings:      Cost  Damage  Armor
+Damage +1    25     1       0
+Damage +2    50     2       0
+Damage +3   100     3       0
+Defense +1   20     0       1
+Defense +2   40     0       2
+Defense +3   80     0       3";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Item {
+    pub name: String,
+    pub cost: i64,
+    pub damage: i64,
+    pub armor: i64,
+}
+
+#[derive(Default)]
+pub struct Shop {
+    weapons: Vec<Item>,
+    armor: Vec<Item>,
+    rings: Vec<Item>,
+}
+
+fn parse_item(input: &str, line: &str) -> Result<Item, ParseError> {
+    // Names can have spaces in them, so the numbers are taken off the end
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [ref name @ .., cost, damage, armor] = fields[..] else {
+        return Err(ParseError::at(
+            input,
+            line,
+            "an item like `Dagger  8  4  0`",
+        ));
+    };
+    if name.is_empty() {
+        return Err(ParseError::at(input, cost, "an item name"));
+    }
+
+    Ok(Item {
+        name: name.join(" "),
+        cost: parse_at(input, cost, "a cost")?,
+        damage: parse_at(input, damage, "a damage value")?,
+        armor: parse_at(input, armor, "an armor value")?,
+    })
+}
+
+// `table` is the part of `input` holding the shop, so errors point at the right spot in the input
+fn parse_shop(input: &str, table: &str) -> Result<Shop, ParseError> {
+    let mut shop = Shop::default();
+    for section in table.trim().split("\n\n") {
+        let mut lines = section.lines();
+        let header = lines.next().unwrap_or_default();
+        let (kind, _) = header.split_once(':').unwrap_or_default();
+        let items = match kind {
+            "Weapons" => &mut shop.weapons,
+            "Armor" => &mut shop.armor,
+            "Rings" => &mut shop.rings,
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    header,
+                    "`Weapons:`, `Armor:` or `Rings:`",
+                ));
+            }
+        };
+        for line in lines {
+            items.push(parse_item(input, line)?);
+        }
+    }
+    // Every loadout needs a weapon
+    if shop.weapons.is_empty() {
+        return Err(ParseError::at(
+            input,
+            &table[table.len()..],
+            "a `Weapons:` section with at least one weapon",
+        ));
+    }
+    Ok(shop)
+}
+
+#[derive(Logos)]
+#[logos(skip r"[ \n]+")]
+enum Token {
+    #[token("Hit Points:")]
+    HitPoints,
+
+    #[token("Damage:")]
+    Damage,
+
+    #[token("Armor:")]
+    Armor,
+
+    #[regex("[0-9]+", |lex| lex.slice().parse::<i64>().ok())]
+    Number(i64),
+}
+
+impl Token {
+    fn take_number(self) -> Option<i64> {
+        match self {
+            Self::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+}
+
+fn parse_boss(input: &str) -> Result<Build, ParseError> {
+    let mut lex = Token::lexer(input);
+    let mut stat = |label: Token, expected| {
+        expect_token(&mut lex, expected, |token| {
+            (std::mem::discriminant(&token) == std::mem::discriminant(&label)).then_some(())
+        })?;
+        expect_token(&mut lex, "a number", Token::take_number)
+    };
+    let boss = Build {
+        health: stat(Token::HitPoints, "`Hit Points:`")?,
+        damage: stat(Token::Damage, "`Damage:`")?,
+        armor: stat(Token::Armor, "`Armor:`")?,
+    };
+    match next_token_or_end(&mut lex, "the end of the input")? {
+        Some(_) => Err(ParseError::new(input, lex.span(), "the end of the input")),
+        None => Ok(boss),
+    }
+}
+
+pub struct Build {
     health: i64,
     damage: i64,
     armor: i64,
 }
 
 impl Build {
-    fn augment(
-        &self,
-        &Item {
-            attack: damage,
-            defense: armor,
-            ..
-        }: &Item,
-    ) -> Self {
-        Self {
-            health: self.health,
-            damage: self.damage + damage,
-            armor: self.armor + armor,
-        }
-    }
-
     fn can_beat(&self, other: &Build) -> bool {
         let applied_self_damage = 1.max(self.damage - other.armor);
         let turns_to_beat_other =
@@ -147,59 +170,172 @@ impl Build {
     }
 }
 
-// Vec of all combinations of (cost, damage, armor) for 1 weapon, 0-1 armor, and 0-2 rings
-// I could be more optimal, but this is truly such a small amount of total work that I
-// prefer the simpler, more elegant, slightly less optimal solution.
-fn all_combos() -> Vec<Item> {
-    let mut combos = FxHashSet::default();
-
-    for weapon in WEAPON_STORE.iter() {
-        for armor in ARMOR_STORE.iter() {
-            for ring1 in RING_STORE.iter() {
-                for ring2 in RING_STORE.iter() {
-                    if ring1 != ring2 {
-                        combos.insert(Item {
-                            cost: weapon.cost + armor.cost + ring1.cost + ring2.cost,
-                            attack: weapon.attack + ring1.attack + ring2.attack,
-                            defense: armor.defense + ring1.defense + ring2.defense,
-                        });
-                    }
-                }
-            }
+// What the player bought, with the items in the order weapon, armor, rings
+pub struct Loadout<'a> {
+    pub items: Vec<&'a Item>,
+    pub cost: i64,
+}
+
+impl<'a> Loadout<'a> {
+    pub fn names(&self) -> Vec<&'a str> {
+        self.items.iter().map(|item| item.name.as_str()).collect()
+    }
+
+    fn player(&self) -> Build {
+        Build {
+            health: PLAYER_HEALTH,
+            damage: self.items.iter().map(|item| item.damage).sum(),
+            armor: self.items.iter().map(|item| item.armor).sum(),
         }
     }
+}
 
-    combos.into_iter().collect()
+// Every way to buy exactly one weapon, up to one piece of armor and up to two different rings. It's
+// only a few hundred loadouts, so trying them all is plenty quick
+fn all_loadouts(shop: &Shop) -> impl Iterator<Item = Loadout<'_>> {
+    let armor = || shop.armor.iter().map(Some).chain([None]);
+    let rings = || (0..=2).flat_map(|count| shop.rings.iter().combinations(count));
+    shop.weapons
+        .iter()
+        .cartesian_product(armor().cartesian_product(rings().collect_vec()))
+        .map(|(weapon, (armor, rings))| {
+            let items: Vec<&Item> = [weapon].into_iter().chain(armor).chain(rings).collect();
+            let cost = items.iter().map(|item| item.cost).sum();
+            Loadout { items, cost }
+        })
 }
 
-fn find_extreme_builds() -> (i64, i64) {
-    let base_player = Build {
-        health: 100,
-        damage: 0,
-        armor: 0,
-    };
+// The cheapest loadout that beats the boss, and the most expensive one that doesn't
+fn find_extreme_loadouts<'a>(
+    shop: &'a Shop,
+    boss: &Build,
+) -> (Option<Loadout<'a>>, Option<Loadout<'a>>) {
+    let (winners, losers): (Vec<_>, Vec<_>) =
+        all_loadouts(shop).partition(|loadout| loadout.player().can_beat(boss));
+    (
+        winners.into_iter().min_by_key(|loadout| loadout.cost),
+        losers.into_iter().max_by_key(|loadout| loadout.cost),
+    )
+}
 
-    // The AofC day 21 input
-    let boss = Build {
-        health: 103,
-        damage: 9,
-        armor: 2,
+// The boss's stats, optionally followed by a blank line and a shop table to use instead of `SHOP`
+fn parse_input(input: &str) -> Result<(Shop, Build), ParseError> {
+    let start = input.len() - input.trim_start().len();
+    let (boss, table) = match input[start..].find("\n\n") {
+        Some(idx) => input.split_at(start + idx),
+        None => (input, ""),
+    };
+    let shop = if table.trim().is_empty() {
+        parse_shop(SHOP, SHOP)?
+    } else {
+        parse_shop(input, table)?
     };
+    Ok((shop, parse_boss(boss)?))
+}
 
-    let mut lowest_cost_winner = i64::MAX;
-    let mut highest_cost_loser = 0;
-    for item in all_combos() {
-        if base_player.augment(&item).can_beat(&boss) {
-            lowest_cost_winner = lowest_cost_winner.min(item.cost);
-        } else {
-            highest_cost_loser = highest_cost_loser.max(item.cost);
-        }
+pub const INPUT: &str = "
+Hit Points: 103
+Damage: 9
+Armor: 2";
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = (Shop, Build);
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1((shop, boss): &Self::Input) -> impl Display {
+        find_extreme_loadouts(shop, boss)
+            .0
+            .expect("no loadout beats the boss")
+            .cost
     }
 
-    (lowest_cost_winner, highest_cost_loser)
+    fn part_2((shop, boss): &Self::Input) -> impl Display {
+        find_extreme_loadouts(shop, boss)
+            .1
+            .expect("every loadout beats the boss")
+            .cost
+    }
+}
+
+#[test]
+fn parsing() {
+    let shop = parse_shop(SHOP, SHOP).unwrap();
+    assert_eq!(
+        (5, 5, 6),
+        (shop.weapons.len(), shop.armor.len(), shop.rings.len())
+    );
+    assert_eq!(
+        Item {
+            name: "Damage +3".to_string(),
+            cost: 100,
+            damage: 3,
+            armor: 0
+        },
+        shop.rings[2]
+    );
+    let shields = "Shields:  Cost  Damage  Armor\nBuckler  5  0  1";
+    let error = parse_shop(shields, shields).err().unwrap();
+    assert_eq!((1, 1), (error.line, error.column));
+    let rings = "Rings:  Cost  Damage  Armor\nDamage +1  25  one  0";
+    let error = parse_shop(rings, rings).err().unwrap();
+    assert_eq!("a damage value", error.expected);
+
+    let boss = parse_boss(INPUT).unwrap();
+    assert_eq!((103, 9, 2), (boss.health, boss.damage, boss.armor));
+    let error = parse_boss("Hit Points: 12\nArmor: 7").err().unwrap();
+    assert_eq!("`Damage:`", error.expected);
+
+    // A shop after the boss replaces the default one, and its errors point into the whole input
+    let input =
+        "Hit Points: 12\nDamage: 7\nArmor: 2\n\nWeapons:  Cost  Damage  Armor\nStick  1  1  0";
+    let (shop, boss) = parse_input(input).unwrap();
+    assert_eq!(
+        (1, 0, 0),
+        (shop.weapons.len(), shop.armor.len(), shop.rings.len())
+    );
+    assert_eq!(12, boss.health);
+    let error = parse_input(&input.replace("Stick  1", "Stick  x"))
+        .err()
+        .unwrap();
+    assert_eq!((6, 8), (error.line, error.column));
+    let error = parse_input("Hit Points: 12\nDamage: 7\nArmor: 2\n\nRings:  Cost  Damage  Armor")
+        .err()
+        .unwrap();
+    assert_eq!(
+        "a `Weapons:` section with at least one weapon",
+        error.expected
+    );
 }
 
 #[test]
 fn both_parts() {
-    assert_eq!((121, 201), find_extreme_builds());
+    let shop = parse_shop(SHOP, SHOP).unwrap();
+    let boss = parse_boss(INPUT).unwrap();
+    let (winner, loser) = find_extreme_loadouts(&shop, &boss);
+
+    let winner = winner.unwrap();
+    assert_eq!(121, winner.cost);
+    assert_eq!(vec!["Longsword", "Chainmail", "Damage +2"], winner.names());
+    let loser = loser.unwrap();
+    assert_eq!(201, loser.cost);
+    assert_eq!(
+        vec!["Dagger", "Leather", "Damage +3", "Defense +3"],
+        loser.names()
+    );
+
+    // A stick can't beat this boss, and there's nothing else to buy
+    let (shop, boss) = parse_input(
+        "Hit Points: 1000\nDamage: 50\nArmor: 0\n\nWeapons:  Cost  Damage  Armor\nStick  1  1  0",
+    )
+    .unwrap();
+    let (winner, loser) = find_extreme_loadouts(&shop, &boss);
+    assert!(winner.is_none());
+    assert_eq!(vec!["Stick"], loser.unwrap().names());
 }
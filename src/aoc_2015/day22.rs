@@ -1,22 +1,62 @@
-use std::{collections::BinaryHeap, i32};
+use std::fmt::Display;
 
-#[derive(PartialEq, Eq)]
-struct GameState {
-    // Player
-    player_health: i32,
-    player_shield: i32,
-    mana: i32,
-    shield_remaining: u32,
-    poison_remaining: u32,
-    recharge_remaining: u32,
-    total_mana_spend: i32,
+use logos::Logos;
 
-    // Boss
-    boss_health: i32,
+use crate::util::{
+    parse::{ParseError, expect_token, next_token_or_end},
+    search::dijkstra,
+    solution::Solution,
+};
+
+#[derive(Logos)]
+#[logos(skip r"[ \n]+")]
+enum Token {
+    #[token("Hit Points:")]
+    HitPoints,
+
+    #[token("Damage:")]
+    Damage,
+
+    #[regex("[0-9]+", |lex| lex.slice().parse::<i32>().ok())]
+    Number(i32),
 }
 
-#[derive(PartialEq, Eq)]
-enum Action {
+impl Token {
+    fn take_hit_points(self) -> Option<()> {
+        matches!(self, Self::HitPoints).then_some(())
+    }
+
+    fn take_damage(self) -> Option<()> {
+        matches!(self, Self::Damage).then_some(())
+    }
+
+    fn take_number(self) -> Option<i32> {
+        match self {
+            Self::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+}
+
+pub struct Boss {
+    health: i32,
+    damage: i32,
+}
+
+fn parse_boss(input: &str) -> Result<Boss, ParseError> {
+    let mut lex = Token::lexer(input);
+    expect_token(&mut lex, "`Hit Points:`", Token::take_hit_points)?;
+    let health = expect_token(&mut lex, "a number", Token::take_number)?;
+    expect_token(&mut lex, "`Damage:`", Token::take_damage)?;
+    let damage = expect_token(&mut lex, "a number", Token::take_number)?;
+    match next_token_or_end(&mut lex, "the end of the input")? {
+        Some(_) => Err(ParseError::new(input, lex.span(), "the end of the input")),
+        None => Ok(Boss { health, damage }),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
     MagicMissile,
     Drain,
     Shield,
@@ -24,190 +64,409 @@ enum Action {
     Recharge,
 }
 
-const BOSS_DAMAGE: i32 = 9;
-
-const MAGIC_MISSILE_MANA: i32 = 53;
-const MAGIC_MISSILE_DAMAGE: i32 = 4;
+impl Action {
+    const ALL: [Action; 5] = [
+        Self::MagicMissile,
+        Self::Drain,
+        Self::Shield,
+        Self::Poison,
+        Self::Recharge,
+    ];
+}
 
-const DRAIN_MANA: i32 = 73;
-const DRAIN_AMOUNT: i32 = 2;
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::MagicMissile => "Magic Missile",
+            Self::Drain => "Drain",
+            Self::Shield => "Shield",
+            Self::Poison => "Poison",
+            Self::Recharge => "Recharge",
+        })
+    }
+}
 
-const SHIELD_MANA: i32 = 113;
-const SHIELD_TURNS: u32 = 6;
-const SHIELD_AMOUNT: i32 = 7;
+// What a spell does. Spells that last no turns do their damage and healing as they're cast, and
+// the rest are effects that do all of theirs at the start of every turn while they last
+#[derive(Clone, Copy)]
+pub struct Spell {
+    pub cost: i32,
+    pub damage: i32,
+    pub heal: i32,
+    pub armor: i32,
+    pub mana: i32,
+    pub turns: u32,
+}
 
-const POISON_MANA: i32 = 173;
-const POISON_TURNS: u32 = 6;
-const POISON_DAMAGE: i32 = 3;
+const NO_SPELL: Spell = Spell {
+    cost: 0,
+    damage: 0,
+    heal: 0,
+    armor: 0,
+    mana: 0,
+    turns: 0,
+};
 
-const RECHARGE_MANA: i32 = 229;
-const RECHARGE_TURNS: u32 = 5;
-const RECHARGE_AMOUNT: i32 = 101;
+pub struct Rules {
+    pub player_health: i32,
+    pub player_mana: i32,
+    // The player loses a hit point at the start of each of their turns
+    pub hard: bool,
+    // Indexed by `Action`
+    pub spells: [Spell; 5],
+}
 
-impl GameState {
-    fn base_state() -> Self {
+// The rules from the puzzle
+impl Default for Rules {
+    fn default() -> Self {
         Self {
             player_health: 50,
-            player_shield: 0,
-            mana: 500,
-            shield_remaining: 0,
-            poison_remaining: 0,
-            recharge_remaining: 0,
-            boss_health: 51,
-            total_mana_spend: 0,
+            player_mana: 500,
+            hard: false,
+            spells: [
+                Spell {
+                    cost: 53,
+                    damage: 4,
+                    ..NO_SPELL
+                },
+                Spell {
+                    cost: 73,
+                    damage: 2,
+                    heal: 2,
+                    ..NO_SPELL
+                },
+                Spell {
+                    cost: 113,
+                    armor: 7,
+                    turns: 6,
+                    ..NO_SPELL
+                },
+                Spell {
+                    cost: 173,
+                    damage: 3,
+                    turns: 6,
+                    ..NO_SPELL
+                },
+                Spell {
+                    cost: 229,
+                    mana: 101,
+                    turns: 5,
+                    ..NO_SPELL
+                },
+            ],
         }
     }
+}
 
-    fn available_actions(&self) -> Vec<Action> {
-        let mut actions = Vec::with_capacity(5);
-        if self.mana >= MAGIC_MISSILE_MANA {
-            actions.push(Action::MagicMissile);
+impl Rules {
+    fn spell(&self, action: Action) -> &Spell {
+        &self.spells[action as usize]
+    }
+}
 
-            if self.mana >= DRAIN_MANA {
-                actions.push(Action::Drain);
+// The state at the start of one of the player's turns. Mana spent isn't part of it, since that's
+// the cost the search keeps track of
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct GameState {
+    player_health: i32,
+    player_armor: i32,
+    mana: i32,
+    // How many more turns each effect lasts, indexed by `Action`
+    timers: [u32; 5],
+    boss_health: i32,
+}
 
-                if self.mana >= SHIELD_MANA && self.shield_remaining == 0 {
-                    actions.push(Action::Shield);
-                }
+impl GameState {
+    fn new(boss: &Boss, rules: &Rules) -> Self {
+        Self {
+            player_health: rules.player_health,
+            player_armor: 0,
+            mana: rules.player_mana,
+            timers: [0; 5],
+            boss_health: boss.health,
+        }
+    }
 
-                if self.mana >= POISON_MANA && self.poison_remaining == 0 {
-                    actions.push(Action::Poison);
-                }
+    // Effects can only be cast again once they've worn off
+    fn can_cast(&self, action: Action, rules: &Rules) -> bool {
+        self.mana >= rules.spell(action).cost && self.timers[action as usize] == 0
+    }
 
-                if self.mana >= RECHARGE_MANA && self.recharge_remaining == 0 {
-                    actions.push(Action::Recharge)
-                }
+    fn apply_effects(&mut self, rules: &Rules) {
+        self.player_armor = 0;
+        for action in Action::ALL {
+            let timer = &mut self.timers[action as usize];
+            if *timer > 0 {
+                *timer -= 1;
+                let spell = rules.spell(action);
+                self.player_armor += spell.armor;
+                self.player_health += spell.heal;
+                self.mana += spell.mana;
+                self.boss_health -= spell.damage;
             }
         }
+    }
 
-        actions
+    // Returns true if the game is still going, false if the player loses
+    fn apply_boss_attack(&mut self, boss: &Boss) -> bool {
+        self.player_health -= (boss.damage - self.player_armor).max(1);
+        self.player_health > 0
     }
 
     // Returns true if the game is still going, false if the player loses
-    fn apply_effects(&mut self, hard: bool) -> bool {
-        if hard {
-            if self.player_health == 1 {
-                return false;
-            } else {
-                self.player_health -= 1;
-            }
+    fn apply_hard_mode(&mut self, rules: &Rules) -> bool {
+        if rules.hard {
+            self.player_health -= 1;
         }
+        self.player_health > 0
+    }
 
-        if self.shield_remaining > 0 {
-            self.shield_remaining -= 1;
-            self.player_shield = SHIELD_AMOUNT;
+    fn apply_player_turn(&self, action: Action, rules: &Rules) -> Self {
+        let spell = rules.spell(action);
+        let mut next = self.clone();
+        next.mana -= spell.cost;
+        if spell.turns == 0 {
+            next.player_health += spell.heal;
+            next.boss_health -= spell.damage;
         } else {
-            self.player_shield = 0;
+            next.timers[action as usize] = spell.turns;
         }
+        next
+    }
 
-        if self.poison_remaining > 0 {
-            self.poison_remaining -= 1;
-            self.boss_health -= POISON_DAMAGE;
+    // Plays out a player turn and the boss turn after it for every spell the player can cast,
+    // giving the spell and the state it leads to. Rounds the player loses are left out, and if
+    // the effects win the game before the player casts anything there's no spell
+    fn next_rounds(&self, boss: &Boss, rules: &Rules) -> Vec<(Option<Action>, Self)> {
+        let mut state = self.clone();
+        if !state.apply_hard_mode(rules) {
+            return Vec::new();
         }
-
-        if self.recharge_remaining > 0 {
-            self.recharge_remaining -= 1;
-            self.mana += RECHARGE_AMOUNT;
+        state.apply_effects(rules);
+        if state.is_win() {
+            return vec![(None, state)];
         }
 
-        true
+        Action::ALL
+            .into_iter()
+            .filter(|&action| state.can_cast(action, rules))
+            .filter_map(|action| {
+                let mut next = state.apply_player_turn(action, rules);
+                // Boss's turn
+                next.apply_effects(rules);
+                if next.is_win() || next.apply_boss_attack(boss) {
+                    Some((Some(action), next))
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
 
-    // Returns true if the game is still going, false if the player loses
-    fn apply_boss_attack(&mut self) -> bool {
-        self.player_health -= BOSS_DAMAGE - self.player_shield;
-        self.player_health > 0
+    fn is_win(&self) -> bool {
+        self.boss_health <= 0
     }
+}
 
-    fn apply_player_turn(&self, action: Action) -> Self {
-        let (player_damage, health_gained, mana_used) = match action {
-            Action::MagicMissile => (MAGIC_MISSILE_DAMAGE, 0, MAGIC_MISSILE_MANA),
-            Action::Drain => (DRAIN_AMOUNT, DRAIN_AMOUNT, DRAIN_MANA),
-            Action::Shield => (0, 0, SHIELD_MANA),
-            Action::Poison => (0, 0, POISON_MANA),
-            Action::Recharge => (0, 0, RECHARGE_MANA),
-        };
+// The least mana the player can spend and still win, along with the spells to cast
+fn find_lowest_mana_win(boss: &Boss, rules: &Rules) -> Option<(i32, Vec<Action>)> {
+    let (mana, path) = dijkstra(
+        GameState::new(boss, rules),
+        |state| {
+            state
+                .next_rounds(boss, rules)
+                .into_iter()
+                .map(|(action, next)| (next, action.map_or(0, |action| rules.spell(action).cost)))
+        },
+        GameState::is_win,
+    )?;
 
-        Self {
-            player_health: self.player_health + health_gained,
-            player_shield: self.player_shield,
-            mana: self.mana - mana_used,
-            shield_remaining: if action == Action::Shield {
-                SHIELD_TURNS
-            } else {
-                self.shield_remaining
-            },
-            poison_remaining: if action == Action::Poison {
-                POISON_TURNS
-            } else {
-                self.poison_remaining
-            },
-            recharge_remaining: if action == Action::Recharge {
-                RECHARGE_TURNS
+    // The search only keeps the states, so the spell cast between each pair is worked back out
+    let spells = path
+        .windows(2)
+        .filter_map(|step| {
+            step[0]
+                .next_rounds(boss, rules)
+                .into_iter()
+                .find(|(_, next)| *next == step[1])
+                .and_then(|(action, _)| action)
+        })
+        .collect();
+    Some((mana, spells))
+}
+
+fn describe_state(log: &mut Vec<String>, heading: &str, state: &GameState) {
+    log.push(format!("-- {heading} turn --"));
+    log.push(format!(
+        "- Player has {} hit points, {} armor, {} mana",
+        state.player_health, state.player_armor, state.mana
+    ));
+    log.push(format!("- Boss has {} hit points", state.boss_health));
+}
+
+fn describe_effects(log: &mut Vec<String>, before: &GameState, after: &GameState) {
+    for action in Action::ALL {
+        let (was, now) = (
+            before.timers[action as usize],
+            after.timers[action as usize],
+        );
+        if was > 0 {
+            log.push(if now == 0 {
+                format!("{action} wears off.")
             } else {
-                self.recharge_remaining
-            },
-            boss_health: self.boss_health - player_damage,
-            total_mana_spend: self.total_mana_spend + mana_used,
+                format!("{action}'s timer is now {now}.")
+            });
         }
     }
-
-    fn is_win(&self) -> bool {
-        self.boss_health <= 0
-    }
 }
 
-impl PartialOrd for GameState {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other.total_mana_spend.partial_cmp(&self.total_mana_spend)
-    }
+// Plays out `spells` in order, one line per thing that happens, in the style of the puzzle's
+// examples. Stops as soon as either side wins, or when a spell can't be cast or there are none
+// left to cast
+pub fn battle_log(boss: &Boss, rules: &Rules, spells: &[Action]) -> String {
+    let mut log = Vec::new();
+    let mut state = GameState::new(boss, rules);
+    let mut spells = spells.iter();
+    let mut mana_spent = 0;
+
+    let outcome = loop {
+        describe_state(&mut log, "Player", &state);
+        if !state.apply_hard_mode(rules) {
+            break "The player loses their last hit point, and the boss wins.";
+        }
+        let before = state.clone();
+        state.apply_effects(rules);
+        describe_effects(&mut log, &before, &state);
+        if state.is_win() {
+            break "This kills the boss, and the player wins.";
+        }
+
+        let Some(&action) = spells.next() else {
+            break "The player has no spells left to cast.";
+        };
+        if !state.can_cast(action, rules) {
+            log.push(format!("Player can't cast {action}."));
+            break "The player can't cast a spell, and loses.";
+        }
+        state = state.apply_player_turn(action, rules);
+        mana_spent += rules.spell(action).cost;
+        log.push(format!("Player casts {action}."));
+        if state.is_win() {
+            break "This kills the boss, and the player wins.";
+        }
+
+        log.push(String::new());
+        describe_state(&mut log, "Boss", &state);
+        let before = state.clone();
+        state.apply_effects(rules);
+        describe_effects(&mut log, &before, &state);
+        if state.is_win() {
+            break "This kills the boss, and the player wins.";
+        }
+        let alive = state.apply_boss_attack(boss);
+        log.push(format!(
+            "Boss attacks for {} damage.",
+            (boss.damage - state.player_armor).max(1)
+        ));
+        if !alive {
+            break "This kills the player, and the boss wins.";
+        }
+        log.push(String::new());
+    };
+
+    log.push(outcome.to_string());
+    log.push(format!("The player spent {mana_spent} mana."));
+    log.join("\n")
 }
 
-impl Ord for GameState {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.total_mana_spend.cmp(&self.total_mana_spend)
+pub const INPUT: &str = "
+Hit Points: 51
+Damage: 9";
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Boss;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_boss(input)
     }
-}
 
-fn find_lowest_mana_win(hard: bool) -> i32 {
-    // Store state from the moment after a player has used a turn.
-    let mut game_states = BinaryHeap::<GameState>::new();
-    let base_state = GameState::base_state();
-    for first_action in base_state.available_actions() {
-        let mut first_state = base_state.apply_player_turn(first_action);
-        first_state.apply_effects(hard);
-        game_states.push(first_state);
+    fn part_1(boss: &Self::Input) -> impl Display {
+        find_lowest_mana_win(boss, &Rules::default())
+            .expect("the player can't win against this boss")
+            .0
     }
 
-    let mut lowest_mana_cost = i32::MAX;
-    while let Some(mut game_state) = game_states.pop() {
-        if game_state.total_mana_spend >= lowest_mana_cost {
-            continue;
-        }
-        // Boss's turn
-        game_state.apply_effects(false);
-        if game_state.is_win() {
-            lowest_mana_cost = lowest_mana_cost.min(game_state.total_mana_spend);
-        } else if game_state.apply_boss_attack() {
-            // Player's turn
-            if game_state.apply_effects(hard) {
-                for action in game_state.available_actions() {
-                    let next_state = game_state.apply_player_turn(action);
-                    if game_state.is_win() {
-                        lowest_mana_cost = lowest_mana_cost.min(game_state.total_mana_spend);
-                    } else {
-                        game_states.push(next_state);
-                    }
-                }
-            }
-        }
+    fn part_2(boss: &Self::Input) -> impl Display {
+        let rules = Rules {
+            hard: true,
+            ..Rules::default()
+        };
+        find_lowest_mana_win(boss, &rules)
+            .expect("the player can't win against this boss in hard mode")
+            .0
     }
+}
 
-    lowest_mana_cost
+#[test]
+fn samples() {
+    use Action::*;
+
+    let rules = Rules {
+        player_health: 10,
+        player_mana: 250,
+        ..Rules::default()
+    };
+    let boss = Boss {
+        health: 13,
+        damage: 8,
+    };
+    let log = battle_log(&boss, &rules, &[Poison, MagicMissile]);
+    assert!(log.contains("- Player has 2 hit points, 0 armor, 77 mana"));
+    assert!(log.ends_with("the player wins.\nThe player spent 226 mana."));
+
+    let boss = Boss {
+        health: 14,
+        damage: 8,
+    };
+    let spells = [Recharge, Shield, Drain, Poison, MagicMissile];
+    let log = battle_log(&boss, &rules, &spells);
+    assert!(log.contains("Shield's timer is now 5."));
+    assert!(log.contains("Boss attacks for 1 damage."));
+    assert!(log.contains("Recharge wears off."));
+    assert!(log.ends_with("the player wins.\nThe player spent 641 mana."));
+    assert_eq!(
+        Some((641, spells.to_vec())),
+        find_lowest_mana_win(&boss, &rules)
+    );
+
+    // Shield can't be cast again while it's still going
+    let log = battle_log(&boss, &rules, &[Shield, MagicMissile, Shield]);
+    assert!(log.contains("Player can't cast Shield."));
+
+    // Without enough mana for even the cheapest spell, the player loses on their first turn
+    let rules = Rules {
+        player_mana: 52,
+        ..rules
+    };
+    assert_eq!(None, find_lowest_mana_win(&boss, &rules));
 }
 
 #[test]
 fn both_parts() {
-    assert_eq!(900, find_lowest_mana_win(false));
-    assert_eq!(1216, find_lowest_mana_win(true));
+    let boss = parse_boss(INPUT).unwrap();
+    let (mana, spells) = find_lowest_mana_win(&boss, &Rules::default()).unwrap();
+    assert_eq!(900, mana);
+    assert!(battle_log(&boss, &Rules::default(), &spells).ends_with("spent 900 mana."));
+
+    let rules = Rules {
+        hard: true,
+        ..Rules::default()
+    };
+    let (mana, spells) = find_lowest_mana_win(&boss, &rules).unwrap();
+    assert_eq!(1216, mana);
+    assert!(battle_log(&boss, &rules, &spells).ends_with("spent 1216 mana."));
 }
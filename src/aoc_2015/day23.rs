@@ -1,6 +1,13 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
-const INPUT: &'static str = include_str!("./inputs/day23.txt");
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, next_token_or_end, non_empty},
+    solution::Solution,
+};
 
 #[derive(Logos)]
 #[logos(skip r"[\n ,]")]
@@ -23,42 +30,42 @@ enum Token {
     #[token("jio")]
     JumpIfOne,
 
-    #[regex("[ab]", |lex| lex.slice().chars().nth(0).unwrap())]
+    #[regex("[ab]", |lex| lex.slice().chars().next())]
     Register(char),
 
-    #[regex("[+-][0-9]+", |lex| lex.slice().parse::<i64>().unwrap())]
+    #[regex("[+-][0-9]+", |lex| lex.slice().parse::<i64>().ok())]
     Offset(i64),
 }
 
 impl Token {
-    fn to_register(self) -> Register {
+    fn take_register(self) -> Option<Register> {
         match self {
             Self::Register(ch) => {
                 if ch == 'a' {
-                    Register::A
+                    Some(Register::A)
                 } else {
-                    Register::B
+                    Some(Register::B)
                 }
             }
-            _ => unreachable!(),
+            _ => None,
         }
     }
 
-    fn to_offset(self) -> i64 {
+    fn take_offset(self) -> Option<i64> {
         match self {
-            Self::Offset(offset) => offset,
-            _ => unreachable!(),
+            Self::Offset(offset) => Some(offset),
+            _ => None,
         }
     }
 }
 
 #[derive(PartialEq, Eq)]
-enum Register {
+pub enum Register {
     A,
     B,
 }
 
-enum Ins {
+pub enum Ins {
     Half(Register),
     Triple(Register),
     Increment(Register),
@@ -67,44 +74,44 @@ enum Ins {
     JumpIfOne(Register, i64),
 }
 
-type Instructions = Vec<Ins>;
+pub type Instructions = Vec<Ins>;
 
-fn create(input: &str) -> Instructions {
+fn create(input: &str) -> Result<Instructions, ParseError> {
     let mut instructions = Vec::new();
     let mut tokens = Token::lexer(input);
-    while let Some(Ok(token)) = tokens.next() {
+    while let Some(token) = next_token_or_end(&mut tokens, "an instruction")? {
         match token {
             Token::Half => {
-                let reg = tokens.next().unwrap().unwrap().to_register();
+                let reg = expect_token(&mut tokens, "a register", Token::take_register)?;
                 instructions.push(Ins::Half(reg));
             }
             Token::Triple => {
-                let reg = tokens.next().unwrap().unwrap().to_register();
+                let reg = expect_token(&mut tokens, "a register", Token::take_register)?;
                 instructions.push(Ins::Triple(reg));
             }
             Token::Increment => {
-                let reg = tokens.next().unwrap().unwrap().to_register();
+                let reg = expect_token(&mut tokens, "a register", Token::take_register)?;
                 instructions.push(Ins::Increment(reg));
             }
             Token::Jump => {
-                let offset = tokens.next().unwrap().unwrap().to_offset();
+                let offset = expect_token(&mut tokens, "an offset", Token::take_offset)?;
                 instructions.push(Ins::Jump(offset));
             }
             Token::JumpIfEven => {
-                let reg = tokens.next().unwrap().unwrap().to_register();
-                let offset = tokens.next().unwrap().unwrap().to_offset();
+                let reg = expect_token(&mut tokens, "a register", Token::take_register)?;
+                let offset = expect_token(&mut tokens, "an offset", Token::take_offset)?;
                 instructions.push(Ins::JumpIfEven(reg, offset));
             }
             Token::JumpIfOne => {
-                let reg = tokens.next().unwrap().unwrap().to_register();
-                let offset = tokens.next().unwrap().unwrap().to_offset();
+                let reg = expect_token(&mut tokens, "a register", Token::take_register)?;
+                let offset = expect_token(&mut tokens, "an offset", Token::take_offset)?;
                 instructions.push(Ins::JumpIfOne(reg, offset));
             }
-            _ => unreachable!(),
+            _ => return Err(ParseError::new(input, tokens.span(), "an instruction")),
         }
     }
 
-    instructions
+    non_empty(input, instructions, "an instruction")
 }
 
 fn exec(instructions: &Instructions, init_a: i32) -> i32 {
@@ -164,9 +171,30 @@ fn exec(instructions: &Instructions, init_a: i32) -> i32 {
     b
 }
 
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Instructions;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        create(input)
+    }
+
+    fn part_1(instructions: &Self::Input) -> impl Display {
+        exec(instructions, 0)
+    }
+
+    fn part_2(instructions: &Self::Input) -> impl Display {
+        exec(instructions, 1)
+    }
+}
+
 #[test]
 fn both_parts() {
-    let instructions = create(INPUT);
+    let Some(input) = test_input(2015, 23) else {
+        return;
+    };
+    let instructions = create(&input).unwrap();
     assert_eq!(255, exec(&instructions, 0));
     assert_eq!(334, exec(&instructions, 1));
 }
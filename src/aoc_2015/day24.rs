@@ -1,6 +1,16 @@
+use std::fmt::Display;
+
 use itertools::Itertools;
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 1
 2
 3
@@ -12,21 +22,19 @@ const SAMPLE: &'static str = "
 10
 11";
 
-const INPUT: &'static str = include_str!("./inputs/day24.txt");
-
-fn parse_packages(input: &str) -> Vec<usize> {
+fn parse_packages(input: &str) -> Result<Vec<usize>, ParseError> {
     let mut packages: Vec<usize> = input
         .trim()
         .split("\n")
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect();
+        .map(|s| parse_at(input, s.trim(), "a package weight"))
+        .collect::<Result<_, _>>()?;
     packages.sort();
-    packages.into_iter().rev().collect()
+    Ok(packages.into_iter().rev().collect())
 }
 
-fn find_lowest_config(packages: &Vec<usize>, compartments: usize) -> usize {
+fn find_lowest_config(packages: &[usize], compartments: usize) -> usize {
     let total_size: usize = packages.iter().sum();
-    if total_size % compartments != 0 {
+    if !total_size.is_multiple_of(compartments) {
         panic!("Packages cannot be split into {compartments} equal groups");
     }
     let compartment_weight = total_size / compartments;
@@ -38,25 +46,46 @@ fn find_lowest_config(packages: &Vec<usize>, compartments: usize) -> usize {
         if subset.len() > len_of_smallest_valid_set {
             break;
         }
-        if compartment_weight == subset.iter().copied().sum() {
-            if subset.len() <= len_of_smallest_valid_set {
-                len_of_smallest_valid_set = subset.len();
-                smallest_valid_quantum_entaglement =
-                    smallest_valid_quantum_entaglement.min(subset.iter().copied().product());
-            }
+        if compartment_weight == subset.iter().copied().sum()
+            && subset.len() <= len_of_smallest_valid_set
+        {
+            len_of_smallest_valid_set = subset.len();
+            smallest_valid_quantum_entaglement =
+                smallest_valid_quantum_entaglement.min(subset.iter().copied().product());
         }
     }
 
     smallest_valid_quantum_entaglement
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_packages(input)
+    }
+
+    fn part_1(packages: &Self::Input) -> impl Display {
+        find_lowest_config(packages, 3)
+    }
+
+    fn part_2(packages: &Self::Input) -> impl Display {
+        find_lowest_config(packages, 4)
+    }
+}
+
 #[test]
 fn both_parts() {
-    let sample_packages = parse_packages(SAMPLE);
+    let sample_packages = parse_packages(SAMPLE).unwrap();
     assert_eq!(99, find_lowest_config(&sample_packages, 3));
     assert_eq!(44, find_lowest_config(&sample_packages, 4));
 
-    let input_packages = parse_packages(INPUT);
+    let Some(input) = test_input(2015, 24) else {
+        return;
+    };
+    let input_packages = parse_packages(&input).unwrap();
     assert_eq!(10439961859, find_lowest_config(&input_packages, 3));
     assert_eq!(72050269, find_lowest_config(&input_packages, 4));
 }
@@ -1,3 +1,11 @@
+use std::fmt::Display;
+
+use crate::util::{
+    math::mod_pow,
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
 /* How I derived the equation to find the number of iterations:
 I played with the shape of the iterations by row then by column. Starting at row 1 col 1 (which has value 1),
 each successive row has an incrementing value: For row 5, we get 1 (+1) 2 (+2) 4 (+3) 7 (+4) 11..., which has a shape like this:
@@ -28,21 +36,50 @@ fn nth(n: usize) -> usize {
     ((n * n) - n) / 2 + 1
 }
 
-fn calculate_code(iterations: usize) -> usize {
-    let mut value = 20151125;
-    for _ in 0..iterations {
-        value = value * 252533 % 33554393
+// Each step multiplies by the same number, so `iterations` steps multiply by its power
+fn calculate_code(iterations: usize) -> u64 {
+    20151125 * mod_pow(252533, iterations as u64, 33554393) % 33554393
+}
+
+// Pulls the row and column out of the sentence that the input is given in
+fn parse_position(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut numbers = input
+        .split(|ch: char| !ch.is_ascii_digit())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| parse_at(input, segment, "a row or column"));
+    let mut next = |expected| {
+        numbers
+            .next()
+            .unwrap_or_else(|| Err(ParseError::end_of_input(input, expected)))
+    };
+    Ok((next("a row")?, next("a column")?))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = (usize, usize);
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+    const HAS_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_position(input)
+    }
+
+    fn part_1((row, col): &Self::Input) -> impl Display {
+        calculate_code(calculate_iterations(*row, *col))
     }
-    value
 }
 
+pub const INPUT: &str = "To continue, please consult the code grid in the manual.  Enter the code at row 3010, column 3019.";
+
 #[test]
 fn part_1() {
     let sample_iterations = calculate_iterations(3, 4);
     assert_eq!(7981243, calculate_code(sample_iterations));
 
-    const ROW: usize = 3010;
-    const COL: usize = 3019;
-    let iterations = calculate_iterations(ROW, COL);
+    let (row, col) = parse_position(INPUT).unwrap();
+    let iterations = calculate_iterations(row, col);
     assert_eq!(8997277, calculate_code(iterations));
 }
@@ -1,53 +1,59 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
 
-use crate::util::Pair;
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Direction, Pair,
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
 
-fn dir_diff(dir: u8) -> Pair {
-    match dir {
-        b'^' => Pair(0, 1),
-        b'v' => Pair(0, -1),
-        b'>' => Pair(1, 0),
-        b'<' => Pair(-1, 0),
-        _ => unreachable!(),
-    }
+fn parse_directions(input: &str) -> Result<Vec<Direction>, ParseError> {
+    let directions = parse_chars(input, "a direction", |ch| {
+        Direction::from_char(ch).is_some()
+    })?;
+    Ok(directions
+        .chars()
+        .filter_map(Direction::from_char)
+        .collect())
 }
 
-fn houses_visited(input: &[u8]) -> usize {
+fn houses_visited(input: &[Direction]) -> usize {
     let start = Pair(0, 0);
     let mut houses: HashSet<Pair> = HashSet::new();
-    houses.insert(start.clone());
+    houses.insert(start);
 
     input
         .iter()
         .fold((houses, start), |(mut house_set, last_house), dir| {
-            let next_house = last_house + dir_diff(*dir);
-            house_set.insert(next_house.clone());
+            let next_house = last_house + dir.step();
+            house_set.insert(next_house);
             (house_set, next_house)
         })
         .0
         .len()
 }
 
-fn houses_visited_with_robo_santa(input: &[u8]) -> usize {
+fn houses_visited_with_robo_santa(input: &[Direction]) -> usize {
     let santa_start: Pair = Pair(0, 0);
     let robot_start: Pair = Pair(0, 0);
     let mut houses: HashSet<Pair> = HashSet::new();
-    houses.insert(santa_start.clone());
+    houses.insert(santa_start);
 
     input
         .iter()
         .enumerate()
         .fold((houses, santa_start, robot_start), |acc, (index, dir)| {
             let (mut house_set, last_santa, last_robot) = acc;
-            let diff = dir_diff(*dir);
+            let diff = dir.step();
             let (next_santa, next_robot, new_pos) = if index % 2 == 0 {
                 // Real Santa's turn
                 let new_pos = last_santa + diff;
-                (new_pos.clone(), last_robot, new_pos)
+                (new_pos, last_robot, new_pos)
             } else {
                 // Robo-Santa's turn
                 let new_pos = last_robot + diff;
-                (last_santa, new_pos.clone(), new_pos)
+                (last_santa, new_pos, new_pos)
             };
             house_set.insert(new_pos);
             (house_set, next_santa, next_robot)
@@ -56,20 +62,56 @@ fn houses_visited_with_robo_santa(input: &[u8]) -> usize {
         .len()
 }
 
-const S1: &[u8] = b"^>v<";
-const S2: &[u8] = b"^v^v^v^v^v";
-const INPUT: &[u8] = include_bytes!("./inputs/day3.txt");
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Direction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_directions(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        houses_visited(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        houses_visited_with_robo_santa(input)
+    }
+}
+
+#[cfg(test)]
+const S1: &str = "^>v<";
+#[cfg(test)]
+const S2: &str = "^v^v^v^v^v";
 
 #[test]
 fn part_1() {
-    assert_eq!(4, houses_visited(S1));
-    assert_eq!(2, houses_visited(S2));
-    assert_eq!(2565, houses_visited(INPUT));
+    assert_eq!(4, houses_visited(&parse_directions(S1).unwrap()));
+    assert_eq!(2, houses_visited(&parse_directions(S2).unwrap()));
+
+    let Some(input) = test_input(2015, 3) else {
+        return;
+    };
+    assert_eq!(2565, houses_visited(&parse_directions(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(3, houses_visited_with_robo_santa(S1));
-    assert_eq!(11, houses_visited_with_robo_santa(S2));
-    assert_eq!(2639, houses_visited_with_robo_santa(INPUT));
+    assert_eq!(
+        3,
+        houses_visited_with_robo_santa(&parse_directions(S1).unwrap())
+    );
+    assert_eq!(
+        11,
+        houses_visited_with_robo_santa(&parse_directions(S2).unwrap())
+    );
+
+    let Some(input) = test_input(2015, 3) else {
+        return;
+    };
+    assert_eq!(
+        2639,
+        houses_visited_with_robo_santa(&parse_directions(&input).unwrap())
+    );
 }
@@ -1,4 +1,10 @@
-use crate::util::generate_padded_hash;
+use std::fmt::Display;
+
+use crate::util::{
+    hash::padded_matches,
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
 
 fn count_hash_zeros(hash: [u8; 16]) -> usize {
     let mut num_zeros = 0;
@@ -11,29 +17,51 @@ fn count_hash_zeros(hash: [u8; 16]) -> usize {
             return num_zeros;
         }
     }
-    return 32;
+    32
 }
 
 fn find_hash_input(input: &[u8], num_zeros: usize) -> usize {
-    let mut pad = 1;
-    loop {
-        let hash = generate_padded_hash(input, pad);
-        if count_hash_zeros(hash) >= num_zeros {
-            return pad;
-        }
-        pad += 1;
+    let (pad, _) = padded_matches(input, 1, |&hash| {
+        (count_hash_zeros(hash) >= num_zeros).then_some(())
+    })
+    .next()
+    .unwrap();
+    pad
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<u8>;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+    const SLOW_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let key = parse_chars(input, "a secret key", |ch| !ch.is_whitespace())?;
+        Ok(key.as_bytes().to_vec())
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        find_hash_input(input, 5)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        find_hash_input(input, 6)
     }
 }
 
-const INPUT: &[u8] = b"yzbqklnj";
+pub const INPUT: &str = "yzbqklnj";
 
 #[test]
 fn part_1() {
-    assert_eq!(282749, find_hash_input(INPUT, 5));
+    assert_eq!(282749, find_hash_input(INPUT.as_bytes(), 5));
 }
 
+// Even spread across every core this is slow on a machine with only a few, so it stays behind the
+// `slow` feature. `aoc bench 2015 4 --slow` shows how long it takes
 #[test]
+#[cfg_attr(not(feature = "slow"), ignore = "slow, run with `--features slow`")]
 fn part_2() {
-    // In release mode, this takes ~1.34 seconds to run
-    // assert_eq!(9962624, find_hash_input(INPUT, 6));
+    assert_eq!(9962624, find_hash_input(INPUT.as_bytes(), 6));
 }
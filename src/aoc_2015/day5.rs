@@ -1,4 +1,11 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, non_empty},
+    solution::Solution,
+};
 
 fn is_nice(word: &[u8]) -> bool {
     let mut vowel_count = 0;
@@ -30,12 +37,27 @@ fn is_nice(word: &[u8]) -> bool {
     has_double && vowel_count >= 3
 }
 
-fn count_nice(input: &[u8]) -> usize {
-    input
-        .trim_ascii()
-        .split(|c| *c == b'\n')
-        .filter(|word| is_nice(*word))
-        .count()
+fn parse_words(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    let words = input
+        .trim()
+        .lines()
+        .map(|line| {
+            let word = line.trim();
+            if word.len() < 2 || !word.bytes().all(|ch| ch.is_ascii_lowercase()) {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "a word of at least two lowercase letters",
+                ));
+            }
+            Ok(word.as_bytes().to_vec())
+        })
+        .collect::<Result<_, _>>()?;
+    non_empty(input, words, "a word")
+}
+
+fn count_nice(words: &[Vec<u8>]) -> usize {
+    words.iter().filter(|word| is_nice(word)).count()
 }
 
 type Pair = (u8, u8);
@@ -77,26 +99,58 @@ fn is_really_nice(word: &[u8]) -> bool {
     false
 }
 
-fn count_really_nice(input: &[u8]) -> usize {
-    input
-        .trim_ascii()
-        .split(|c| *c == b'\n')
-        .filter(|word| is_really_nice(*word))
-        .count()
+fn count_really_nice(words: &[Vec<u8>]) -> usize {
+    words.iter().filter(|word| is_really_nice(word)).count()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_words(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_nice(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        count_really_nice(input)
+    }
 }
 
-const SAMPLE_1: &[u8] = b"ugknbfddgicrmopn\njchzalrnumimnmhp\nhaegwjzuvuyypxyu\ndvszwmarrgswjxmb\n";
-const SAMPLE_2: &[u8] = b"qjhvhtzxzqqjkmpb\nxxyxx\nuurcxstgmygtbstg\nieodomkazucvgmuy\n";
-const INPUT: &[u8] = include_bytes!("./inputs/day5.txt");
+#[cfg(test)]
+const SAMPLE_1: &str = "ugknbfddgicrmopn\njchzalrnumimnmhp\nhaegwjzuvuyypxyu\ndvszwmarrgswjxmb\n";
+#[cfg(test)]
+const SAMPLE_2: &str = "qjhvhtzxzqqjkmpb\nxxyxx\nuurcxstgmygtbstg\nieodomkazucvgmuy\n";
 
 #[test]
 fn part_1() {
-    assert_eq!(1, count_nice(SAMPLE_1));
-    assert_eq!(236, count_nice(INPUT));
+    assert_eq!(1, count_nice(&parse_words(SAMPLE_1).unwrap()));
+
+    let Some(input) = test_input(2015, 5) else {
+        return;
+    };
+    assert_eq!(236, count_nice(&parse_words(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(2, count_really_nice(SAMPLE_2));
-    assert_eq!(51, count_really_nice(INPUT));
+    assert_eq!(2, count_really_nice(&parse_words(SAMPLE_2).unwrap()));
+
+    let Some(input) = test_input(2015, 5) else {
+        return;
+    };
+    assert_eq!(51, count_really_nice(&parse_words(&input).unwrap()));
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_words("ugknbfddgicrmopn\n\nxxyxx").unwrap_err();
+    assert_eq!((2, 1), (err.line, err.column));
+    let err = parse_words("xxyxx\nx").unwrap_err();
+    assert_eq!((2, 1), (err.line, err.column));
+    assert!(parse_words("xxYxx").is_err());
 }
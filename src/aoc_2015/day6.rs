@@ -2,7 +2,13 @@ use core::str;
 use logos::{Lexer, Logos};
 use std::{collections::HashSet, fmt::Display};
 
-use crate::util::Pair;
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Pair,
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+};
 
 #[derive(Logos)]
 #[logos(skip r"[ ,\n]", skip r"through")]
@@ -18,7 +24,7 @@ enum Token {
     Toggle,
 
     // Other
-    #[regex("[0-9]+", |lex| lex.slice().parse::<i64>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<i64>().ok())]
     Integer(i64),
 }
 
@@ -39,57 +45,49 @@ enum Action {
     Toggle,
 }
 
-impl From<Token> for Action {
-    fn from(value: Token) -> Self {
-        match value {
-            Token::TurnOff => Action::TurnOff,
-            Token::TurnOn => Action::TurnOn,
-            Token::Toggle => Action::Toggle,
-            _ => panic!("Attempted to read action from number, parser was desynced"),
+impl Token {
+    fn take_action(self) -> Option<Action> {
+        match self {
+            Token::TurnOff => Some(Action::TurnOff),
+            Token::TurnOn => Some(Action::TurnOn),
+            Token::Toggle => Some(Action::Toggle),
+            _ => None,
         }
     }
-}
 
-impl From<Token> for i64 {
-    fn from(value: Token) -> Self {
-        match value {
-            Token::Integer(value) => value,
-            _ => panic!("Attempted to read number from action, parser was desynced"),
+    fn take_integer(self) -> Option<i64> {
+        match self {
+            Token::Integer(value) => Some(value),
+            _ => None,
         }
     }
 }
 
-struct Command {
+pub struct Command {
     action: Action,
     start: Pair,
     end: Pair,
 }
 
-fn read_line(lexer: &mut Lexer<'_, Token>) -> Option<Command> {
-    if let Some(action) = lexer.next() {
-        Some(Command {
-            action: action.unwrap().into(),
-            start: Pair(
-                lexer.next().unwrap().unwrap().into(),
-                lexer.next().unwrap().unwrap().into(),
-            ),
-            end: Pair(
-                lexer.next().unwrap().unwrap().into(),
-                lexer.next().unwrap().unwrap().into(),
-            ),
-        })
-    } else {
-        None
-    }
+fn read_line(lexer: &mut Lexer<'_, Token>) -> Result<Option<Command>, ParseError> {
+    let Some(action) = expect_token_or_end(lexer, "an action", Token::take_action)? else {
+        return Ok(None);
+    };
+    let mut coordinate = || expect_token(lexer, "a coordinate", Token::take_integer);
+    Ok(Some(Command {
+        action,
+        start: Pair(coordinate()?, coordinate()?),
+        end: Pair(coordinate()?, coordinate()?),
+    }))
 }
 
-fn parse_commands(input: &'static str) -> Vec<Command> {
+fn parse_commands(input: &str) -> Result<Vec<Command>, ParseError> {
     let mut lex = Token::lexer(input);
     let mut commands = Vec::<Command>::new();
-    while let Some(command) = read_line(&mut lex) {
+    while let Some(command) = read_line(&mut lex)? {
         commands.push(command);
     }
-    commands
+    non_empty(input, commands, "an action")
 }
 
 fn light(value: bool) -> i64 {
@@ -101,25 +99,25 @@ fn light(value: bool) -> i64 {
 // toggle we reach, we invert the toggle state, and when we return, we invert if the toggle
 // is active. Doing this in reverse order means we have terminal commands that can prevent
 // use from having to iterate through all commands.
-fn is_lit(commands: &Vec<Command>, x: i64, y: i64) -> i64 {
+fn is_lit(commands: &[Command], x: i64, y: i64) -> i64 {
     let mut toggled = false;
     for Command { action, start, end } in commands.iter().rev() {
         if start.0 <= x && x <= end.0 && start.1 <= y && y <= end.1 {
             match action {
-                Action::TurnOn => return light(true != toggled),
-                Action::TurnOff => return light(false != toggled),
+                Action::TurnOn => return light(!toggled),
+                Action::TurnOff => return light(toggled),
                 Action::Toggle => {
                     toggled = !toggled;
                 }
             }
         }
     }
-    light(false != toggled)
+    light(toggled)
 }
 
 // Since we need the full context of all commands that a pixel may go through, we can't
 // terminate early like with part 1. Still, it's just quick maffs.
-fn brightness_level(commands: &Vec<Command>, x: i64, y: i64) -> i64 {
+fn brightness_level(commands: &[Command], x: i64, y: i64) -> i64 {
     let mut level: i64 = 0;
     for Command { action, start, end } in commands.iter() {
         if start.0 <= x && x <= end.0 && start.1 <= y && y <= end.1 {
@@ -157,7 +155,7 @@ where
     (x_boundaries, y_boundaries)
 }
 
-fn get_lit_lights(commands: &Vec<Command>, f: fn(&Vec<Command>, i64, i64) -> i64) -> i64 {
+fn get_lit_lights(commands: &[Command], f: fn(&[Command], i64, i64) -> i64) -> i64 {
     let (x_boundaries, y_boundaries) = get_boundaries(commands.iter());
 
     let mut on_count: i64 = 0;
@@ -180,12 +178,31 @@ fn get_lit_lights(commands: &Vec<Command>, f: fn(&Vec<Command>, i64, i64) -> i64
     on_count
 }
 
-const INPUT: &'static str = include_str!("./inputs/day6.txt");
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_commands(input)
+    }
+
+    fn part_1(commands: &Self::Input) -> impl Display {
+        get_lit_lights(commands, is_lit)
+    }
+
+    fn part_2(commands: &Self::Input) -> impl Display {
+        get_lit_lights(commands, brightness_level)
+    }
+}
 
 // This was way faster than I was expecting
 #[test]
 fn both_parts() {
-    let commands = parse_commands(INPUT);
+    let Some(input) = test_input(2015, 6) else {
+        return;
+    };
+    let commands = parse_commands(&input).unwrap();
     assert_eq!(400_410, get_lit_lights(&commands, is_lit));
     assert_eq!(15_343_601, get_lit_lights(&commands, brightness_level));
 }
@@ -1,7 +1,14 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 use logos::{Lexer, Logos};
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, next_token, next_token_or_end},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[ \n]")]
 enum Token {
@@ -19,7 +26,7 @@ enum Token {
     #[token("->")]
     Arrow,
 
-    #[regex("[0-9]+", |lex| lex.slice().parse::<u16>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<u16>().ok())]
     Integer(u16),
 
     #[regex("[a-z]+", |lex| lex.slice().to_owned())]
@@ -28,57 +35,47 @@ enum Token {
 
 impl Token {
     fn is_not(&self) -> bool {
-        match self {
-            Self::Not => true,
-            _ => false,
-        }
+        matches!(self, Self::Not)
     }
 
-    fn take_source(self) -> Source {
+    fn take_source(self) -> Option<Source> {
         match self {
-            Self::Integer(value) => Source::Signal(value),
-            Self::Wire(value) => Source::Wire(value),
-            _ => panic!("Attempted to pull missing `source value"),
+            Self::Integer(value) => Some(Source::Signal(value)),
+            Self::Wire(value) => Some(Source::Wire(value)),
+            _ => None,
         }
     }
 
     fn is_arrow(&self) -> bool {
-        match self {
-            Self::Arrow => true,
-            _ => false,
-        }
+        matches!(self, Self::Arrow)
+    }
+
+    fn take_arrow(self) -> Option<()> {
+        self.is_arrow().then_some(())
     }
 
-    fn assert_arrow(self) {
+    // Shifting a 16 bit signal by 16 or more would overflow
+    fn take_shift(self) -> Option<u16> {
         match self {
-            Self::Arrow => (),
-            _ => panic!("Not an arrow"),
+            Self::Integer(value) if value < 16 => Some(value),
+            _ => None,
         }
     }
 
-    fn take_wire(self) -> String {
+    fn take_wire(self) -> Option<String> {
         match self {
-            Self::Wire(value) => value,
-            _ => panic!("Tried to take wire value from non-wire"),
+            Self::Wire(value) => Some(value),
+            _ => None,
         }
     }
 }
 
-enum Source {
+pub enum Source {
     Wire(String),
     Signal(u16),
 }
 
-impl Source {
-    fn take_signal(self) -> u16 {
-        match self {
-            Source::Signal(value) => value,
-            Source::Wire(_) => panic!("Attempted to pull number from wire"),
-        }
-    }
-}
-
-enum Gate {
+pub enum Gate {
     Send(Source, String),
     Not(Source, String),
     And(Source, Source, String),
@@ -101,44 +98,100 @@ impl Gate {
 }
 
 // I would like to figure out how to write better quick parsers
-fn read_line(lexer: &mut Lexer<'_, Token>) -> Option<Gate> {
-    if let Some(token) = lexer.next() {
-        let first = token.unwrap();
-        if first.is_not() {
-            Some(parse_not(lexer))
-        } else {
-            let operand_1 = first.take_source();
-            let next = lexer.next().unwrap().unwrap();
-            if next.is_arrow() {
-                let output = lexer.next().unwrap().unwrap().take_wire();
-                Some(Gate::Send(operand_1, output))
-            } else {
-                Some(parse_binop(lexer, operand_1, next))
-            }
-        }
+fn read_line(lexer: &mut Lexer<'_, Token>) -> Result<Option<Gate>, ParseError> {
+    let Some(first) = next_token_or_end(lexer, "a gate")? else {
+        return Ok(None);
+    };
+    if first.is_not() {
+        return parse_not(lexer).map(Some);
+    }
+
+    let operand_1 = first
+        .take_source()
+        .ok_or_else(|| ParseError::new(lexer.source(), lexer.span(), "a wire or signal"))?;
+    let next = next_token(lexer, "an operator or `->`")?;
+    if next.is_arrow() {
+        let output = expect_token(lexer, "a wire", Token::take_wire)?;
+        Ok(Some(Gate::Send(operand_1, output)))
     } else {
-        None
+        parse_binop(lexer, operand_1, next).map(Some)
     }
 }
 
-fn parse_not(lexer: &mut Lexer<'_, Token>) -> Gate {
-    let operand = lexer.next().unwrap().unwrap().take_source();
-    lexer.next().unwrap().unwrap().assert_arrow();
-    let output = lexer.next().unwrap().unwrap().take_wire();
-    Gate::Not(operand, output)
+fn read_output(lexer: &mut Lexer<'_, Token>) -> Result<String, ParseError> {
+    expect_token(lexer, "`->`", Token::take_arrow)?;
+    expect_token(lexer, "a wire", Token::take_wire)
+}
+
+fn parse_not(lexer: &mut Lexer<'_, Token>) -> Result<Gate, ParseError> {
+    let operand = expect_token(lexer, "a wire or signal", Token::take_source)?;
+    let output = read_output(lexer)?;
+    Ok(Gate::Not(operand, output))
+}
+
+fn parse_binop(
+    lexer: &mut Lexer<'_, Token>,
+    operand_1: Source,
+    op: Token,
+) -> Result<Gate, ParseError> {
+    let op_span = lexer.span();
+    let gate = match op {
+        Token::And => {
+            let operand_2 = expect_token(lexer, "a wire or signal", Token::take_source)?;
+            Gate::And(operand_1, operand_2, read_output(lexer)?)
+        }
+        Token::Or => {
+            let operand_2 = expect_token(lexer, "a wire or signal", Token::take_source)?;
+            Gate::Or(operand_1, operand_2, read_output(lexer)?)
+        }
+        Token::RShift => {
+            let offset = expect_token(lexer, "a shift below 16", Token::take_shift)?;
+            Gate::RShift(operand_1, offset, read_output(lexer)?)
+        }
+        Token::LShift => {
+            let offset = expect_token(lexer, "a shift below 16", Token::take_shift)?;
+            Gate::LShift(operand_1, offset, read_output(lexer)?)
+        }
+        _ => {
+            return Err(ParseError::new(
+                lexer.source(),
+                op_span,
+                "an operator or `->`",
+            ));
+        }
+    };
+    Ok(gate)
 }
 
-fn parse_binop(lexer: &mut Lexer<'_, Token>, operand_1: Source, op: Token) -> Gate {
-    let operand_2 = lexer.next().unwrap().unwrap().take_source();
-    lexer.next().unwrap().unwrap().assert_arrow();
-    let output = lexer.next().unwrap().unwrap().take_wire();
-    match op {
-        Token::And => Gate::And(operand_1, operand_2, output),
-        Token::Or => Gate::Or(operand_1, operand_2, output),
-        Token::RShift => Gate::RShift(operand_1, operand_2.take_signal(), output),
-        Token::LShift => Gate::LShift(operand_1, operand_2.take_signal(), output),
-        _ => panic!("Expected op, found some other thing"),
+fn parse_circuit(input: &str) -> Result<HashMap<String, Gate>, ParseError> {
+    let mut lex = Token::lexer(input);
+    let mut gate_map = HashMap::<String, Gate>::new();
+    while let Some(gate) = read_line(&mut lex)? {
+        gate_map.insert(gate.output_label(), gate);
+    }
+
+    // Every wire that's read from needs a gate driving it, which means going over the wires
+    // again now that all the gates are known. The one after each `->` is the gate's own output
+    let mut lex = Token::lexer(input);
+    let mut is_output = false;
+    while let Some(Ok(token)) = lex.next() {
+        if let Token::Wire(wire) = &token
+            && !is_output
+            && !gate_map.contains_key(wire)
+        {
+            return Err(ParseError::new(
+                input,
+                lex.span(),
+                "a wire driven by some gate",
+            ));
+        }
+        is_output = token.is_arrow();
+    }
+    if !gate_map.contains_key("a") {
+        return Err(ParseError::end_of_input(input, "a gate driving wire `a`"));
     }
+
+    Ok(gate_map)
 }
 
 fn calculate_wire(
@@ -151,8 +204,8 @@ fn calculate_wire(
     }
     let cur_gate = map.get(label).unwrap();
     let result = match cur_gate {
-        Gate::Send(source, _) => extract_value(&source, map, cache),
-        Gate::Not(source, _) => extract_value(&source, map, cache) ^ u16::MAX,
+        Gate::Send(source, _) => extract_value(source, map, cache),
+        Gate::Not(source, _) => extract_value(source, map, cache) ^ u16::MAX,
         Gate::And(source_1, source_2, _) => {
             extract_value(source_1, map, cache) & extract_value(source_2, map, cache)
         }
@@ -173,39 +226,72 @@ fn extract_value(
     cache: &mut HashMap<String, u16>,
 ) -> u16 {
     match source {
-        Source::Signal(value) => return *value,
-        Source::Wire(label) => calculate_wire(&label, map, cache),
+        Source::Signal(value) => *value,
+        Source::Wire(label) => calculate_wire(label, map, cache),
     }
 }
 
-fn calculate_all_wires(input: &str, b_override: Option<u16>) -> u16 {
-    let mut lex = Token::lexer(input);
-
-    let mut gate_map = HashMap::<String, Gate>::new();
+fn calculate_all_wires(gate_map: &HashMap<String, Gate>, b_override: Option<u16>) -> u16 {
     let mut gate_cache = HashMap::<String, u16>::new();
     if let Some(b_override) = b_override {
         gate_cache.insert(String::from("b"), b_override);
     }
 
-    while let Some(gate) = read_line(&mut lex) {
-        gate_map.insert(gate.output_label(), gate);
-    }
-
-    let gate_map = gate_map; // Remove mutability
-
     for (wire_label, _) in gate_map.iter() {
-        calculate_wire(wire_label, &gate_map, &mut gate_cache);
+        calculate_wire(wire_label, gate_map, &mut gate_cache);
     }
 
     *gate_cache.get("a").unwrap()
 }
 
-const INPUT: &'static str = include_str!("./inputs/day7.txt");
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = HashMap<String, Gate>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_circuit(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        calculate_all_wires(input, None)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        let a = calculate_all_wires(input, None);
+        calculate_all_wires(input, Some(a))
+    }
+}
 
 #[test]
 fn both_parts() {
-    let part_1_answer = calculate_all_wires(INPUT, None);
+    let Some(input) = test_input(2015, 7) else {
+        return;
+    };
+    let gate_map = parse_circuit(&input).unwrap();
+    let part_1_answer = calculate_all_wires(&gate_map, None);
     assert_eq!(3176, part_1_answer);
-    let part_2_answer = calculate_all_wires(INPUT, Some(part_1_answer));
+    let part_2_answer = calculate_all_wires(&gate_map, Some(part_1_answer));
     assert_eq!(14710, part_2_answer);
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_circuit("123 -> x\nx AND -> y\n").err().unwrap();
+    assert_eq!((2, 7), (err.line, err.column));
+    assert_eq!("a wire or signal", err.expected);
+
+    let err = parse_circuit("x LSHIFT 2 -> ").err().unwrap();
+    assert_eq!(None, err.found);
+
+    let err = parse_circuit("123 -> x\nx LSHIFT 16 -> a").err().unwrap();
+    assert_eq!("a shift below 16", err.expected);
+    let err = parse_circuit("123 -> x\nx AND y -> a").err().unwrap();
+    assert_eq!(
+        (2, 7, "a wire driven by some gate"),
+        (err.line, err.column, err.expected)
+    );
+    let err = parse_circuit("123 -> x\n").err().unwrap();
+    assert_eq!("a gate driving wire `a`", err.expected);
+    assert!(parse_circuit("").is_err());
+}
@@ -1,8 +1,17 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, tokenize},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[\n]")]
-enum Token {
+pub enum Token {
     #[token(r#"""#)]
     Quote,
 
@@ -51,24 +60,63 @@ impl Token {
     }
 }
 
-const INPUT: &'static str = include_str!("./inputs/day8.txt");
+fn parse_strings(input: &str) -> Result<Vec<Token>, ParseError> {
+    // The lexer only sees the pieces, so check each line is wrapped in quotes first
+    for line in input.trim().lines() {
+        let literal = line.trim();
+        if literal.len() < 2 || !literal.starts_with('"') || !literal.ends_with('"') {
+            return Err(ParseError::at(input, line, "a string literal in quotes"));
+        }
+    }
+    tokenize(input, "a string literal")
+}
 
-#[test]
-fn part_1() {
+fn decoded_diff(tokens: &[Token]) -> usize {
     let mut diff = 0;
-    for token_result in Token::lexer(INPUT) {
-        let token = token_result.unwrap();
+    for token in tokens {
         diff += token.code_size() - token.data_size();
     }
-    assert_eq!(1350, diff);
+    diff
 }
 
-#[test]
-fn part_2() {
+fn encoded_diff(tokens: &[Token]) -> usize {
     let mut diff = 0;
-    for token_result in Token::lexer(INPUT) {
-        let token = token_result.unwrap();
+    for token in tokens {
         diff += token.new_size() - token.code_size();
     }
-    assert_eq!(2085, diff);
+    diff
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Token>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_strings(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        decoded_diff(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        encoded_diff(input)
+    }
+}
+
+#[test]
+fn part_1() {
+    let Some(input) = test_input(2015, 8) else {
+        return;
+    };
+    assert_eq!(1350, decoded_diff(&parse_strings(&input).unwrap()));
+}
+
+#[test]
+fn part_2() {
+    let Some(input) = test_input(2015, 8) else {
+        return;
+    };
+    assert_eq!(2085, encoded_diff(&parse_strings(&input).unwrap()));
 }
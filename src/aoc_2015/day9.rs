@@ -1,138 +1,164 @@
-use std::{collections::HashMap, usize};
+use std::fmt::Display;
 
-use itertools::Itertools;
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    intern::Interner,
+    parse::{ParseError, expect_token, expect_token_or_end},
+    solution::Solution,
+    tsp::{self, Goal, MAX_PLACES, Route},
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
+London to Dublin = 464
+London to Belfast = 518
+Dublin to Belfast = 141";
+
 #[derive(Logos)]
 #[logos(skip r"[ \n=]", skip "to")]
-enum Token {
-    #[token("Faerun")]
-    Faerun,
-
-    #[token("Tristram")]
-    Tristram,
-
-    #[token("Tambi")]
-    Tambi,
-
-    #[token("Norrath")]
-    Norrath,
-
-    #[token("Snowdin")]
-    Snowdin,
-
-    #[token("Straylight")]
-    Straylight,
-
-    #[token("AlphaCentauri")]
-    AlphaCentauri,
+enum Token<'s> {
+    #[regex(r"[A-Za-z]+")]
+    Place(&'s str),
 
-    #[token("Arbre")]
-    Arbre,
-
-    #[regex(r"[0-9]+", |lex| lex.slice().parse::<usize>().unwrap())]
+    #[regex(r"[0-9]+", |lex| lex.slice().parse::<usize>().ok())]
     Distance(usize),
 }
 
-impl Token {
-    fn take_city(self) -> City {
+impl<'s> Token<'s> {
+    fn take_place(self) -> Option<&'s str> {
         match self {
-            Self::Faerun => City::Faerun,
-            Self::Tristram => City::Tristram,
-            Self::Tambi => City::Tambi,
-            Self::Norrath => City::Norrath,
-            Self::Snowdin => City::Snowdin,
-            Self::Straylight => City::Straylight,
-            Self::AlphaCentauri => City::AlphaCentauri,
-            Self::Arbre => City::Arbre,
-            Self::Distance(_) => panic!("Expected city, found distance"),
+            Self::Place(place) => Some(place),
+            _ => None,
         }
     }
 
-    fn take_dist(self) -> usize {
+    fn take_dist(self) -> Option<usize> {
         match self {
-            Self::Distance(dist) => dist,
-            _ => panic!("Expected distance, found city"),
+            Self::Distance(dist) => Some(dist),
+            _ => None,
         }
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-enum City {
-    Faerun,
-    Tristram,
-    Tambi,
-    Norrath,
-    Snowdin,
-    Straylight,
-    AlphaCentauri,
-    Arbre,
-}
-
-impl City {
-    fn all() -> Vec<City> {
-        vec![
-            City::Faerun,
-            City::Tristram,
-            City::Tambi,
-            City::Norrath,
-            City::Snowdin,
-            City::Straylight,
-            City::AlphaCentauri,
-            City::Arbre,
-        ]
-    }
+// Every place named in the input, with `distances[a][b]` being how far apart places `a` and `b`
+// are
+pub struct DistanceMatrix {
+    pub places: Vec<String>,
+    pub distances: Vec<Vec<usize>>,
 }
 
-fn build_dist_map(input: &str) -> HashMap<(City, City), usize> {
-    let mut h = HashMap::new();
-
+fn build_dist_matrix(input: &str) -> Result<DistanceMatrix, ParseError> {
+    let mut places = Interner::new();
+    let mut edges = Vec::new();
+
+    // Routing gets too slow to bother with past `MAX_PLACES`
+    let mut intern = |place| match places.intern(place) {
+        idx if idx < MAX_PLACES => Ok(idx),
+        _ => Err(ParseError::at(
+            input,
+            place,
+            "one of the 16 places already named",
+        )),
+    };
     let mut lex = Token::lexer(input);
-    while let Some(city_1) = lex.next() {
-        let city_1 = city_1.unwrap().take_city();
-        let city_2 = lex.next().unwrap().unwrap().take_city();
-        let dist = lex.next().unwrap().unwrap().take_dist();
+    while let Some(place_1) = expect_token_or_end(&mut lex, "a place", Token::take_place)? {
+        let place_1 = intern(place_1)?;
+        let place_2 = intern(expect_token(&mut lex, "a place", Token::take_place)?)?;
+        let dist = expect_token(&mut lex, "a distance", Token::take_dist)?;
+        edges.push((place_1, place_2, dist));
+    }
+    if edges.is_empty() {
+        return Err(ParseError::end_of_input(input, "a place"));
+    }
 
-        h.insert((city_1, city_2), dist);
-        h.insert((city_2, city_1), dist);
+    let mut distances = vec![vec![None; places.len()]; places.len()];
+    for (from, to, dist) in edges {
+        distances[from][to] = Some(dist);
+        distances[to][from] = Some(dist);
     }
+    let distances = distances
+        .into_iter()
+        .enumerate()
+        .map(|(from, row)| {
+            row.into_iter()
+                .enumerate()
+                .map(|(to, dist)| match dist {
+                    Some(dist) => Ok(dist),
+                    None if from == to => Ok(0),
+                    None => Err(ParseError::end_of_input(
+                        input,
+                        "a distance between every pair of places",
+                    )),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(DistanceMatrix {
+        places: places.into_names(),
+        distances,
+    })
+}
 
-    h
+fn find_cheapest_route_cost(matrix: &DistanceMatrix) -> usize {
+    tsp::solve(&matrix.distances, Route::Path, Goal::Shortest, None)
+        .unwrap()
+        .0
 }
 
-fn route_cost(route: Vec<City>, lookup: &HashMap<(City, City), usize>) -> usize {
-    route.windows(2).fold(0, |sum, win| {
-        sum + *lookup.get(&(win[0].clone(), win[1].clone())).unwrap()
-    })
+fn find_highest_route_cost(matrix: &DistanceMatrix) -> usize {
+    tsp::solve(&matrix.distances, Route::Path, Goal::Longest, None)
+        .unwrap()
+        .0
 }
 
-fn find_cheapest_route_cost(lookup: &HashMap<(City, City), usize>) -> usize {
-    let mut min_cost = usize::MAX;
-    for perm in City::all().into_iter().permutations(8) {
-        let cost = route_cost(perm, lookup);
-        if cost < min_cost {
-            min_cost = cost;
-        }
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = DistanceMatrix;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        build_dist_matrix(input)
     }
-    min_cost
-}
 
-fn find_highest_route_cost(lookup: &HashMap<(City, City), usize>) -> usize {
-    let mut max_cost = 0;
-    for perm in City::all().into_iter().permutations(8) {
-        let cost = route_cost(perm, lookup);
-        if cost > max_cost {
-            max_cost = cost;
-        }
+    fn part_1(matrix: &Self::Input) -> impl Display {
+        find_cheapest_route_cost(matrix)
+    }
+
+    fn part_2(matrix: &Self::Input) -> impl Display {
+        find_highest_route_cost(matrix)
     }
-    max_cost
 }
 
-const INPUT: &'static str = include_str!("./inputs/day9.txt");
+#[test]
+fn sample() {
+    let matrix = build_dist_matrix(SAMPLE).unwrap();
+    assert_eq!(vec!["London", "Dublin", "Belfast"], matrix.places);
+    assert_eq!(605, find_cheapest_route_cost(&matrix));
+    assert_eq!(982, find_highest_route_cost(&matrix));
+
+    let missing = build_dist_matrix("London to Dublin = 464\nDublin to Belfast = 141")
+        .err()
+        .unwrap();
+    assert_eq!("a distance between every pair of places", missing.expected);
+
+    let too_many = ('b'..='q')
+        .map(|place| format!("Xa to X{place} = 1\n"))
+        .collect::<String>();
+    let error = build_dist_matrix(&too_many).err().unwrap();
+    assert_eq!((16, 7), (error.line, error.column));
+    assert_eq!("one of the 16 places already named", error.expected);
+}
 
 #[test]
 fn both_parts() {
-    let lookup = build_dist_map(INPUT);
-    assert_eq!(117, find_cheapest_route_cost(&lookup));
-    assert_eq!(909, find_highest_route_cost(&lookup));
+    let Some(input) = test_input(2015, 9) else {
+        return;
+    };
+    let matrix = build_dist_matrix(&input).unwrap();
+    assert_eq!(117, find_cheapest_route_cost(&matrix));
+    assert_eq!(909, find_highest_route_cost(&matrix));
 }
@@ -1,13 +1,23 @@
+use std::fmt::Display;
+
 use fxhash::FxHashSet;
 use logos::{Lexer, Logos};
 
-fn parse_dist(lex: &mut Lexer<Token>) -> i64 {
-    lex.slice()[1..].parse::<i64>().unwrap()
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Direction, Pair,
+    parse::{ParseError, tokenize},
+    solution::Solution,
+};
+
+fn parse_dist(lex: &mut Lexer<Token>) -> Option<i64> {
+    lex.slice()[1..].parse::<i64>().ok()
 }
 
 #[derive(Logos)]
 #[logos(skip r"[ ,\n]")]
-enum Token {
+pub enum Token {
     #[regex("R[0-9]+", parse_dist)]
     Right(i64),
 
@@ -15,105 +25,102 @@ enum Token {
     Left(i64),
 }
 
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Dir {
-    fn vector(&self, token: &Token) -> (i64, i64, Self) {
-        match token {
-            &Token::Right(x) => match self {
-                Self::Up => (x, 0, Self::Right),
-                Self::Right => (0, -x, Self::Down),
-                Self::Down => (-x, 0, Self::Left),
-                Self::Left => (0, x, Self::Up),
-            },
-            &Token::Left(x) => match self {
-                Self::Up => (-x, 0, Self::Left),
-                Self::Left => (0, -x, Self::Down),
-                Self::Down => (x, 0, Self::Right),
-                Self::Right => (0, x, Self::Up),
-            },
+impl Token {
+    // Turns to face the new direction and says how far to walk that way
+    fn turn(&self, facing: Direction) -> (Direction, i64) {
+        match *self {
+            Token::Right(dist) => (facing.turn_right(), dist),
+            Token::Left(dist) => (facing.turn_left(), dist),
         }
     }
 }
 
-fn dist_away(input: &str) -> i64 {
-    let mut x = 0;
-    let mut y = 0;
-    let mut dir = Dir::Up;
-
-    let tokens = Token::lexer(input);
-    for token in tokens.into_iter() {
-        let token = token.unwrap();
-        let (new_x, new_y, new_dir) = dir.vector(&token);
-        x += new_x;
-        y += new_y;
-        dir = new_dir;
-    }
-
-    return x.abs() + y.abs();
+fn parse_instructions(input: &str) -> Result<Vec<Token>, ParseError> {
+    tokenize(input, "a turn like `R2` or `L3`")
 }
 
-// Removing `.rev()` doesn't make a difference, but I think it's technically correct because it could matter in theory
-fn points_in_vec(
-    (start_x, start_y): (i64, i64),
-    (end_x, end_y): (i64, i64),
-    facing: &Dir,
-) -> Vec<(i64, i64)> {
-    match facing {
-        Dir::Up => (start_y + 1..end_y + 1).map(|y| (start_x, y)).collect(),
-        Dir::Down => (end_y..start_y).rev().map(|y| (start_x, y)).collect(),
-        Dir::Right => (start_x + 1..end_x + 1).map(|x| (x, start_y)).collect(),
-        Dir::Left => (end_x..start_x).rev().map(|x| (x, start_y)).collect(),
+fn dist_away(instructions: &[Token]) -> i64 {
+    let mut pos = Pair::ORIGIN;
+    let mut facing = Direction::Up;
+
+    for token in instructions {
+        let (new_facing, dist) = token.turn(facing);
+        pos += new_facing.step() * dist;
+        facing = new_facing;
     }
+
+    pos.manhattan(Pair::ORIGIN)
 }
 
-fn first_visited_twice(input: &str) -> i64 {
-    let mut x = 0;
-    let mut y = 0;
-    let mut dir = Dir::Up;
-
-    let mut visited = FxHashSet::<(i64, i64)>::default();
-
-    let tokens = Token::lexer(input);
-    for token in tokens.into_iter() {
-        let token = token.unwrap();
-        let (delta_x, delta_y, new_dir) = dir.vector(&token);
-        let new_x = x + delta_x;
-        let new_y = y + delta_y;
-        for coord in points_in_vec((x, y), (new_x, new_y), &new_dir) {
-            if visited.contains(&coord) {
-                let (final_x, final_y) = coord;
-                return final_x.abs() + final_y.abs();
-            } else {
-                visited.insert(coord);
+fn first_visited_twice(instructions: &[Token]) -> Option<i64> {
+    let mut pos = Pair::ORIGIN;
+    let mut facing = Direction::Up;
+
+    let mut visited = FxHashSet::<Pair>::default();
+
+    for token in instructions {
+        let (new_facing, dist) = token.turn(facing);
+        for _ in 0..dist {
+            pos += new_facing.step();
+            if !visited.insert(pos) {
+                return Some(pos.manhattan(Pair::ORIGIN));
             }
         }
+        facing = new_facing;
+    }
+
+    None
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Token>;
 
-        x = new_x;
-        y = new_y;
-        dir = new_dir;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
     }
 
-    unreachable!()
+    fn part_1(input: &Self::Input) -> impl Display {
+        dist_away(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        first_visited_twice(input).expect("no location is visited twice")
+    }
 }
 
-const SAMPLE_1: &'static str = "R5, L5, R5, R3";
-const SAMPLE_2: &'static str = "R8, R4, R4, R8";
-const INPUT: &'static str = include_str!("./inputs/day1.txt");
+#[cfg(test)]
+const SAMPLE_1: &str = "R5, L5, R5, R3";
+#[cfg(test)]
+const SAMPLE_2: &str = "R8, R4, R4, R8";
 
 #[test]
 fn part_1() {
-    assert_eq!(12, dist_away(SAMPLE_1));
-    assert_eq!(291, dist_away(INPUT));
+    assert_eq!(12, dist_away(&parse_instructions(SAMPLE_1).unwrap()));
+
+    let Some(input) = test_input(2016, 1) else {
+        return;
+    };
+    assert_eq!(291, dist_away(&parse_instructions(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(4, first_visited_twice(SAMPLE_2));
-    assert_eq!(159, first_visited_twice(INPUT));
+    assert_eq!(
+        Some(4),
+        first_visited_twice(&parse_instructions(SAMPLE_2).unwrap())
+    );
+    assert_eq!(
+        None,
+        first_visited_twice(&parse_instructions(SAMPLE_1).unwrap())
+    );
+
+    let Some(input) = test_input(2016, 1) else {
+        return;
+    };
+    assert_eq!(
+        Some(159),
+        first_visited_twice(&parse_instructions(&input).unwrap())
+    );
 }
@@ -1,40 +1,41 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
 use fxhash::FxHashMap;
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, next_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[\n ]", skip "gives low to", skip "and high to", skip "goes to")]
 enum Token {
-    #[regex(r"bot [0-9]+", |lex| lex.slice()[4..].parse::<usize>().unwrap())]
+    #[regex(r"bot [0-9]+", |lex| lex.slice()[4..].parse::<usize>().ok())]
     Bot(usize),
 
-    #[regex(r"output [0-9]+", |lex| lex.slice()[7..].parse::<usize>().unwrap())]
+    #[regex(r"output [0-9]+", |lex| lex.slice()[7..].parse::<usize>().ok())]
     Output(usize),
 
-    #[regex(r"value [0-9]+", |lex| lex.slice()[6..].parse::<usize>().unwrap())]
+    #[regex(r"value [0-9]+", |lex| lex.slice()[6..].parse::<usize>().ok())]
     Value(usize),
 }
 
 impl Token {
-    fn to_value(self) -> usize {
-        match self {
-            Self::Bot(val) | Self::Output(val) | Self::Value(val) => val,
-        }
-    }
-
-    fn is_input(&self) -> bool {
+    fn take_bot(self) -> Option<usize> {
         match self {
-            &Self::Value(_) => true,
-            _ => false,
+            Self::Bot(val) => Some(val),
+            _ => None,
         }
     }
 
-    fn to_destination(self) -> Dest {
+    fn take_destination(self) -> Option<Dest> {
         match self {
-            Self::Bot(val) => Dest::Bot(val),
-            Self::Output(val) => Dest::Output(val),
-            _ => panic!("Attempted to treat value op as an output"),
+            Self::Bot(val) => Some(Dest::Bot(val)),
+            Self::Output(val) => Some(Dest::Output(val)),
+            Self::Value(_) => None,
         }
     }
 }
@@ -55,31 +56,79 @@ struct ValueIns {
     bot: usize,
 }
 
-fn parse_instructions(input: &str) -> (Vec<ValueIns>, FxHashMap<usize, Transfer>, Vec<usize>) {
+type Instructions = (Vec<ValueIns>, FxHashMap<usize, Transfer>, Vec<usize>);
+
+fn parse_instructions(input: &str) -> Result<Instructions, ParseError> {
     let mut values = Vec::new();
     let mut transfers = FxHashMap::default();
     let mut all_ids = Vec::new();
+    // Every bot that gets handed a chip, along with where it was named
+    let mut receivers = Vec::new();
     let mut tokens = Token::lexer(input);
-    while let Some(Ok(head)) = tokens.next() {
-        let is_input = head.is_input();
-        let input_value = head.to_value();
-        if is_input {
-            let bot = tokens.next().unwrap().unwrap().to_value();
-            values.push(ValueIns {
-                value: input_value,
-                bot,
-            });
-        } else {
-            all_ids.push(input_value);
-            let low = tokens.next().unwrap().unwrap().to_destination();
-            let high = tokens.next().unwrap().unwrap().to_destination();
-            transfers.insert(input_value, Transfer { low, high });
+    while let Some(head) = next_token_or_end(&mut tokens, "a `value` or `bot` instruction")? {
+        match head {
+            Token::Value(value) => {
+                let bot = expect_token(&mut tokens, "a bot to take the value", Token::take_bot)?;
+                receivers.push((bot, tokens.span()));
+                values.push(ValueIns { value, bot });
+            }
+            Token::Bot(id) => {
+                if transfers.contains_key(&id) {
+                    return Err(ParseError::new(
+                        input,
+                        tokens.span(),
+                        "a bot that doesn't have an instruction yet",
+                    ));
+                }
+                all_ids.push(id);
+                let mut destination = || {
+                    let dest =
+                        expect_token(&mut tokens, "a bot or output", Token::take_destination)?;
+                    if let Dest::Bot(bot) = dest {
+                        receivers.push((bot, tokens.span()));
+                    }
+                    Ok(dest)
+                };
+                let low = destination()?;
+                let high = destination()?;
+                transfers.insert(id, Transfer { low, high });
+            }
+            Token::Output(_) => {
+                return Err(ParseError::new(
+                    tokens.source(),
+                    tokens.span(),
+                    "a `value` or `bot` instruction",
+                ));
+            }
         }
     }
-    (values, transfers, all_ids)
+
+    // Each bot needs an instruction for what to do with its chips, and can only hold two of them
+    let mut handed = FxHashMap::default();
+    for (bot, span) in receivers {
+        let count = handed.entry(bot).or_insert(0);
+        *count += 1;
+        if !transfers.contains_key(&bot) {
+            return Err(ParseError::new(
+                input,
+                span,
+                "a bot with its own instruction",
+            ));
+        }
+        if *count > 2 {
+            return Err(ParseError::new(
+                input,
+                span,
+                "a bot that's handed at most two chips",
+            ));
+        }
+    }
+
+    let values = non_empty(input, values, "a `value` instruction")?;
+    Ok((values, transfers, all_ids))
 }
 
-enum BotState {
+pub enum BotState {
     Empty,
     One(usize),
     Both(usize, usize),
@@ -88,13 +137,13 @@ enum BotState {
 impl BotState {
     fn add_value(&mut self, new_value: usize) -> bool {
         let mut resolved = false;
-        *self = match self {
-            &mut Self::Empty => Self::One(new_value),
-            &mut Self::One(high_value) => {
+        *self = match *self {
+            Self::Empty => Self::One(new_value),
+            Self::One(high_value) => {
                 resolved = true;
                 Self::Both(new_value, high_value)
             }
-            &mut Self::Both(..) => panic!("Bot already has 2 values"),
+            Self::Both(..) => panic!("Bot already has 2 values"),
         };
         resolved
     }
@@ -124,7 +173,6 @@ fn initialize_bot_states(
 
     let mut resolved_bots = VecDeque::new();
     for ValueIns { value, bot } in values {
-        // bot_map.entry(value).and_modify()
         let state = bot_map.get_mut(&bot).unwrap();
         if state.add_value(value) {
             resolved_bots.push_back(bot);
@@ -134,8 +182,10 @@ fn initialize_bot_states(
     (bot_map, resolved_bots)
 }
 
-fn process_bot_commands(input: &str) -> (FxHashMap<usize, usize>, FxHashMap<usize, BotState>) {
-    let (values, transfers, all_ids) = parse_instructions(input);
+pub type BotResults = (FxHashMap<usize, usize>, FxHashMap<usize, BotState>);
+
+fn process_bot_commands(input: &str) -> Result<BotResults, ParseError> {
+    let (values, transfers, all_ids) = parse_instructions(input)?;
 
     // Initialize bot states
     let (mut bot_map, mut resolved) = initialize_bot_states(all_ids, values);
@@ -169,28 +219,71 @@ fn process_bot_commands(input: &str) -> (FxHashMap<usize, usize>, FxHashMap<usiz
         }
     }
 
-    (output_map, bot_map)
+    Ok((output_map, bot_map))
 }
 
+// Bots that never got both their chips didn't compare anything
 fn find_part_1_bot(states: &FxHashMap<usize, BotState>) -> Option<usize> {
-    for state in states {
-        let (low, high) = state.1.get_low_high();
-        if low == 17 && high == 61 {
-            return Some(*state.0);
+    for (&id, state) in states {
+        if let BotState::Both(..) = state
+            && state.get_low_high() == (17, 61)
+        {
+            return Some(id);
         }
     }
     None
 }
 
-const INPUT: &str = include_str!("./inputs/day10.txt");
+fn output_product(outputs: &FxHashMap<usize, usize>) -> Option<usize> {
+    Some(outputs.get(&0)? * outputs.get(&1)? * outputs.get(&2)?)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = BotResults;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        process_bot_commands(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        find_part_1_bot(&input.1).expect("no bot compares chips 17 and 61")
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        output_product(&input.0).expect("outputs 0, 1 and 2 should all get a chip")
+    }
+}
 
 #[test]
 fn both_parts() {
-    let (outputs, states) = process_bot_commands(INPUT);
+    let Some(input) = test_input(2016, 10) else {
+        return;
+    };
+    let (outputs, states) = process_bot_commands(&input).unwrap();
     assert_eq!(Some(56), find_part_1_bot(&states));
+    assert_eq!(Some(7847), output_product(&outputs));
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_instructions("value 5 goes to bot 2\nvalue 3 goes to bot 1\nbot 2 gives low to bot 1 and high to output 0")
+        .err()
+        .unwrap();
+    assert_eq!(
+        (2, 17, "a bot with its own instruction"),
+        (err.line, err.column, err.expected)
+    );
+
+    let err = parse_instructions("value 5 goes to bot 2\nvalue 3 goes to bot 2\nvalue 1 goes to bot 2\nbot 2 gives low to output 1 and high to output 0")
+        .err()
+        .unwrap();
+    assert_eq!((3, 17), (err.line, err.column));
 
-    let prod = outputs.get(&0).unwrap_or(&0)
-        * outputs.get(&1).unwrap_or(&0)
-        * outputs.get(&2).unwrap_or(&0);
-    assert_eq!(7847, prod);
+    let err = parse_instructions("bot 2 gives low to output 1 and high to output 0\nbot 2 gives low to output 2 and high to output 3")
+        .err()
+        .unwrap();
+    assert_eq!((2, 1), (err.line, err.column));
+    assert!(parse_instructions("\n").is_err());
 }
@@ -1,19 +1,21 @@
-use std::collections::VecDeque;
+use std::fmt::Display;
 
 use itertools::Itertools;
-use nohash_hasher::IntSet;
 
-// SAMPLE
-// The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
-// The second floor contains a hydrogen generator.
-// The third floor contains a lithium generator.
-// The fourth floor contains nothing relevant.
-
-// INPUT
-// The first floor contains a polonium generator, a thulium generator, a thulium-compatible microchip, a promethium generator, a ruthenium generator, a ruthenium-compatible microchip, a cobalt generator, and a cobalt-compatible microchip.
-// The second floor contains a polonium-compatible microchip and a promethium-compatible microchip.
-// The third floor contains nothing relevant.
-// The fourth floor contains nothing relevant.
+#[cfg(test)]
+const SAMPLE: &str = "
+The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.
+";
+
+pub const INPUT: &str = "
+The first floor contains a polonium generator, a thulium generator, a thulium-compatible microchip, a promethium generator, a ruthenium generator, a ruthenium-compatible microchip, a cobalt generator, and a cobalt-compatible microchip.
+The second floor contains a polonium-compatible microchip and a promethium-compatible microchip.
+The third floor contains nothing relevant.
+The fourth floor contains nothing relevant.
+";
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Element {
@@ -30,6 +32,23 @@ enum Element {
 }
 
 impl Element {
+    const ALL: [Element; 7] = [
+        Hydrogen, Lithium, Thulium, Ruthenium, Cobalt, Polonium, Promethium,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hydrogen" => Some(Hydrogen),
+            "lithium" => Some(Lithium),
+            "thulium" => Some(Thulium),
+            "ruthenium" => Some(Ruthenium),
+            "cobalt" => Some(Cobalt),
+            "polonium" => Some(Polonium),
+            "promethium" => Some(Promethium),
+            _ => None,
+        }
+    }
+
     fn base_value(&self) -> u64 {
         // For the 8 bits that make up the specific machines on each floor,
         // each element is a bit in a specific place
@@ -92,8 +111,10 @@ impl Machine {
 
 use Machine::*;
 
+use crate::util::{parse::ParseError, search::bfs, solution::Solution};
+
 #[derive(PartialEq, Eq, Clone)]
-struct Building {
+pub struct Building {
     elevator: u8,
     floor0: Vec<Machine>,
     floor1: Vec<Machine>,
@@ -102,7 +123,7 @@ struct Building {
 }
 
 // A valid floor is one where either there are no generators, there are no microchips, or every microchip has a matching generator.
-fn is_floor_valid(floor: &Vec<Machine>) -> bool {
+fn is_floor_valid(floor: &[Machine]) -> bool {
     let rtg_elems: Vec<Element> = floor
         .iter()
         .filter(|m| m.is_rtg())
@@ -118,46 +139,22 @@ fn is_floor_valid(floor: &Vec<Machine>) -> bool {
 }
 
 impl Building {
-    // Parsing these does not sound like fun
-    fn create_sample() -> Self {
-        Self {
-            elevator: 0,
-            floor0: vec![Microchip(Hydrogen), Microchip(Lithium)],
-            floor1: vec![Generator(Hydrogen)],
-            floor2: vec![Generator(Lithium)],
-            floor3: Vec::new(),
+    // The problem for part 2 states that there are 2 new elements, elerium and dilithium. However,
+    // any two elements the building doesn't use yet can stand in for them, since it won't change
+    // the output what the elements are called.
+    fn with_extra_pairs(&self) -> Option<Self> {
+        let used = self.building_hash();
+        let mut unused = Element::ALL.into_iter().filter(|element| {
+            let bits = Generator(*element).base_value() | Microchip(*element).base_value();
+            (0..4).all(|floor| (used >> (floor * 16)) & bits == 0)
+        });
+        let (first, second) = (unused.next()?, unused.next()?);
+        let mut building = self.clone();
+        for element in [first, second] {
+            building.floor0.push(Microchip(element));
+            building.floor0.push(Generator(element));
         }
-    }
-
-    fn create_input() -> Self {
-        Self {
-            elevator: 0,
-            floor0: vec![
-                Generator(Polonium),
-                Generator(Thulium),
-                Generator(Promethium),
-                Generator(Ruthenium),
-                Generator(Cobalt),
-                Microchip(Thulium),
-                Microchip(Ruthenium),
-                Microchip(Cobalt),
-            ],
-            floor1: vec![Microchip(Polonium), Microchip(Promethium)],
-            floor2: Vec::new(),
-            floor3: Vec::new(),
-        }
-    }
-
-    fn _create_full_input() -> Self {
-        let mut input = Self::create_input();
-        // The problem for part 2 states that there are 2 new elements, elerium and dilithium. However, since
-        // hydrogen and lithium are not used in the real input, I can sub those instead. It won't change
-        // the output what the elements are called.
-        input.floor0.push(Microchip(Hydrogen));
-        input.floor0.push(Microchip(Lithium));
-        input.floor0.push(Generator(Hydrogen));
-        input.floor0.push(Generator(Lithium));
-        input
+        Some(building)
     }
 
     // (has_elevator, floor machines)
@@ -269,7 +266,6 @@ impl Building {
                 .floor0
                 .iter()
                 .fold(0, |acc, machine| acc | machine.base_value())
-                << 0
             | self
                 .floor1
                 .iter()
@@ -311,7 +307,7 @@ impl Building {
         elevator_powerset.next(); // Get rid of the initial empty set since the elevator must always have at least 1 item
         let mut single_item_move = Vec::new();
         let mut double_item_move = Vec::new();
-        while let Some(in_elevator) = elevator_powerset.next() {
+        for in_elevator in elevator_powerset {
             if in_elevator.len() == 1 {
                 single_item_move.push(in_elevator);
             } else if in_elevator.len() == 2 {
@@ -366,37 +362,155 @@ impl Building {
     }
 }
 
-fn find_shortest_arrangement(start: Building) -> u64 {
-    let mut known_states: IntSet<u64> = IntSet::default();
-    let mut next_states: VecDeque<(u64, u64)> = VecDeque::new();
-
-    for b in start.next_possible_states() {
-        let building_value = b.building_hash();
-        known_states.insert(building_value);
-        next_states.push_back((1, building_value));
+const FLOOR_STARTS: [&str; 4] = [
+    "The first floor contains ",
+    "The second floor contains ",
+    "The third floor contains ",
+    "The fourth floor contains ",
+];
+
+// `item` is something like `a thulium generator` or `a thulium-compatible microchip`
+fn parse_machine(input: &str, item: &str) -> Result<Machine, ParseError> {
+    let Some(item) = item.strip_prefix("a ") else {
+        return Err(ParseError::at(input, item, "an item starting with `a `"));
+    };
+    let (name, machine): (&str, fn(Element) -> Machine) =
+        if let Some(name) = item.strip_suffix(" generator") {
+            (name, Generator)
+        } else if let Some(name) = item.strip_suffix("-compatible microchip") {
+            (name, Microchip)
+        } else {
+            return Err(ParseError::at(input, item, "a generator or microchip"));
+        };
+    match Element::from_name(name) {
+        Some(element) => Ok(machine(element)),
+        None => Err(ParseError::at(input, name, "a known element")),
     }
+}
 
-    while let Some((iter, cur_hash)) = next_states.pop_front() {
-        let cur_building = Building::from_hash(cur_hash);
-        for next_building in cur_building.next_possible_states() {
-            if next_building.is_done() {
-                return iter + 1;
-            }
-            let next_hash = next_building.building_hash();
-            if !known_states.contains(&next_hash) {
-                known_states.insert(next_hash);
-                next_states.push_back((iter + 1, next_hash));
+fn parse_building(input: &str) -> Result<Building, ParseError> {
+    let mut floors: [Vec<Machine>; 4] = Default::default();
+    let mut lines = input.trim().lines();
+    // Each machine can only be in the building once, or the hashes stop being unique
+    let mut seen = 0;
+    for (floor, start) in floors.iter_mut().zip(FLOOR_STARTS) {
+        let Some(line) = lines.next().filter(|line| !line.is_empty()) else {
+            return Err(ParseError::end_of_input(input, "a line for every floor"));
+        };
+        let Some(contents) = line.strip_prefix(start) else {
+            return Err(ParseError::at(input, line, "the next floor's description"));
+        };
+        let Some(contents) = contents.strip_suffix('.') else {
+            return Err(ParseError::at(input, &line[line.len()..], "`.`"));
+        };
+        if contents == "nothing relevant" {
+            continue;
+        }
+        for item in contents.split(", ").flat_map(|part| part.split(" and ")) {
+            let item = item.strip_prefix("and ").unwrap_or(item);
+            let machine = parse_machine(input, item)?;
+            if seen & machine.base_value() != 0 {
+                return Err(ParseError::at(
+                    input,
+                    item,
+                    "a machine that isn't already listed",
+                ));
             }
+            seen |= machine.base_value();
+            floor.push(machine);
         }
     }
+    if let Some(line) = lines.next() {
+        return Err(ParseError::at(input, line, "the end of the input"));
+    }
+
+    let [floor0, floor1, floor2, floor3] = floors;
+    Ok(Building {
+        elevator: 0,
+        floor0,
+        floor1,
+        floor2,
+        floor3,
+    })
+}
+
+// Buildings are searched by their hashes, so the states are cheap to store and compare
+fn find_shortest_arrangement(start: Building) -> usize {
+    let (steps, _) = bfs(
+        start.building_hash(),
+        |&hash| {
+            Building::from_hash(hash)
+                .next_possible_states()
+                .into_iter()
+                .map(|building| building.building_hash())
+        },
+        |&hash| Building::from_hash(hash).is_done(),
+    )
+    .expect("Could not find solution");
+    steps
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Building;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+    const SLOW_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_building(input)
+    }
+
+    fn part_1(building: &Self::Input) -> impl Display {
+        find_shortest_arrangement(building.clone())
+    }
 
-    panic!("Could not find solution");
+    fn part_2(building: &Self::Input) -> impl Display {
+        let building = building
+            .with_extra_pairs()
+            .expect("there are no unused elements left to stand in for elerium and dilithium");
+        find_shortest_arrangement(building)
+    }
 }
 
 #[test]
 fn part_1() {
-    assert_eq!(11, find_shortest_arrangement(Building::create_sample()));
-    assert_eq!(47, find_shortest_arrangement(Building::create_input()));
-    // Takes >1 minute
-    // assert_eq!(71, find_shortest_arrangement(Building::_create_full_input()));
+    assert_eq!(
+        11,
+        find_shortest_arrangement(parse_building(SAMPLE).unwrap())
+    );
+    assert_eq!(
+        47,
+        find_shortest_arrangement(parse_building(INPUT).unwrap())
+    );
+}
+
+// Takes >1 minute
+#[test]
+#[cfg_attr(not(feature = "slow"), ignore = "slow, run with `--features slow`")]
+fn part_2() {
+    let building = parse_building(INPUT).unwrap().with_extra_pairs().unwrap();
+    assert_eq!(71, find_shortest_arrangement(building));
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_building(&SAMPLE.replace("lithium generator", "lithium reactor"))
+        .err()
+        .unwrap();
+    assert_eq!((4, 28), (err.line, err.column));
+    let err = parse_building(&SAMPLE.replace("a lithium-", "an iron-"))
+        .err()
+        .unwrap();
+    assert_eq!("an item starting with `a `", err.expected);
+    let err = parse_building(&SAMPLE.replace("lithium generator", "hydrogen generator"))
+        .err()
+        .unwrap();
+    assert_eq!("a machine that isn't already listed", err.expected);
+    let err = parse_building(SAMPLE.trim().rsplit_once('\n').unwrap().0)
+        .err()
+        .unwrap();
+    assert_eq!("a line for every floor", err.expected);
+    assert!(parse_building("").is_err());
 }
@@ -1,5 +1,14 @@
+use std::fmt::Display;
+
 use logos::{Lexer, Logos};
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, next_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[\n ]")]
 pub enum Token {
@@ -21,10 +30,10 @@ pub enum Token {
     #[token("out")]
     Out,
 
-    #[regex("[a-z]", |lex| lex.slice().chars().nth(0))]
+    #[regex("[a-z]", |lex| lex.slice().chars().next())]
     Register(char),
 
-    #[regex("-?[0-9]+", |lex| lex.slice().parse::<isize>().unwrap())]
+    #[regex("-?[0-9]+", |lex| lex.slice().parse::<isize>().ok())]
     Value(isize),
 }
 
@@ -42,18 +51,12 @@ impl Operand {
     }
 }
 
-impl From<Token> for Operand {
-    fn from(value: Token) -> Self {
-        match value {
-            Token::Value(val) => Operand::Value(val),
-            Token::Register(reg) => Operand::Register(match reg {
-                'a' => 0,
-                'b' => 1,
-                'c' => 2,
-                'd' => 3,
-                _ => unreachable!(),
-            }),
-            _ => panic!("Can't convert Token to Datum"),
+impl Token {
+    fn take_operand(self) -> Option<Operand> {
+        match self {
+            Self::Value(val) => Some(Operand::Value(val)),
+            Self::Register(reg @ 'a'..='d') => Some(Operand::Register(reg as usize - 'a' as usize)),
+            _ => None,
         }
     }
 }
@@ -67,52 +70,62 @@ pub enum Instruction {
     Out(Operand),
 }
 
-fn get_operand(tokens: &mut Lexer<Token>) -> Operand {
-    Operand::from(tokens.next().unwrap().unwrap())
+fn get_operand(tokens: &mut Lexer<Token>) -> Result<Operand, ParseError> {
+    expect_token(
+        tokens,
+        "a register `a` to `d` or a number",
+        Token::take_operand,
+    )
 }
 
-pub fn parse_instructions(input: &str) -> Vec<Instruction> {
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
     let mut instructions: Vec<Instruction> = Vec::new();
     let mut tokens = Token::lexer(input);
 
-    while let Some(Ok(token)) = tokens.next() {
+    while let Some(token) = next_token_or_end(&mut tokens, "an instruction")? {
         match token {
             Token::Copy => {
-                let x = get_operand(&mut tokens);
-                let y = get_operand(&mut tokens);
+                let x = get_operand(&mut tokens)?;
+                let y = get_operand(&mut tokens)?;
                 instructions.push(Instruction::Cpy(x, y));
             }
             Token::Increment => {
-                let x = get_operand(&mut tokens);
+                let x = get_operand(&mut tokens)?;
                 instructions.push(Instruction::Inc(x));
             }
             Token::Decrement => {
-                let x = get_operand(&mut tokens);
+                let x = get_operand(&mut tokens)?;
                 instructions.push(Instruction::Dec(x));
             }
             Token::JumpNotZero => {
-                let x = get_operand(&mut tokens);
-                let y = get_operand(&mut tokens);
+                let x = get_operand(&mut tokens)?;
+                let y = get_operand(&mut tokens)?;
                 instructions.push(Instruction::Jnz(x, y));
             }
             Token::Toggle => {
-                let x = get_operand(&mut tokens);
+                let x = get_operand(&mut tokens)?;
                 instructions.push(Instruction::Tgl(x));
             }
             Token::Out => {
-                let x = get_operand(&mut tokens);
+                let x = get_operand(&mut tokens)?;
                 instructions.push(Instruction::Out(x));
             }
-            _ => panic!("Unexpected token"),
+            Token::Register(_) | Token::Value(_) => {
+                return Err(ParseError::new(
+                    tokens.source(),
+                    tokens.span(),
+                    "an instruction",
+                ));
+            }
         }
     }
 
-    instructions
+    non_empty(input, instructions, "an instruction")
 }
 
 pub type Registers = [isize; 4];
 
-pub fn execute(ins: &Vec<Instruction>, a: isize, b: isize, c: isize, d: isize) -> isize {
+pub fn execute(ins: &[Instruction], a: isize, b: isize, c: isize, d: isize) -> isize {
     let mut ptr = 0;
     let mut state: Registers = [a, b, c, d];
     let len = ins.len();
@@ -149,7 +162,7 @@ pub fn execute(ins: &Vec<Instruction>, a: isize, b: isize, c: isize, d: isize) -
                 if x.to_value(&state) != 0 {
                     let delta = y.to_value(&state);
                     if delta.is_negative() {
-                        let delta_pos = delta.abs() as usize;
+                        let delta_pos = delta.unsigned_abs();
                         if delta_pos > ptr {
                             panic!("Can't make ptr negative");
                         }
@@ -177,6 +190,25 @@ pub fn execute(ins: &Vec<Instruction>, a: isize, b: isize, c: isize, d: isize) -
     state[0]
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Self::Input) -> impl Display {
+        execute(instructions, 0, 0, 0, 0)
+    }
+
+    fn part_2(instructions: &Self::Input) -> impl Display {
+        execute(instructions, 0, 0, 1, 0)
+    }
+}
+
+#[cfg(test)]
 const SAMPLE: &str = "
 cpy 41 a
 inc a
@@ -185,14 +217,22 @@ dec a
 jnz a 2
 dec a";
 
-const INPUT: &str = include_str!("./inputs/day12.txt");
-
 #[test]
 fn both_parts() {
-    let sample_ins = parse_instructions(SAMPLE);
-    let input_ins = parse_instructions(INPUT);
-
+    let sample_ins = parse_instructions(SAMPLE).unwrap();
     assert_eq!(42, execute(&sample_ins, 0, 0, 0, 0));
+
+    let Some(input) = test_input(2016, 12) else {
+        return;
+    };
+    let input_ins = parse_instructions(&input).unwrap();
     assert_eq!(317993, execute(&input_ins, 0, 0, 0, 0));
     assert_eq!(9227647, execute(&input_ins, 0, 0, 1, 0));
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_instructions("cpy 41 a\ninc e\n").err().unwrap();
+    assert_eq!((2, 5), (err.line, err.column));
+    assert_eq!(Some(String::from("e")), err.found);
+}
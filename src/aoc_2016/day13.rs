@@ -1,141 +1,79 @@
-use std::collections::{VecDeque, binary_heap::BinaryHeap};
-
-use fxhash::FxHashSet;
-
-#[derive(PartialEq, Eq, Hash)]
-struct Point(usize, usize);
-
-impl Point {
-    fn is_space(&self, input: usize) -> bool {
-        let x = self.0;
-        let y = self.1;
-        let total = (x * x) + (3 * x) + (2 * x * y) + y + (y * y) + input;
-        total.count_ones() % 2 == 0
-    }
-
-    fn man_dist(&self, other: &Self) -> usize {
-        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
-    }
+use std::fmt::Display;
+
+use crate::util::{
+    Pair,
+    heap::RadixHeap,
+    parse::{ParseError, parse_at},
+    search::{astar_with, bfs_within},
+    solution::Solution,
+};
+
+fn is_space(Pair(x, y): Pair, input: i64) -> bool {
+    let total = (x * x) + (3 * x) + (2 * x * y) + y + (y * y) + input;
+    total.count_ones().is_multiple_of(2)
 }
 
-#[derive(PartialEq, Eq)]
-struct Space {
-    cost: usize,
-    heuristic: usize,
-    position: Point,
+// The building only extends right and down from the origin
+fn open_neighbors(pos: Pair, input: i64) -> impl Iterator<Item = Pair> {
+    pos.neighbors4()
+        .into_iter()
+        .filter(move |&next| next.0 >= 0 && next.1 >= 0 && is_space(next, input))
 }
 
-impl Ord for Space {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // f(n) = g(n) + h(n), so compare cost plus heuristic against other points
-        // Reversed so that the BinaryHeap becomes a min-heap instead of a max-heap
-        (other.cost + other.heuristic).cmp(&(self.cost + self.heuristic))
-    }
+const START: Pair = Pair(1, 1);
+const END: Pair = Pair(31, 39);
+
+pub const INPUT: &str = "1352";
+
+// The map goes on forever, so this would never return if `end` were walled off. It can only give
+// up if `START` is boxed in. Every step is 1 and changes the distance to `end` by 1, so the
+// estimates never go down and a `RadixHeap` can queue them
+fn find_shortest_path(input: i64, end: Pair) -> u32 {
+    let (steps, _) = astar_with::<RadixHeap<_, _>, _, _, _>(
+        START,
+        |&pos| open_neighbors(pos, input).map(|next| (next, 1)),
+        |&pos| pos.manhattan(end) as u32,
+        |&pos| pos == end,
+    )
+    .expect("START should not be boxed in");
+    steps
 }
 
-impl PartialOrd for Space {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+fn count_all_spaces_within(input: i64, max_cost: usize) -> usize {
+    bfs_within(START, max_cost, |&pos| open_neighbors(pos, input)).len()
 }
 
-fn get_neighbors(point: &Point) -> Vec<Point> {
-    let x = point.0;
-    let y = point.1;
-    let mut ns = vec![Point(x + 1, y), Point(x, y + 1)];
-    // ,
-    // Point (x, y-1),
-    if x > 0 {
-        ns.push(Point(x - 1, y));
-    }
-    if y > 0 {
-        ns.push(Point(x, y - 1));
-    }
-
-    ns
+fn parse_favorite_number(input: &str) -> Result<i64, ParseError> {
+    parse_at(input, input.trim(), "the designer's favorite number")
 }
 
-const START: Point = Point(1, 1);
-// const END: Point = Point (31, 39);
-
-fn find_shortest_path(input: usize, end: Point) -> usize {
-    let mut visited: FxHashSet<Point> = FxHashSet::default();
-    let mut queue: BinaryHeap<Space> = BinaryHeap::new();
-    queue.push(Space {
-        cost: 0,
-        position: START,
-        heuristic: START.man_dist(&end),
-    });
-
-    // The map can go on forever, so queue will never be empty. This should at least
-    // keep us from hitting any infinite loops
-    while visited.len() < 1_000_000 || queue.is_empty() {
-        let next = queue.pop().unwrap();
-        if next.position == end {
-            return next.cost;
-        }
-
-        let cost = next.cost + 1;
-        for neighbor in get_neighbors(&next.position) {
-            if neighbor.is_space(input) && !visited.contains(&neighbor) {
-                queue.push(Space {
-                    cost,
-                    heuristic: neighbor.man_dist(&end),
-                    position: neighbor,
-                });
-            }
-        }
-
-        visited.insert(next.position);
-    }
+pub struct Day13;
 
-    panic!(
-        "Could not find path to END, visited {} spaces",
-        visited.len()
-    );
-}
+impl Solution for Day13 {
+    type Input = i64;
 
-struct FillSpace {
-    cost: usize,
-    position: Point,
-}
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_favorite_number(input)
+    }
 
-fn count_all_spaces_within(input: usize, max_cost: usize) -> usize {
-    let mut visited: FxHashSet<Point> = FxHashSet::default();
-    let mut stack: VecDeque<FillSpace> = VecDeque::new();
-
-    stack.push_back(FillSpace {
-        cost: 0,
-        position: START,
-    });
-    while !stack.is_empty() {
-        let next = stack.pop_front().unwrap();
-
-        if next.cost < max_cost {
-            let new_cost = next.cost + 1;
-            for neighbor in get_neighbors(&next.position) {
-                if neighbor.is_space(input) && !visited.contains(&neighbor) {
-                    stack.push_back(FillSpace {
-                        cost: new_cost,
-                        position: neighbor,
-                    });
-                }
-            }
-        }
-
-        visited.insert(next.position);
+    fn part_1(number: &Self::Input) -> impl Display {
+        find_shortest_path(*number, END)
     }
 
-    visited.len()
+    fn part_2(number: &Self::Input) -> impl Display {
+        count_all_spaces_within(*number, 50)
+    }
 }
 
 #[test]
 fn part_1() {
     // Sample
-    assert_eq!(11, find_shortest_path(10, Point(7, 4)));
+    assert_eq!(11, find_shortest_path(10, Pair(7, 4)));
 
     // Actual input
-    assert_eq!(90, find_shortest_path(1352, Point(31, 39)));
+    assert_eq!(90, find_shortest_path(1352, END));
 }
 
 #[test]
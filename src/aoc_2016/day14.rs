@@ -1,190 +1,160 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
-use fxhash::FxHashMap;
+use crate::util::{
+    hash::{batch_size, generate_stretched_hash, parallel_map},
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
 
-use crate::util::{generate_hash, generate_padded_hash};
+// How far past a triplet to look for a matching quint
+const LOOKAHEAD: usize = 1000;
 
-type HashArr = [u8; 32];
+// The extra rounds of hashing part 2 adds to every hash
+const STRETCH_ROUNDS: usize = 2016;
 
-fn hash_form(hash: [u8; 16]) -> String {
-    let mut output = String::new();
-    for byte in hash {
-        output.push_str(&format!("{:0>2x}", byte));
-    }
-    output
-}
-
-fn generate_expensive_hash(base: &[u8], padding: usize) -> [u8; 16] {
-    let mut hash: [u8; 16] = generate_padded_hash(base, padding);
-    for _ in 0..2016 {
-        hash = generate_hash(&hash_form(hash).as_bytes());
-    }
-    hash
+// The first hex digit repeated three times in a row, and a bit for every digit repeated five times
+#[derive(Clone, Copy)]
+struct HashDetails {
+    triplet: Option<u8>,
+    quints: u16,
 }
 
-fn easier_hash(hash: [u8; 16]) -> HashArr {
-    let mut output = [0; 32];
-
-    for (i, byte) in hash.into_iter().enumerate() {
-        let first = byte >> 4;
-        let second = byte & 0x0F;
-        output[i * 2] = first;
-        output[i * 2 + 1] = second;
-    }
-
-    output
-}
-
-fn find_relevant_details(hash: &HashArr) -> (Option<u8>, Vec<u8>) {
-    let mut count = 1;
-    let mut cur_match: u8 = hash[0];
-    let mut triplet: Option<u8> = None;
-    let mut quints = Vec::new();
-
-    for half_byte in &hash[1..] {
-        if *half_byte == cur_match {
-            count += 1;
-
-            if count == 3 && triplet.is_none() {
-                triplet = Some(*half_byte);
-            }
-
-            if count == 5 {
-                quints.push(*half_byte);
-            }
+fn find_relevant_details(hash: [u8; 16]) -> HashDetails {
+    let nibbles = hash.into_iter().flat_map(|byte| [byte >> 4, byte & 0x0F]);
+    let mut details = HashDetails {
+        triplet: None,
+        quints: 0,
+    };
+    let mut run = (16, 0);
+    for nibble in nibbles {
+        run = if nibble == run.0 {
+            (nibble, run.1 + 1)
         } else {
-            cur_match = *half_byte;
-            count = 1;
+            (nibble, 1)
+        };
+        if run.1 == 3 && details.triplet.is_none() {
+            details.triplet = Some(nibble);
+        }
+        if run.1 == 5 {
+            details.quints |= 1 << nibble;
         }
     }
-
-    (triplet, quints)
+    details
 }
 
-struct FutureSearchState {
+// Walks the hashes in order while keeping the next `LOOKAHEAD` of them worked out in a ring
+// buffer, topped up a batch at a time across every core. A count of the quints of each digit in
+// that window is kept up to date as it slides, so checking a triplet is a single lookup
+struct HashStream<'a> {
+    input: &'a [u8],
+    rounds: usize,
+    // The details of every hash from `index` onwards that's been worked out so far
+    window: VecDeque<HashDetails>,
     index: usize,
-    quints: FxHashMap<usize, Vec<u8>>,
+    // How many hashes in `index + 1..=index + LOOKAHEAD` have a quint of each digit
+    quint_counts: [u32; 16],
 }
 
-struct TripletKey {
-    index: usize,
-    character: u8,
-}
-
-fn confirm_key(
-    input: &[u8],
-    current_key: &TripletKey,
-    future_search_state: &mut FutureSearchState,
-    key_queue: &mut VecDeque<TripletKey>,
-    expensive: bool,
-) -> bool {
-    // Search existing keys first
-    for index in (current_key.index + 1)..future_search_state.index {
-        if let Some(quints) = future_search_state.quints.get(&index) {
-            if quints.contains(&current_key.character) {
-                return true;
-            }
+impl<'a> HashStream<'a> {
+    fn new(input: &'a [u8], rounds: usize) -> Self {
+        let mut stream = Self {
+            input,
+            rounds,
+            window: VecDeque::new(),
+            index: 0,
+            quint_counts: [0; 16],
+        };
+        stream.fill(LOOKAHEAD + 1);
+        for offset in 1..=LOOKAHEAD {
+            stream.count_quints(offset, 1);
         }
+        stream
     }
 
-    // None found, generate new keys up to the limit
-    let max_search_index = current_key.index + 1001; // One more than a thousand since Rust ranges are [x..y)
+    // Makes sure the window holds at least `len` hashes
+    fn fill(&mut self, len: usize) {
+        while self.window.len() < len {
+            let (input, rounds) = (self.input, self.rounds);
+            let start = self.index + self.window.len();
+            self.window
+                .extend(parallel_map(start..start + batch_size(), |index| {
+                    find_relevant_details(generate_stretched_hash(input, index, rounds))
+                }));
+        }
+    }
 
-    for index in future_search_state.index..max_search_index {
-        future_search_state.index += 1;
-        let hash = if expensive {
-            generate_expensive_hash(input, index)
-        } else {
-            generate_padded_hash(input, index)
-        };
-        let hash = easier_hash(hash);
-        let (triplet_hex, quints) = find_relevant_details(&hash);
-
-        triplet_hex.map(|hex| {
-            key_queue.push_back(TripletKey {
-                index,
-                character: hex,
-            });
-        });
-
-        if !quints.is_empty() {
-            future_search_state.quints.insert(index, quints.clone());
-            if quints.contains(&current_key.character) {
-                return true;
+    fn count_quints(&mut self, offset: usize, change: i32) {
+        let quints = self.window[offset].quints;
+        for (digit, count) in self.quint_counts.iter_mut().enumerate() {
+            if quints & 1 << digit != 0 {
+                *count = count.wrapping_add_signed(change);
             }
         }
     }
 
-    false
-}
+    // Whether the current hash is a key, then moves on to the next one
+    fn advance(&mut self) -> bool {
+        let is_key = self.window[0]
+            .triplet
+            .is_some_and(|digit| self.quint_counts[digit as usize] > 0);
+
+        self.window.pop_front();
+        self.index += 1;
+        // The new current hash leaves the lookahead and the one after the old end joins it
+        self.count_quints(0, -1);
+        self.fill(LOOKAHEAD + 1);
+        self.count_quints(LOOKAHEAD, 1);
+        is_key
+    }
 
-// Not even sure if this is necessary
-fn find_next_potential_key(input: &[u8], index: &mut usize, expensive: bool) -> TripletKey {
-    loop {
-        let hash = if expensive {
-            generate_expensive_hash(input, *index)
-        } else {
-            generate_padded_hash(input, *index)
-        };
-        let hash = easier_hash(hash);
-        // Any quints found here are not needed since this function is only called
-        // when the triplet is needed, and thus a quint wouldn't confirm anything
-        let (triplet_hex, _) = find_relevant_details(&hash);
-
-        *index += 1;
-        if let Some(hex) = triplet_hex {
-            return TripletKey {
-                index: *index,
-                character: hex,
-            };
+    fn next_key(&mut self) -> usize {
+        loop {
+            let index = self.index;
+            if self.advance() {
+                return index;
+            }
         }
     }
 }
 
-fn find_keys(input: &[u8], expensive: bool) -> usize {
-    let mut key_queue: VecDeque<TripletKey> = VecDeque::new();
+fn find_keys(input: &[u8], rounds: usize) -> usize {
+    let mut stream = HashStream::new(input, rounds);
+    (0..64).map(|_| stream.next_key()).last().unwrap()
+}
 
-    // Can't start until we have the first potential key
-    let mut index = 0;
-    key_queue.push_back(find_next_potential_key(input, &mut index, expensive));
+pub const INPUT: &str = "ihaygndm";
 
-    let mut found_keys: Vec<usize> = Vec::new();
-    let mut future_keys = FutureSearchState {
-        index: index + 1,
-        quints: FxHashMap::default(),
-    };
+pub struct Day14;
 
-    while found_keys.len() < 64 {
-        let current_key = match key_queue.pop_front() {
-            Some(key) => {
-                index = key.index + 1;
-                key
-            }
-            None => find_next_potential_key(input, &mut index, expensive),
-        };
-        if confirm_key(
-            input,
-            &current_key,
-            &mut future_keys,
-            &mut key_queue,
-            expensive,
-        ) {
-            found_keys.push(current_key.index);
-        }
+impl Solution for Day14 {
+    type Input = Vec<u8>;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let salt = parse_chars(input, "a salt", |ch| !ch.is_whitespace())?;
+        Ok(salt.as_bytes().to_vec())
     }
 
-    found_keys.pop().unwrap()
+    fn part_1(input: &Self::Input) -> impl Display {
+        find_keys(input, 0)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        find_keys(input, STRETCH_ROUNDS)
+    }
 }
 
 #[test]
 fn part_1() {
-    assert_eq!(22728, find_keys(b"abc", false));
-    assert_eq!(15035, find_keys(b"ihaygndm", false));
+    assert_eq!(22728, find_keys(b"abc", 0));
+    assert_eq!(15035, find_keys(INPUT.as_bytes(), 0));
 }
 
+// A few seconds in release mode, but several minutes of unoptimized MD5 in debug mode
 #[test]
+#[cfg_attr(not(feature = "slow"), ignore = "slow, run with `--features slow`")]
 fn part_2() {
-    // Takes over a minute to evalute in release mode, and over 3 minutes in debug mode
-    // assert_eq!(22551, find_keys(b"abc", true));
-    // assert_eq!(19968, find_keys(b"ihaygndm", true));
+    assert_eq!(22551, find_keys(b"abc", STRETCH_ROUNDS));
+    assert_eq!(19968, find_keys(INPUT.as_bytes(), STRETCH_ROUNDS));
 }
@@ -1,50 +1,56 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
+use crate::util::{
+    math::crt,
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[\n .]", skip r"Disc #\d+ has", skip r"positions; at time=0, it is at position")]
 enum Token {
-    #[regex(r"\d+", |lex| lex.slice().parse::<usize>().unwrap())]
+    #[regex(r"\d+", |lex| lex.slice().parse::<usize>().ok())]
     Number(usize),
 }
 
 impl Token {
-    fn value(self) -> usize {
+    fn take_number(self) -> Option<usize> {
         match self {
-            Self::Number(val) => val,
+            Self::Number(val) => Some(val),
         }
     }
 }
 
-struct Disc {
+#[derive(Clone)]
+pub struct Disc {
     number: usize,
     num_positions: usize,
     cur_position: usize,
 }
 
-impl Disc {
-    fn rotate(&mut self, time: usize) {
-        self.cur_position += time;
-        self.cur_position %= self.num_positions;
-    }
-}
-
-fn parse_discs(input: &str) -> Vec<Disc> {
+fn parse_discs(input: &str) -> Result<Vec<Disc>, ParseError> {
     let mut discs = Vec::new();
     let mut tokens = Token::lexer(input);
-    while let Some(Ok(token)) = tokens.next() {
-        let num_positions = token.value();
-        let cur_position = tokens.next().unwrap().unwrap().value();
+    let positive = |token: Token| token.take_number().filter(|&val| val > 0);
+    while let Some(num_positions) =
+        expect_token_or_end(&mut tokens, "a positive number of positions", positive)?
+    {
+        let cur_position = expect_token(&mut tokens, "a starting position", |token| {
+            token.take_number().filter(|&val| val < num_positions)
+        })?;
         discs.push(Disc {
             number: discs.len() + 1,
             num_positions,
             cur_position,
         });
     }
-    discs
+    non_empty(input, discs, "a positive number of positions")
 }
 
-fn parse_with_extra_disc(input: &str) -> Vec<Disc> {
-    let mut discs = parse_discs(input);
+fn with_extra_disc(discs: &[Disc]) -> Vec<Disc> {
+    let mut discs = discs.to_vec();
     discs.push(Disc {
         number: discs.len() + 1,
         num_positions: 11,
@@ -53,73 +59,28 @@ fn parse_with_extra_disc(input: &str) -> Vec<Disc> {
     discs
 }
 
-fn rotate_discs(discs: &mut Vec<Disc>, time: usize) -> bool {
-    let mut finished = true;
-
-    for disc in discs {
-        disc.rotate(time);
-        if disc.cur_position != 0 {
-            finished = false;
-        }
-    }
-
-    finished
-}
-
-fn find_earliest_capsule_time(mut discs: Vec<Disc>) -> usize {
-    let mut total_time = 0;
-    let mut highest_num_positions = 0;
-    let mut cur_pos_of_highest = 0;
-
-    // First, pre-rotate based on the time that it takes the ball to fall to a disc.
-    // This way we simply search for the time where all discs are at position 0. The
-    // `number` attribute conveniently informs us how far to pre-rotate.
-    for disc in &mut discs {
-        disc.rotate(disc.number);
-        if disc.num_positions > highest_num_positions {
-            highest_num_positions = disc.num_positions;
-            cur_pos_of_highest = disc.cur_position;
-        }
-    }
-
-    // Now, we rotate all discs so that the disc with the highest number of positions
-    // is at 0. This time is tracked.
-    if cur_pos_of_highest > 0 {
-        let mut finished_from_start = true;
-        let added_time = highest_num_positions - cur_pos_of_highest;
-        total_time += added_time;
-        for disc in &mut discs {
-            disc.rotate(added_time);
-            if disc.cur_position != 0 {
-                finished_from_start = false;
-            }
-        }
-        // This is definitely not needed for input, but I'm a stickler for a solution
-        // for ANY well formed input.
-        if finished_from_start {
-            return total_time;
-        }
-    }
-
-    // Now the boring part
-    loop {
-        total_time += highest_num_positions;
-        if rotate_discs(&mut discs, highest_num_positions) {
-            break;
-        }
-    }
-
-    // println!("Total time is {total_time}, we're working with {:?}", discs);
-
-    total_time
+// The capsule reaches disc `n` at `time + n`, so it falls through when
+// `cur_position + time + n ≡ 0 (mod num_positions)` for every disc at once
+fn find_earliest_capsule_time(discs: &[Disc]) -> i64 {
+    let congruences: Vec<(i64, i64)> = discs
+        .iter()
+        .map(|disc| {
+            let modulus = disc.num_positions as i64;
+            let offset = (disc.cur_position + disc.number) as i64;
+            ((-offset).rem_euclid(modulus), modulus)
+        })
+        .collect();
+    let (time, _) = crt(&congruences).expect("the discs should line up eventually");
+    time
 }
 
+#[cfg(test)]
 const SAMPLE: &str = "
 Disc #1 has 5 positions; at time=0, it is at position 4.
 Disc #2 has 2 positions; at time=0, it is at position 1.
 ";
 
-const INPUT: &str = "
+pub const INPUT: &str = "
 Disc #1 has 7 positions; at time=0, it is at position 0.
 Disc #2 has 13 positions; at time=0, it is at position 0.
 Disc #3 has 3 positions; at time=0, it is at position 2.
@@ -128,21 +89,43 @@ Disc #5 has 17 positions; at time=0, it is at position 0.
 Disc #6 has 19 positions; at time=0, it is at position 7.
 ";
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Disc>;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_discs(input)
+    }
+
+    fn part_1(discs: &Self::Input) -> impl Display {
+        find_earliest_capsule_time(discs)
+    }
+
+    fn part_2(discs: &Self::Input) -> impl Display {
+        find_earliest_capsule_time(&with_extra_disc(discs))
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(5, find_earliest_capsule_time(parse_discs(SAMPLE)));
-    assert_eq!(121834, find_earliest_capsule_time(parse_discs(INPUT)));
+    assert_eq!(5, find_earliest_capsule_time(&parse_discs(SAMPLE).unwrap()));
+    assert_eq!(
+        121834,
+        find_earliest_capsule_time(&parse_discs(INPUT).unwrap())
+    );
 }
 
 #[test]
 fn part_2() {
-    // Amazingly, I did not need to use the Chinese remainder theorem here
     assert_eq!(
         85,
-        find_earliest_capsule_time(parse_with_extra_disc(SAMPLE))
+        find_earliest_capsule_time(&with_extra_disc(&parse_discs(SAMPLE).unwrap()))
     );
     assert_eq!(
         3208099,
-        find_earliest_capsule_time(parse_with_extra_disc(INPUT))
+        find_earliest_capsule_time(&with_extra_disc(&parse_discs(INPUT).unwrap()))
     );
 }
@@ -1,3 +1,10 @@
+use std::fmt::Display;
+
+use crate::util::{
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
+
 type Data = Vec<bool>;
 
 fn data_str(data: &Data) -> String {
@@ -19,7 +26,7 @@ fn dragon_curve_step(data: &mut Data) {
 }
 
 fn checksum(data: Data) -> Data {
-    if data.len() % 2 != 0 {
+    if !data.len().is_multiple_of(2) {
         panic!("Can't create checksum for data of len {}", data.len());
     }
 
@@ -30,7 +37,7 @@ fn checksum(data: Data) -> Data {
         next_data.push(*bit_1 == *bit_2);
     }
 
-    if next_data.len() % 2 == 0 {
+    if next_data.len().is_multiple_of(2) {
         return checksum(next_data);
     }
     next_data
@@ -45,7 +52,28 @@ fn generate_dummy_checksum(input: &str, len: usize) -> String {
     data_str(&checksum(data))
 }
 
-const INPUT: &str = "01110110101001000";
+pub const INPUT: &str = "01110110101001000";
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = String;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let data = parse_chars(input, "a `0` or `1`", |ch| ch == '0' || ch == '1')?;
+        Ok(data.to_owned())
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        generate_dummy_checksum(input, 272)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        generate_dummy_checksum(input, 35651584)
+    }
+}
 
 #[test]
 fn both_parts() {
@@ -1,6 +1,10 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
-use crate::util::generate_hash;
+use crate::util::{
+    hash::generate_hash,
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
 
 enum Dir {
     Left,
@@ -82,12 +86,16 @@ impl State {
     }
 }
 
+#[cfg(test)]
 const SAMPLE_1: &str = "ihgpwlah";
+#[cfg(test)]
 const SAMPLE_2: &str = "kglvqrro";
+#[cfg(test)]
 const SAMPLE_3: &str = "ulqzkmiv";
-const INPUT: &str = "yjjvjgan";
+pub const INPUT: &str = "yjjvjgan";
 
-fn paths_out(input: &str) -> (String, usize) {
+// The shortest path to the vault and the length of the longest one, if the vault can be reached
+fn paths_out(input: &str) -> Option<(String, usize)> {
     let mut paths = VecDeque::new();
     paths.push_back(State {
         col: 0,
@@ -110,21 +118,49 @@ fn paths_out(input: &str) -> (String, usize) {
         }
     }
 
-    (shortest.unwrap(), last_len)
+    shortest.map(|shortest| (shortest, last_len))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = String;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let passcode = parse_chars(input, "a passcode", |ch| !ch.is_whitespace())?;
+        Ok(passcode.to_owned())
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        paths_out(input)
+            .expect("every path to the vault is locked")
+            .0
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        paths_out(input)
+            .expect("every path to the vault is locked")
+            .1
+    }
 }
 
 #[test]
 fn test() {
-    let (shortest, longest_len) = paths_out(SAMPLE_1);
+    let (shortest, longest_len) = paths_out(SAMPLE_1).unwrap();
     assert_eq!("DDRRRD", &shortest);
     assert_eq!(370, longest_len);
-    let (shortest, longest_len) = paths_out(SAMPLE_2);
+    let (shortest, longest_len) = paths_out(SAMPLE_2).unwrap();
     assert_eq!("DDUDRLRRUDRD", &shortest);
     assert_eq!(492, longest_len);
-    let (shortest, longest_len) = paths_out(SAMPLE_3);
+    let (shortest, longest_len) = paths_out(SAMPLE_3).unwrap();
     assert_eq!("DRURDRUDDLLDLUURRDULRLDUUDDDRR", &shortest);
     assert_eq!(830, longest_len);
-    let (shortest, longest_len) = paths_out(INPUT);
+    let (shortest, longest_len) = paths_out(INPUT).unwrap();
     assert_eq!("RLDRUDRDDR", &shortest);
     assert_eq!(498, longest_len);
+
+    // The puzzle's first example gets stuck before reaching the vault
+    assert_eq!(None, paths_out("hijkl"));
 }
@@ -1,12 +1,23 @@
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
+
+#[cfg(test)]
 const SAMPLE_1: &str = "..^^.";
+#[cfg(test)]
 const SAMPLE_2: &str = ".^^.^.^^^^";
-const INPUT: &str = include_str!("./inputs/day18.txt");
 
-fn parse_input(input: &str) -> Vec<bool> {
-    input.chars().map(|ch| ch == '^').collect()
+fn parse_input(input: &str) -> Result<Vec<bool>, ParseError> {
+    let row = parse_chars(input, "`^` or `.`", |ch| ch == '^' || ch == '.')?;
+    Ok(row.chars().map(|ch| ch == '^').collect())
 }
 
-fn count_safe_spaces(row: &Vec<bool>) -> usize {
+fn count_safe_spaces(row: &[bool]) -> usize {
     row.iter().filter(|x| !**x).count()
 }
 
@@ -23,19 +34,16 @@ fn generate_row(row: Vec<bool>) -> Vec<bool> {
         } else {
             *row.get(idx + 1).unwrap()
         };
-        new_row.push(match (left, *middle, right) {
-            (true, true, false)
-            | (false, true, true)
-            | (true, false, false)
-            | (false, false, true) => true,
-            _ => false,
-        });
+        new_row.push(matches!(
+            (left, *middle, right),
+            (true, true, false) | (false, true, true) | (true, false, false) | (false, false, true)
+        ));
     }
     new_row
 }
 
-fn calculate_generations(input: &str, generations: usize) -> usize {
-    let mut row = parse_input(input);
+fn calculate_generations(first_row: &[bool], generations: usize) -> usize {
+    let mut row = first_row.to_vec();
     let mut total_traps = count_safe_spaces(&row);
     for _ in 1..generations {
         row = generate_row(row);
@@ -44,10 +52,41 @@ fn calculate_generations(input: &str, generations: usize) -> usize {
     total_traps
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<bool>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        calculate_generations(input, 40)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        calculate_generations(input, 400_000)
+    }
+}
+
 #[test]
 fn both_parts() {
-    assert_eq!(6, calculate_generations(SAMPLE_1, 3));
-    assert_eq!(38, calculate_generations(SAMPLE_2, 10));
-    assert_eq!(1951, calculate_generations(INPUT, 40));
-    assert_eq!(20_002_936, calculate_generations(INPUT, 400_000));
+    assert_eq!(6, calculate_generations(&parse_input(SAMPLE_1).unwrap(), 3));
+    assert_eq!(
+        38,
+        calculate_generations(&parse_input(SAMPLE_2).unwrap(), 10)
+    );
+
+    let Some(input) = test_input(2016, 18) else {
+        return;
+    };
+    assert_eq!(
+        1951,
+        calculate_generations(&parse_input(&input).unwrap(), 40)
+    );
+    assert_eq!(
+        20_002_936,
+        calculate_generations(&parse_input(&input).unwrap(), 400_000)
+    );
 }
@@ -1,5 +1,17 @@
+use std::fmt::Display;
+
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+#[cfg(test)]
 const SAMPLE: usize = 5;
-const INPUT: usize = 3004953;
+pub const INPUT: &str = "3004953";
+
+fn parse_elf_count(input: &str) -> Result<usize, ParseError> {
+    parse_at(input, input.trim(), "a number of elves")
+}
 
 fn last_elf_part_1(input: usize) -> usize {
     let mut largest_power_of_two = 1;
@@ -53,14 +65,34 @@ fn last_elf_part_2(input: usize) -> usize {
     }
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = usize;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_elf_count(input)
+    }
+
+    fn part_1(count: &Self::Input) -> impl Display {
+        last_elf_part_1(*count)
+    }
+
+    fn part_2(count: &Self::Input) -> impl Display {
+        last_elf_part_2(*count)
+    }
+}
+
 #[test]
 fn part_1() {
     assert_eq!(3, last_elf_part_1(SAMPLE));
-    assert_eq!(1815603, last_elf_part_1(INPUT));
+    assert_eq!(1815603, last_elf_part_1(parse_elf_count(INPUT).unwrap()));
 }
 
 #[test]
 fn part_2() {
     assert_eq!(2, last_elf_part_2(SAMPLE));
-    assert_eq!(1410630, last_elf_part_2(INPUT));
+    assert_eq!(1410630, last_elf_part_2(parse_elf_count(INPUT).unwrap()));
 }
@@ -1,8 +1,17 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
-#[derive(Logos)]
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, tokenize},
+    solution::Solution,
+};
+
+#[derive(Logos, Clone, Copy)]
 #[logos()]
-enum Dir {
+pub enum Dir {
     #[token("U")]
     Up,
     #[token("R")]
@@ -18,10 +27,7 @@ enum Dir {
 
 impl Dir {
     fn is_nl(&self) -> bool {
-        match self {
-            Self::Newline => true,
-            _ => false,
-        }
+        matches!(self, Self::Newline)
     }
 }
 
@@ -116,20 +122,40 @@ impl WorseKeypadPosition {
     }
 }
 
-const SAMPLE: &'static str = "ULL
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Dir>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_directions(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        eval_code(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        eval_hard_code(input)
+    }
+}
+
+#[cfg(test)]
+const SAMPLE: &str = "ULL
 RRDDD
 LURDL
 UUUUD";
 
-const INPUT: &'static str = include_str!("./inputs/day2.txt");
+// Only the end is trimmed, so error positions still line up with the input
+fn parse_directions(input: &str) -> Result<Vec<Dir>, ParseError> {
+    tokenize(input.trim_end(), "one of `U`, `R`, `D` or `L`")
+}
 
-fn eval_code(input: &str) -> String {
+fn eval_code(directions: &[Dir]) -> String {
     let mut code = vec![];
 
     let mut cur_key = KeypadPosition::new();
-    let tokens = Dir::lexer(input.trim());
-    for token in tokens {
-        let dir = token.unwrap();
+    for &dir in directions {
         if dir.is_nl() {
             code.push(cur_key.keypad_value());
         } else {
@@ -141,13 +167,11 @@ fn eval_code(input: &str) -> String {
     code.into_iter().map(|x| x.to_string()).collect()
 }
 
-fn eval_hard_code(input: &str) -> String {
+fn eval_hard_code(directions: &[Dir]) -> String {
     let mut code = vec![];
 
     let mut cur_key = WorseKeypadPosition::new();
-    let tokens = Dir::lexer(input.trim());
-    for token in tokens {
-        let dir = token.unwrap();
+    for &dir in directions {
         if dir.is_nl() {
             code.push(cur_key.keypad_value());
         } else {
@@ -161,12 +185,20 @@ fn eval_hard_code(input: &str) -> String {
 
 #[test]
 fn part_1() {
-    assert_eq!("1985", eval_code(SAMPLE));
-    assert_eq!("18843", eval_code(INPUT));
+    assert_eq!("1985", eval_code(&parse_directions(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2016, 2) else {
+        return;
+    };
+    assert_eq!("18843", eval_code(&parse_directions(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!("5DB3", eval_hard_code(SAMPLE));
-    assert_eq!("67BB9", eval_hard_code(INPUT));
+    assert_eq!("5DB3", eval_hard_code(&parse_directions(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2016, 2) else {
+        return;
+    };
+    assert_eq!("67BB9", eval_hard_code(&parse_directions(&input).unwrap()));
 }
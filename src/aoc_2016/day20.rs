@@ -1,89 +1,85 @@
-use std::u32;
+use std::fmt::Display;
 
 use logos::Logos;
 
-use crate::util::heap::MinHeap;
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    intervals::IntervalSet,
+    parse::{ParseError, next_token_or_end},
+    solution::Solution,
+};
 
+#[cfg(test)]
 const SAMPLE: &str = "
 5-8
 0-2
 4-7";
 
-const INPUT: &str = include_str!("./inputs/day20.txt");
-
 #[derive(Logos)]
 #[logos(skip r"\n")]
 enum Token {
     #[regex("[0-9]+-[0-9]+", |lex| {
-        let mut parts = lex.slice().split('-');
-        (
-            parts.next().unwrap().parse::<u32>().unwrap(),
-            parts.next().unwrap().parse::<u32>().unwrap(),
-        )
+        let (start, end) = lex.slice().split_once('-')?;
+        let range = (start.parse::<u32>().ok()?, end.parse::<u32>().ok()?);
+        (range.0 <= range.1).then_some(range)
     })]
     Range((u32, u32)),
 }
 
-fn parse_sorted_ranges(input: &str) -> MinHeap<u32, u32> {
-    let mut sorted_by_start = MinHeap::default();
+fn parse_blocklist(input: &str) -> Result<IntervalSet<u32>, ParseError> {
+    let mut blocked = IntervalSet::new();
     let mut tokens = Token::lexer(input);
 
-    while let Some(Ok(Token::Range((start, end)))) = tokens.next() {
-        sorted_by_start.push(start, end);
+    while let Some(Token::Range((start, end))) =
+        next_token_or_end(&mut tokens, "a range like `5-8`")?
+    {
+        blocked.insert(start, end);
     }
 
-    sorted_by_start
+    if blocked.is_empty() {
+        return Err(ParseError::end_of_input(input, "a range like `5-8`"));
+    }
+    Ok(blocked)
 }
 
-fn lowest_unblocked_ip(non_overlapping_ranges: &Vec<(u32, u32)>) -> u32 {
-    let mut lowest: u32 = 0;
-    for &(start, end) in non_overlapping_ranges {
-        if lowest < start {
-            break;
-        }
-        if lowest >= start || lowest <= end {
-            lowest = lowest.max(end + 1);
-        }
-    }
+fn lowest_unblocked_ip(blocked: &IntervalSet<u32>) -> Option<u32> {
+    let allowed = blocked.complement(0, u32::MAX);
+    allowed.ranges().first().map(|&(start, _)| start)
+}
 
-    lowest
+fn total_unblocked_ips(max: u32, blocked: &IntervalSet<u32>) -> u64 {
+    blocked.complement(0, max).len()
 }
 
-fn merge_ranges(mut sorted_ranges: MinHeap<u32, u32>) -> Vec<(u32, u32)> {
-    let mut ranges = Vec::new();
-    ranges.push(sorted_ranges.pop().unwrap());
-
-    while let Some((start, end)) = sorted_ranges.pop() {
-        let (_, p_end) = ranges.last_mut().unwrap();
-        if start <= *p_end {
-            if end > *p_end {
-                *p_end = end;
-            }
-        } else {
-            ranges.push((start, end));
-        }
-    }
+pub struct Day20;
 
-    ranges
-}
+impl Solution for Day20 {
+    type Input = IntervalSet<u32>;
 
-fn total_unblocked_ips(max: u32, non_overlapping_ranges: &Vec<(u32, u32)>) -> u32 {
-    let mut count = max;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_blocklist(input)
+    }
 
-    for &(start, end) in non_overlapping_ranges {
-        println!("{start} -> {end}");
-        count -= end - start + 1;
+    fn part_1(blocked: &Self::Input) -> impl Display {
+        lowest_unblocked_ip(blocked).expect("every IP is blocked")
     }
 
-    1 + count
+    fn part_2(blocked: &Self::Input) -> impl Display {
+        total_unblocked_ips(u32::MAX, blocked)
+    }
 }
 
 #[test]
 fn part_1() {
-    let merged_ranges = merge_ranges(parse_sorted_ranges(SAMPLE));
-    assert_eq!(3, lowest_unblocked_ip(&merged_ranges));
-    assert_eq!(2, total_unblocked_ips(9, &merged_ranges));
-    let merged_ranges = merge_ranges(parse_sorted_ranges(INPUT));
-    assert_eq!(31053880, lowest_unblocked_ip(&merged_ranges));
-    assert_eq!(117, total_unblocked_ips(u32::MAX, &merged_ranges));
+    let blocked = parse_blocklist(SAMPLE).unwrap();
+    assert_eq!(Some(3), lowest_unblocked_ip(&blocked));
+    assert_eq!(2, total_unblocked_ips(9, &blocked));
+
+    let Some(input) = test_input(2016, 20) else {
+        return;
+    };
+    let blocked = parse_blocklist(&input).unwrap();
+    assert_eq!(Some(31053880), lowest_unblocked_ip(&blocked));
+    assert_eq!(117, total_unblocked_ips(u32::MAX, &blocked));
 }
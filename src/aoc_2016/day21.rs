@@ -1,10 +1,16 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
 use itertools::Itertools;
 use logos::{Lexer, Logos};
 
-const INPUT: &str = include_str!("./inputs/day21.txt");
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, next_token_or_end, non_empty},
+    solution::Solution,
+};
 
+#[cfg(test)]
 const SAMPLE: &str = "
 swap position 4 with position 0
 swap letter d with letter b
@@ -39,32 +45,31 @@ enum Token {
     #[token("rotate right")]
     RotateRight,
 
-    #[regex("[a-z]", |lex| lex.slice().chars().next().unwrap())]
+    #[regex("[a-z]", |lex| lex.slice().chars().next())]
     Char(char),
 
-    #[regex("[0-9]", |lex| lex.slice().chars().next().unwrap().to_digit(10).unwrap())]
+    #[regex("[0-9]", |lex| lex.slice().chars().next()?.to_digit(10))]
     Number(u32),
 }
 
 impl Token {
-    fn get_num(self) -> usize {
-        if let Self::Number(x) = self {
-            x as usize
-        } else {
-            panic!("Was not a number")
+    fn take_num(self) -> Option<usize> {
+        match self {
+            Self::Number(x) => Some(x as usize),
+            _ => None,
         }
     }
 
-    fn get_char(self) -> char {
-        if let Self::Char(ch) = self {
-            ch
-        } else {
-            panic!("Was not a char")
+    fn take_char(self) -> Option<char> {
+        match self {
+            Self::Char(ch) => Some(ch),
+            _ => None,
         }
     }
 }
 
-enum Instruction {
+#[derive(Clone, Copy)]
+pub enum Instruction {
     SwapPositions(usize, usize),
     SwapLetters(char, char),
     RotateLeft(usize),
@@ -74,58 +79,69 @@ enum Instruction {
     Move(usize, usize),
 }
 
-fn next_num(tokens: &mut Lexer<Token>) -> usize {
-    tokens.next().unwrap().unwrap().get_num()
+// Both passwords are the eight letters `a` through `h`, so instructions can't reach past them
+const PASSWORD_LEN: usize = 8;
+
+fn next_num(tokens: &mut Lexer<Token>) -> Result<usize, ParseError> {
+    expect_token(tokens, "a position below 8", |token| {
+        token.take_num().filter(|&x| x < PASSWORD_LEN)
+    })
 }
 
-fn next_char(tokens: &mut Lexer<Token>) -> char {
-    tokens.next().unwrap().unwrap().get_char()
+fn next_char(tokens: &mut Lexer<Token>) -> Result<char, ParseError> {
+    expect_token(tokens, "a letter from `a` to `h`", |token| {
+        token.take_char().filter(|&ch| ch <= 'h')
+    })
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
     let mut ins = Vec::new();
     let mut tokens = Token::lexer(input);
-    while let Some(Ok(token)) = tokens.next() {
+    while let Some(token) = next_token_or_end(&mut tokens, "an instruction")? {
         match token {
             Token::SwapPositions => {
-                let x = next_num(&mut tokens);
-                let y = next_num(&mut tokens);
+                let x = next_num(&mut tokens)?;
+                let y = next_num(&mut tokens)?;
                 ins.push(Instruction::SwapPositions(x, y));
             }
             Token::SwapLetters => {
-                let x = next_char(&mut tokens);
-                let y = next_char(&mut tokens);
+                let x = next_char(&mut tokens)?;
+                let y = next_char(&mut tokens)?;
                 ins.push(Instruction::SwapLetters(x, y));
             }
             Token::RotateBasedOn => {
-                let x = next_char(&mut tokens);
+                let x = next_char(&mut tokens)?;
                 ins.push(Instruction::RotateBasedOn(x));
             }
             Token::RotateLeft => {
-                let x = next_num(&mut tokens);
+                let x = next_num(&mut tokens)?;
                 ins.push(Instruction::RotateLeft(x));
             }
             Token::RotateRight => {
-                let x = next_num(&mut tokens);
+                let x = next_num(&mut tokens)?;
                 ins.push(Instruction::RotateRight(x));
             }
             Token::Reverse => {
-                let x = next_num(&mut tokens);
-                let y = next_num(&mut tokens);
+                let x = next_num(&mut tokens)?;
+                let y = next_num(&mut tokens)?;
                 ins.push(Instruction::Reverse(x, y));
             }
             Token::Move => {
-                let x = next_num(&mut tokens);
-                let y = next_num(&mut tokens);
+                let x = next_num(&mut tokens)?;
+                let y = next_num(&mut tokens)?;
                 ins.push(Instruction::Move(x, y));
             }
             Token::Char(_) | Token::Number(_) => {
-                panic!("Should not parse char or number outside of a command")
+                return Err(ParseError::new(
+                    tokens.source(),
+                    tokens.span(),
+                    "an instruction",
+                ));
             }
         }
     }
 
-    ins
+    non_empty(input, ins, "an instruction")
 }
 
 /** Manual String */
@@ -281,15 +297,37 @@ fn unprocess_password(password: String, instructions: &Vec<Instruction>) -> Stri
     s.iter().join("")
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Self::Input) -> impl Display {
+        process_password(String::from("abcdefgh"), instructions)
+    }
+
+    fn part_2(instructions: &Self::Input) -> impl Display {
+        let reverse_instructions: Vec<Instruction> = instructions.iter().rev().copied().collect();
+        unprocess_password(String::from("fbgdceah"), &reverse_instructions)
+    }
+}
+
 #[test]
 fn both_parts() {
-    let instructions: Vec<Instruction> = parse_instructions(SAMPLE);
+    let instructions: Vec<Instruction> = parse_instructions(SAMPLE).unwrap();
     assert_eq!(
         "decab",
         &process_password(String::from("abcde"), &instructions)
     );
 
-    let instructions: Vec<Instruction> = parse_instructions(INPUT);
+    let Some(input) = test_input(2016, 21) else {
+        return;
+    };
+    let instructions: Vec<Instruction> = parse_instructions(&input).unwrap();
     assert_eq!(
         "cbeghdaf",
         &process_password(String::from("abcdefgh"), &instructions)
@@ -1,14 +1,21 @@
+use std::fmt::Display;
+
 use fxhash::FxHashMap;
 use logos::Logos;
 
-const INPUT: &str = include_str!("./inputs/day22.txt");
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end},
+    solution::Solution,
+};
 
-fn parse_coord(s: &str) -> u32 {
-    s[2..].parse::<u32>().unwrap()
+fn parse_coord(s: &str) -> Option<u32> {
+    s[2..].parse::<u32>().ok()
 }
 
-fn remove_t(s: &str) -> u32 {
-    s[0..s.len() - 1].parse::<u32>().unwrap()
+fn remove_t(s: &str) -> Option<u32> {
+    s[0..s.len() - 1].parse::<u32>().ok()
 }
 
 #[derive(Logos)]
@@ -37,38 +44,57 @@ enum Token {
 }
 
 impl Token {
-    fn value(self) -> u32 {
+    fn take_x(self) -> Option<u32> {
+        match self {
+            Token::X(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    fn take_y(self) -> Option<u32> {
         match self {
-            Token::X(x) => x,
-            Token::Y(x) => x,
-            Token::Num(x) => x,
+            Token::Y(y) => Some(y),
+            _ => None,
+        }
+    }
+
+    fn take_num(self) -> Option<u32> {
+        match self {
+            Token::Num(x) => Some(x),
+            _ => None,
         }
     }
 }
 
-type Key = (i32, i32);
+pub type Key = (i32, i32);
 
 #[derive(PartialEq, Eq)]
-struct Node {
+pub struct Node {
     used: u32,
     size: u32,
     avail: u32,
 }
 
-type Network = FxHashMap<Key, Node>;
+pub type Network = FxHashMap<Key, Node>;
 
-fn parse_network(input: &str) -> Network {
+fn parse_network(input: &str) -> Result<Network, ParseError> {
     let mut grid: Network = FxHashMap::default();
     let mut tokens = Token::lexer(input);
-    while let Some(Ok(token)) = tokens.next() {
-        let x = token.value();
-        let y = tokens.next().unwrap().unwrap().value();
-        let size = tokens.next().unwrap().unwrap().value();
-        let used = tokens.next().unwrap().unwrap().value();
-        let avail = tokens.next().unwrap().unwrap().value();
+    while let Some(x) = expect_token_or_end(&mut tokens, "a node like `-x0`", Token::take_x)? {
+        let y = expect_token(&mut tokens, "a node row like `-y0`", Token::take_y)?;
+        let size = expect_token(&mut tokens, "a size like `90T`", Token::take_num)?;
+        let used = expect_token(&mut tokens, "a used amount like `70T`", Token::take_num)?;
+        let avail = expect_token(
+            &mut tokens,
+            "an available amount like `20T`",
+            Token::take_num,
+        )?;
         grid.insert((x as i32, y as i32), Node { used, size, avail });
     }
-    grid
+    if grid.is_empty() {
+        return Err(ParseError::end_of_input(input, "a node like `-x0`"));
+    }
+    Ok(grid)
 }
 
 fn count_viable_pairs(grid: &Network) -> u32 {
@@ -104,37 +130,52 @@ fn count_viable_pairs(grid: &Network) -> u32 {
 // to move the data from x0-y(max) to x0-y0. That's just the manhattan distance between the hole and
 // x0-y(max-1), then the hold is cyclically moved to transfer the data upwards. Each data move takes 5
 // moves of the hole to bring the data to x0-y1 with the hold on x0-y0, then it's one last move to finish.
-fn shortest_number_of_moves(grid: &Network) -> i32 {
-    let (key, _) = grid.iter().find(|(_, v)| v.used == 0).unwrap();
+// Grids without the hole or the wall aren't shaped like that, so there's no answer for them
+fn shortest_number_of_moves(grid: &Network) -> Option<i32> {
+    let (key, _) = grid.iter().find(|(_, v)| v.used == 0)?;
     let max_x = grid.iter().fold(0, |max, ((x, _), _)| max.max(*x));
     let min_wall_x = grid
         .iter()
         .filter(|(_, v)| v.size > 200)
-        .fold(i32::MAX, |min, (k, _)| min.min(k.0));
+        .map(|(k, _)| k.0)
+        .min()?;
     let man_dist_around_wall = key.1 + (max_x - key.0).abs() + ((key.0 - min_wall_x + 1) * 2);
 
-    println!(
-        "{} + {} + {} = {}",
-        key.1,
-        (max_x - key.0).abs(),
-        ((key.0 - min_wall_x + 1) * 2),
-        key.1 + (max_x - key.0).abs() + ((key.0 - min_wall_x + 1) * 2)
-    );
-
     // subtract 1 from max since by moving it the `man_dist_around_wall` amound, we would have pushed the
     // desired data into the next space with the hold on the opposite end as the goal. That means we only
     // have (max_x - 1) rotation moves, which takes 5 moves to perform
-    man_dist_around_wall + ((max_x - 1) * 5)
+    Some(man_dist_around_wall + ((max_x - 1) * 5))
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_network(input)
+    }
+
+    fn part_1(grid: &Self::Input) -> impl Display {
+        count_viable_pairs(grid)
+    }
+
+    fn part_2(grid: &Self::Input) -> impl Display {
+        shortest_number_of_moves(grid).expect("the grid should have one empty node and a wall")
+    }
 }
 
 #[test]
 fn part_1() {
-    let grid = parse_network(INPUT);
+    let Some(input) = test_input(2016, 22) else {
+        return;
+    };
+    let grid = parse_network(&input).unwrap();
 
     // I've already guaranteed that all nodes are connected to a single network,
     // rather than there being multiple disconnected networks.
     assert_eq!(946, count_viable_pairs(&grid));
-    assert_eq!(195, shortest_number_of_moves(&grid));
+    assert_eq!(Some(195), shortest_number_of_moves(&grid));
 
     // print_grid(&grid);
 }
@@ -1,5 +1,31 @@
-use crate::aoc_2016::day12::{execute, parse_instructions};
+use std::fmt::Display;
 
+use crate::aoc_2016::day12::{Instruction, execute, parse_instructions};
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<Instruction>;
+
+    const SLOW_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Self::Input) -> impl Display {
+        execute(instructions, 7, 0, 0, 0)
+    }
+
+    fn part_2(instructions: &Self::Input) -> impl Display {
+        execute(instructions, 12, 0, 0, 0)
+    }
+}
+
+#[cfg(test)]
 const SAMPLE: &str = "
 cpy 2 a
 tgl a
@@ -9,16 +35,28 @@ cpy 1 a
 dec a
 dec a";
 
-const INPUT: &str = include_str!("./inputs/day23.txt");
-
 #[test]
 fn part_1() {
-    assert_eq!(3, execute(&parse_instructions(SAMPLE), 7, 0, 0, 0));
-    assert_eq!(13958, execute(&parse_instructions(INPUT), 7, 0, 0, 0));
+    assert_eq!(3, execute(&parse_instructions(SAMPLE).unwrap(), 7, 0, 0, 0));
+
+    let Some(input) = test_input(2016, 23) else {
+        return;
+    };
+    assert_eq!(
+        13958,
+        execute(&parse_instructions(&input).unwrap(), 7, 0, 0, 0)
+    );
 }
 
 // This test takes nearly 5 seconds in release mode
-// #[test]
-// fn part_2() {
-//     assert_eq!(479010518, execute(&parse_instructions(INPUT), 12, 0, 0, 0));
-// }
+#[test]
+#[cfg_attr(not(feature = "slow"), ignore = "slow, run with `--features slow`")]
+fn part_2() {
+    let Some(input) = test_input(2016, 23) else {
+        return;
+    };
+    assert_eq!(
+        479010518,
+        execute(&parse_instructions(&input).unwrap(), 12, 0, 0, 0)
+    );
+}
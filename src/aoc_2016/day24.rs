@@ -1,8 +1,19 @@
-use itertools::Itertools;
-use std::{collections::VecDeque, u32, u64};
+use std::{collections::VecDeque, fmt::Display};
 
 use fxhash::{FxHashMap, FxHashSet};
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Pair,
+    grid::Grid,
+    parse::ParseError,
+    search::bfs_within,
+    solution::Solution,
+    tsp::{self, Goal, Route},
+};
+
+#[cfg(test)]
 const SAMPLE: &str = "
 ###########
 #0.1.....2#
@@ -10,87 +21,100 @@ const SAMPLE: &str = "
 #4.......3#
 ###########";
 
-const INPUT: &str = include_str!("./inputs/day24.txt");
-
-enum Space {
+#[derive(Clone, Copy)]
+pub enum Space {
     Wall,
     Empty,
     Interface(u32),
 }
 
-type Grid = Vec<Vec<Space>>;
-type Coord = (usize /*X*/, usize /*Y*/);
-type Interface = (u32 /*Value*/, Coord);
-type Interfaces = Vec<Interface>;
-
-fn parse_grid(input: &str) -> (Grid, Interfaces) {
-    let mut interfaces = Vec::new();
-    let grid = input
-        .trim()
-        .split("\n")
-        .enumerate()
-        .map(|(y, row)| {
-            row.chars()
-                .enumerate()
-                .map(|(x, ch)| {
-                    if ch == '#' {
-                        Space::Wall
-                    } else if ch == '.' {
-                        Space::Empty
-                    } else {
-                        let interface = ch.to_digit(10).unwrap();
-                        interfaces.push((interface, (x, y)));
-                        Space::Interface(interface)
-                    }
-                })
-                .collect()
+pub type Map = Grid<Space>;
+pub type Interface = (u32 /*Value*/, Pair);
+pub type Interfaces = Vec<Interface>;
+
+fn parse_grid(input: &str) -> Result<(Map, Interfaces), ParseError> {
+    let grid = Grid::parse(input, "`#`, `.` or a digit", |ch| match ch {
+        '#' => Some(Space::Wall),
+        '.' => Some(Space::Empty),
+        _ => ch.to_digit(10).map(Space::Interface),
+    })?;
+    let mut interfaces: Interfaces = grid
+        .iter()
+        .filter_map(|(pos, space)| match space {
+            Space::Interface(value) => Some((*value, pos)),
+            _ => None,
         })
         .collect();
+    interfaces.sort_unstable_by_key(|&(value, _)| value);
+
+    // Each digit only appears once once they're checked, so it can be found again in the input
+    let digit_error = |value: u32, expected| {
+        let digit = char::from_digit(value, 10).unwrap();
+        let idx = input.rfind(digit).unwrap();
+        ParseError::new(input, idx..idx + 1, expected)
+    };
+
+    // The interfaces index the distance matrix, so they're numbered from 0 with none skipped
+    // or repeated
+    for (expected, &(value, _)) in (0..).zip(&interfaces) {
+        if value != expected {
+            return Err(digit_error(
+                value,
+                "interfaces numbered from 0 up, each once",
+            ));
+        }
+    }
+    let Some(&(_, start)) = interfaces.first() else {
+        return Err(ParseError::end_of_input(input, "interface `0`"));
+    };
+
+    // A route has to visit every interface, so they all need to be reachable
+    let reachable = bfs_within(start, usize::MAX, |&pos| {
+        grid.neighbors4(pos)
+            .filter(|&next| !matches!(grid[next], Space::Wall))
+            .collect::<Vec<_>>()
+    });
+    if let Some(&(value, _)) = interfaces
+        .iter()
+        .find(|(_, pos)| !reachable.contains_key(pos))
+    {
+        return Err(digit_error(value, "an interface reachable from `0`"));
+    }
 
-    (grid, interfaces)
-}
-
-fn neighbors(&(x, y): &Coord) -> [Coord; 4] {
-    // Since walls cover the edges, this never underflow
-    [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+    Ok((grid, interfaces))
 }
 
 fn find_connections(
-    grid: &Grid,
+    grid: &Map,
     start_interface: &Interface,
     connections: &mut FxHashMap<(u32, u32), u64>,
 ) {
     let &(start, start_coord) = start_interface;
-    let mut visited = FxHashSet::<Coord>::default();
-    let mut queue = VecDeque::<(u64, Coord)>::new();
+    let mut visited = FxHashSet::<Pair>::default();
+    let mut queue = VecDeque::<(u64, Pair)>::new();
 
     visited.insert(start_coord);
-    for n in neighbors(&start_coord) {
+    for n in grid.neighbors4(start_coord) {
         queue.push_back((1, n));
     }
 
     while let Some((steps, cur)) = queue.pop_front() {
-        if !visited.contains(&cur) {
-            let (x, y) = cur;
-            visited.insert((x, y));
-            // These unwraps are safe because the edges are impassable walls
-            let space = grid.get(y).unwrap().get(x).unwrap();
-            match space {
+        if visited.insert(cur) {
+            match grid[cur] {
                 Space::Wall => {} // no-op
                 Space::Empty => {
-                    for n in neighbors(&cur) {
+                    for n in grid.neighbors4(cur) {
                         queue.push_back((steps + 1, n));
                     }
                 }
                 Space::Interface(to) => {
-                    let to = *to;
                     if !connections.contains_key(&(start, to))
                         && !connections.contains_key(&(to, start))
                     {
                         connections.insert((start, to), steps);
                         connections.insert((to, start), steps);
                     }
-                    for n in neighbors(&cur) {
+                    for n in grid.neighbors4(cur) {
                         queue.push_back((steps + 1, n));
                     }
                 }
@@ -99,48 +123,84 @@ fn find_connections(
     }
 }
 
-// Traveling salesman is a lot easier when there are only 7 nodes to worry about
-fn find_shortest_path(
+// Interfaces are numbered from 0 with none missing, so their numbers index the matrix directly.
+// Parsing made sure they can all reach each other
+fn distance_matrix(
     connections: &FxHashMap<(u32, u32), u64>,
     interfaces: &Interfaces,
-) -> (u64, u64) {
-    let (start, rest): (Vec<u32>, Vec<u32>) =
-        interfaces.iter().map(|(x, _)| *x).partition(|a| *a == 0);
-    let start = *start.first().unwrap();
-    let mut shortest_path = u64::MAX;
-    let mut shortest_path_with_reset = u64::MAX;
-    for path in rest.iter().permutations(rest.len()) {
-        let mut path_len = 0;
-        let mut from = start;
-        for next in path {
-            path_len += connections.get(&(from, *next)).unwrap();
-            from = *next;
-        }
-        shortest_path = shortest_path.min(path_len);
+) -> Vec<Vec<u64>> {
+    let count = interfaces.len() as u32;
+    (0..count)
+        .map(|from| {
+            (0..count)
+                .map(|to| {
+                    if from == to {
+                        0
+                    } else {
+                        connections[&(from, to)]
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn shortest_routes((grid, interfaces): &(Map, Interfaces)) -> (u64, u64) {
+    let mut connections = FxHashMap::default();
+    for interface in &interfaces[1..] {
+        find_connections(grid, interface, &mut connections);
+    }
+    let distances = distance_matrix(&connections, interfaces);
+    let shortest = |route| {
+        tsp::solve(&distances, route, Goal::Shortest, Some(0))
+            .expect("there's at least interface 0")
+            .0
+    };
+    (shortest(Route::Path), shortest(Route::Cycle))
+}
+
+pub struct Day24;
 
-        path_len += connections.get(&(from, start)).unwrap();
-        shortest_path_with_reset = shortest_path_with_reset.min(path_len);
+impl Solution for Day24 {
+    type Input = (Map, Interfaces);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        shortest_routes(input).0
     }
 
-    (shortest_path, shortest_path_with_reset)
+    fn part_2(input: &Self::Input) -> impl Display {
+        shortest_routes(input).1
+    }
 }
 
 #[test]
 fn samples() {
-    let (grid, interfaces) = parse_grid(SAMPLE);
-    let mut connections = FxHashMap::default();
-    for interface in &interfaces[1..] {
-        find_connections(&grid, interface, &mut connections);
-    }
-    assert_eq!((14, 20), find_shortest_path(&connections, &interfaces));
+    assert_eq!((14, 20), shortest_routes(&parse_grid(SAMPLE).unwrap()));
 }
 
 #[test]
 fn solutions() {
-    let (grid, interfaces) = parse_grid(INPUT);
-    let mut connections = FxHashMap::default();
-    for interface in &interfaces[1..] {
-        find_connections(&grid, interface, &mut connections);
-    }
-    assert_eq!((498, 804), find_shortest_path(&connections, &interfaces));
+    let Some(input) = test_input(2016, 24) else {
+        return;
+    };
+    assert_eq!((498, 804), shortest_routes(&parse_grid(&input).unwrap()));
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_grid("#####\n#...#\n#####").err().unwrap();
+    assert_eq!("interface `0`", err.expected);
+    let err = parse_grid("#######\n#0.1.3#\n#######").err().unwrap();
+    assert_eq!((2, 6), (err.line, err.column));
+    let err = parse_grid("#######\n#0.1.1#\n#######").err().unwrap();
+    assert_eq!((2, 6), (err.line, err.column));
+    let err = parse_grid("#######\n#0.1#2#\n#######").err().unwrap();
+    assert_eq!(
+        (2, 6, "an interface reachable from `0`"),
+        (err.line, err.column, err.expected)
+    );
 }
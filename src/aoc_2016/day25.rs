@@ -1,8 +1,11 @@
-use crate::aoc_2016::day12::{Instruction, Operand, Registers, parse_instructions};
+use std::fmt::Display;
 
-const INPUT: &str = include_str!("./inputs/day25.txt");
+use crate::aoc_2016::day12::{Instruction, Operand, Registers, parse_instructions};
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
 
-fn is_clock_signal(ins: &Vec<Instruction>, a: isize, max_attempts: usize) -> bool {
+fn is_clock_signal(ins: &[Instruction], a: isize, max_attempts: usize) -> bool {
     let mut ptr = 0;
     let mut state: Registers = [a, 0, 0, 0];
     let len = ins.len();
@@ -38,7 +41,7 @@ fn is_clock_signal(ins: &Vec<Instruction>, a: isize, max_attempts: usize) -> boo
                 if x.to_value(&state) != 0 {
                     let delta = y.to_value(&state);
                     if delta.is_negative() {
-                        let delta_pos = delta.abs() as usize;
+                        let delta_pos = delta.unsigned_abs();
                         if delta_pos > ptr {
                             panic!("Can't make ptr negative");
                         }
@@ -72,7 +75,7 @@ fn is_clock_signal(ins: &Vec<Instruction>, a: isize, max_attempts: usize) -> boo
     true
 }
 
-fn search_for_signal(instructions: &Vec<Instruction>) -> isize {
+fn search_for_signal(instructions: &[Instruction]) -> isize {
     for x in 0..10000 {
         // println!("Attempting {x}:");
         if is_clock_signal(instructions, x, 10) {
@@ -80,10 +83,29 @@ fn search_for_signal(instructions: &Vec<Instruction>) -> isize {
         }
         // println!("");
     }
-    return -1;
+    -1
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<Instruction>;
+
+    const HAS_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Self::Input) -> impl Display {
+        search_for_signal(instructions)
+    }
 }
 
 #[test]
 fn solution() {
-    assert_eq!(192, search_for_signal(&parse_instructions(INPUT)));
+    let Some(input) = test_input(2016, 25) else {
+        return;
+    };
+    assert_eq!(192, search_for_signal(&parse_instructions(&input).unwrap()));
 }
@@ -1,52 +1,57 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[ \n]")]
 enum Token {
-    #[regex("[0-9]+", |lex| lex.slice().parse::<usize>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<usize>().ok())]
     Side(usize),
 }
 
 impl Token {
-    fn value(self) -> usize {
+    fn take_side(self) -> Option<usize> {
         match self {
-            Self::Side(val) => val,
+            Self::Side(val) => Some(val),
         }
     }
 }
 
-fn parse_input_by_row(input: &str) -> Vec<(usize, usize, usize)> {
+pub type Triangle = (usize, usize, usize);
+
+fn parse_rows(input: &str) -> Result<Vec<Triangle>, ParseError> {
     let mut output = vec![];
     let mut lexer = Token::lexer(input);
 
-    while let Some(token) = lexer.next() {
-        output.push((
-            token.unwrap().value(),
-            lexer.next().unwrap().unwrap().value(),
-            lexer.next().unwrap().unwrap().value(),
-        ));
+    while let Some(a) = expect_token_or_end(&mut lexer, "a side length", Token::take_side)? {
+        let b = expect_token(&mut lexer, "a side length", Token::take_side)?;
+        let c = expect_token(&mut lexer, "a side length", Token::take_side)?;
+        output.push((a, b, c));
     }
 
-    output
+    // Part 2 reads the triangles down the columns, three rows at a time
+    if output.len() % 3 != 0 {
+        return Err(ParseError::end_of_input(
+            input,
+            "another row to finish the group of three",
+        ));
+    }
+    non_empty(input, output, "a side length")
 }
 
-fn parse_input_by_col(input: &str) -> Vec<(usize, usize, usize)> {
+// Every three rows hold three triangles written down the columns
+fn regroup_by_col(rows: &[Triangle]) -> Vec<Triangle> {
     let mut output = vec![];
-    let mut lexer = Token::lexer(input);
-
-    while let Some(token) = lexer.next() {
-        let a1 = token.unwrap().value();
-        let a2 = lexer.next().unwrap().unwrap().value();
-        let a3 = lexer.next().unwrap().unwrap().value();
-
-        let b1 = lexer.next().unwrap().unwrap().value();
-        let b2 = lexer.next().unwrap().unwrap().value();
-        let b3 = lexer.next().unwrap().unwrap().value();
-
-        let c1 = lexer.next().unwrap().unwrap().value();
-        let c2 = lexer.next().unwrap().unwrap().value();
-        let c3 = lexer.next().unwrap().unwrap().value();
 
+    for chunk in rows.chunks_exact(3) {
+        let [(a1, a2, a3), (b1, b2, b3), (c1, c2, c3)] = [chunk[0], chunk[1], chunk[2]];
         output.push((a1, b1, c1));
         output.push((a2, b2, c2));
         output.push((a3, b3, c3));
@@ -55,14 +60,14 @@ fn parse_input_by_col(input: &str) -> Vec<(usize, usize, usize)> {
     output
 }
 
-fn valid_triangle(triangle: (usize, usize, usize)) -> bool {
+fn valid_triangle(triangle: Triangle) -> bool {
     let (x, y, z) = triangle;
     x < y + z && y < x + z && z < x + y
 }
 
-fn count_valid_triangles_by_row(input: &str) -> usize {
+fn count_valid_triangles_by_row(rows: &[Triangle]) -> usize {
     let mut count = 0;
-    for triangle in parse_input_by_row(input).into_iter() {
+    for &triangle in rows {
         if valid_triangle(triangle) {
             count += 1
         }
@@ -70,9 +75,9 @@ fn count_valid_triangles_by_row(input: &str) -> usize {
     count
 }
 
-fn count_valid_triangles_by_col(input: &str) -> usize {
+fn count_valid_triangles_by_col(rows: &[Triangle]) -> usize {
     let mut count = 0;
-    for triangle in parse_input_by_col(input).into_iter() {
+    for triangle in regroup_by_col(rows) {
         if valid_triangle(triangle) {
             count += 1
         }
@@ -80,14 +85,42 @@ fn count_valid_triangles_by_col(input: &str) -> usize {
     count
 }
 
-const INPUT: &'static str = include_str!("./inputs/day3.txt");
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Triangle>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rows(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_valid_triangles_by_row(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        count_valid_triangles_by_col(input)
+    }
+}
 
 #[test]
 fn part_1() {
-    assert_eq!(1032, count_valid_triangles_by_row(INPUT));
+    let Some(input) = test_input(2016, 3) else {
+        return;
+    };
+    assert_eq!(
+        1032,
+        count_valid_triangles_by_row(&parse_rows(&input).unwrap())
+    );
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(1838, count_valid_triangles_by_col(INPUT));
+    let Some(input) = test_input(2016, 3) else {
+        return;
+    };
+    assert_eq!(
+        1838,
+        count_valid_triangles_by_col(&parse_rows(&input).unwrap())
+    );
 }
@@ -1,6 +1,13 @@
 use fxhash::FxHashMap;
 use logos::{Lexer, Logos};
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt::Display};
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end, next_token, non_empty},
+    solution::Solution,
+};
 
 fn parse_checksum(lex: &mut Lexer<Token>) -> String {
     let slice = lex.slice();
@@ -13,7 +20,7 @@ enum Token {
     #[regex("[a-z]+", |lex| lex.slice().to_owned())]
     Segment(String),
 
-    #[regex("[0-9]+", |lex| lex.slice().parse::<u32>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<u32>().ok())]
     Id(u32),
 
     #[regex(r"\[[a-z]+\]", parse_checksum)]
@@ -21,75 +28,69 @@ enum Token {
 }
 
 impl Token {
-    fn segment_value(self) -> String {
+    fn take_segment(self) -> Option<String> {
+        match self {
+            Self::Segment(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn take_checksum(self) -> Option<String> {
         match self {
-            Self::Segment(value) => value,
-            _ => panic!("Can't extract segment value from non-segment"),
+            Self::Checksum(value) => Some(value),
+            _ => None,
         }
     }
 }
 
-struct Room {
+pub struct Room {
     id: u32,
     name_segments: Vec<String>,
     checksum: String,
 }
 
-fn parse_room(tokens: &mut Lexer<Token>) -> Option<Room> {
-    let first_segment = tokens.next();
-    let mut name_segments: Vec<String> = vec![];
-    match first_segment {
-        Some(tok) => name_segments.push(tok.unwrap().segment_value()),
-        None => {
-            return None;
-        }
-    }
-
-    let mut id: Option<u32> = None;
-    let checksum: String;
-
-    loop {
-        match tokens.next().unwrap().unwrap() {
-            Token::Segment(value) => {
-                name_segments.push(value);
-            }
-            Token::Id(value) => {
-                id = Some(value);
-            }
-            Token::Checksum(value) => {
-                checksum = value;
-                break;
+fn parse_room(tokens: &mut Lexer<Token>) -> Result<Option<Room>, ParseError> {
+    let Some(first_segment) = expect_token_or_end(tokens, "a room name", Token::take_segment)?
+    else {
+        return Ok(None);
+    };
+    let mut name_segments = vec![first_segment];
+
+    let id = loop {
+        match next_token(tokens, "a name segment or sector ID")? {
+            Token::Segment(value) => name_segments.push(value),
+            Token::Id(value) => break value,
+            Token::Checksum(_) => {
+                return Err(ParseError::new(
+                    tokens.source(),
+                    tokens.span(),
+                    "a name segment or sector ID",
+                ));
             }
         }
-    }
+    };
+    let checksum = expect_token(tokens, "a checksum like `[abcde]`", Token::take_checksum)?;
 
-    if id.is_none() {
-        panic!("Failed to parse all components");
-    }
-
-    Some(Room {
+    Ok(Some(Room {
         name_segments,
-        id: id.unwrap(),
+        id,
         checksum,
-    })
+    }))
 }
 
-fn parse_rooms(input: &str) -> Vec<Room> {
+fn parse_rooms(input: &str) -> Result<Vec<Room>, ParseError> {
     let mut rooms = vec![];
-    let mut tokens = Token::lexer(input).into_iter();
-    loop {
-        if let Some(room) = parse_room(&mut tokens) {
-            rooms.push(room);
-        } else {
-            return rooms;
-        }
+    let mut tokens = Token::lexer(input);
+    while let Some(room) = parse_room(&mut tokens)? {
+        rooms.push(room);
     }
+    non_empty(input, rooms, "a room name")
 }
 
-fn checksum_map(segments: &Vec<String>) -> FxHashMap<char, usize> {
+fn checksum_map(segments: &[String]) -> FxHashMap<char, usize> {
     let mut char_map = FxHashMap::default();
 
-    for seg in segments.into_iter() {
+    for seg in segments.iter() {
         for char in seg.chars() {
             char_map.entry(char).and_modify(|m| *m += 1).or_insert(1);
         }
@@ -98,9 +99,15 @@ fn checksum_map(segments: &Vec<String>) -> FxHashMap<char, usize> {
     char_map
 }
 
-#[derive(PartialEq, Eq, PartialOrd)]
+#[derive(PartialEq, Eq)]
 struct HeatMapPair(char, usize);
 
+impl PartialOrd for HeatMapPair {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for HeatMapPair {
     fn cmp(&self, other: &Self) -> Ordering {
         // This ordering ensures that the count is ordered first (in reverse so that higher is sooner in the list)
@@ -115,25 +122,23 @@ fn validate_checksum(room: &Room) -> u32 {
         .into_iter()
         .map(|(ch, count)| HeatMapPair(ch, count))
         .collect::<Vec<_>>();
-    pairs.sort_by(|a, b| a.cmp(b));
+    pairs.sort();
 
-    if room.checksum == (&pairs[0..5]).iter().map(|v| v.0).collect::<String>() {
+    // Names with fewer than five different letters can't match a full checksum
+    if room.checksum == pairs.iter().take(5).map(|v| v.0).collect::<String>() {
         room.id
     } else {
         0
     }
 }
 
-fn sum_of_valid_room_ids(input: &str) -> u32 {
-    parse_rooms(input)
-        .into_iter()
-        .map(|room| validate_checksum(&room))
-        .sum()
+fn sum_of_valid_room_ids(rooms: &[Room]) -> u32 {
+    rooms.iter().map(validate_checksum).sum()
 }
 
 const LOWER_A: u32 = 'a' as u32;
 fn apply_cypher(room: &Room) -> String {
-    let increment = room.id as u32;
+    let increment = room.id;
     let updated: Vec<String> = room
         .name_segments
         .iter()
@@ -144,34 +149,61 @@ fn apply_cypher(room: &Room) -> String {
                 .collect::<String>()
         })
         .collect();
-    return updated.join(" ");
+    updated.join(" ")
 }
 
-fn find_northpole_objects_id(input: &str) -> Option<u32> {
-    for room in parse_rooms(input).into_iter() {
-        if validate_checksum(&room) > 0 && apply_cypher(&room) == "northpole object storage" {
+fn find_northpole_objects_id(rooms: &[Room]) -> Option<u32> {
+    for room in rooms {
+        if validate_checksum(room) > 0 && apply_cypher(room) == "northpole object storage" {
             return Some(room.id);
         }
     }
     None
 }
 
-const SAMPLE: &'static str = "aaaaa-bbb-z-y-x-123[abxyz]
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Room>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rooms(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        sum_of_valid_room_ids(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        find_northpole_objects_id(input).expect("no real room stores North Pole objects")
+    }
+}
+
+#[cfg(test)]
+const SAMPLE: &str = "aaaaa-bbb-z-y-x-123[abxyz]
 a-b-c-d-e-f-g-h-987[abcde]
 not-a-real-room-404[oarel]
 totally-real-room-200[decoy]
 ";
 // qzmt-zixmtkozy-ivhz-343[zimth] => very encrypted name
 
-const INPUT: &'static str = include_str!("./inputs/day4.txt");
-
 #[test]
 fn part_1() {
-    assert_eq!(1514, sum_of_valid_room_ids(SAMPLE));
-    assert_eq!(278221, sum_of_valid_room_ids(INPUT));
+    assert_eq!(1514, sum_of_valid_room_ids(&parse_rooms(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2016, 4) else {
+        return;
+    };
+    assert_eq!(278221, sum_of_valid_room_ids(&parse_rooms(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(Some(267), find_northpole_objects_id(INPUT));
+    let Some(input) = test_input(2016, 4) else {
+        return;
+    };
+    assert_eq!(
+        Some(267),
+        find_northpole_objects_id(&parse_rooms(&input).unwrap())
+    );
 }
@@ -1,6 +1,12 @@
+use std::fmt::Display;
+
 use fxhash::FxHashSet;
 
-use crate::util::generate_padded_hash;
+use crate::util::{
+    hash::padded_matches,
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
 
 fn from_useful_hash(hash: [u8; 16]) -> Option<char> {
     if hash[0] == 0 && hash[1] == 0 && hash[2] < 0x10 {
@@ -10,7 +16,7 @@ fn from_useful_hash(hash: [u8; 16]) -> Option<char> {
     }
 }
 
-fn _from_inspired_hash(hash: [u8; 16]) -> Option<(usize, char)> {
+fn from_inspired_hash(hash: [u8; 16]) -> Option<(usize, char)> {
     if hash[0] == 0 && hash[1] == 0 && hash[2] < 0x10 {
         let position = hash[2] as usize;
         if position > 7 {
@@ -28,49 +34,62 @@ fn _from_inspired_hash(hash: [u8; 16]) -> Option<(usize, char)> {
 }
 
 fn find_password(input: &[u8]) -> String {
-    let mut i = 0;
-    let mut password_chars: Vec<char> = Vec::new();
-    while password_chars.len() < 8 {
-        let hash = generate_padded_hash(input, i);
-        if let Some(ch) = from_useful_hash(hash) {
-            password_chars.push(ch);
-        }
-
-        i += 1;
-    }
-    password_chars.iter().collect()
+    padded_matches(input, 0, |&hash| from_useful_hash(hash))
+        .take(8)
+        .map(|(_, ch)| ch)
+        .collect()
 }
 
-fn _find_inspired_password(input: &[u8]) -> String {
-    let mut i = 0;
+fn find_inspired_password(input: &[u8]) -> String {
     let mut password_chars: [char; 8] = ['_', '_', '_', '_', '_', '_', '_', '_'];
     let mut remaining_positions = FxHashSet::from_iter([0, 1, 2, 3, 4, 5, 6, 7].iter());
-    while remaining_positions.len() > 0 {
-        let hash = generate_padded_hash(input, i);
-        if let Some((pos, ch)) = _from_inspired_hash(hash) {
-            if remaining_positions.contains(&pos) {
-                password_chars[pos] = ch;
-                remaining_positions.remove(&pos);
+    for (_, (pos, ch)) in padded_matches(input, 0, |&hash| from_inspired_hash(hash)) {
+        if remaining_positions.remove(&pos) {
+            password_chars[pos] = ch;
+            if remaining_positions.is_empty() {
+                break;
             }
         }
-
-        i += 1;
     }
     password_chars.iter().collect()
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<u8>;
+
+    const INPUT: Option<&'static str> = Some(INPUT);
+    const SLOW_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let door_id = parse_chars(input, "a door ID", |ch| !ch.is_whitespace())?;
+        Ok(door_id.as_bytes().to_vec())
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        find_password(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        find_inspired_password(input)
+    }
+}
+
+#[cfg(test)]
 const SAMPLE: &[u8] = b"abc";
-const INPUT: &[u8] = b"ffykfhsq";
+pub const INPUT: &str = "ffykfhsq";
 
 #[test]
 fn part_1() {
     assert_eq!("18f47a30", find_password(SAMPLE));
-    assert_eq!("c6697b55", find_password(INPUT));
+    assert_eq!("c6697b55", find_password(INPUT.as_bytes()));
 }
 
+// These add ~3 seconds in release mode
 #[test]
+#[cfg_attr(not(feature = "slow"), ignore = "slow, run with `--features slow`")]
 fn part_2() {
-    // These add ~3 seconds in release mode
-    // assert_eq!("05ace8e3", _find_inspired_password(SAMPLE));
-    // assert_eq!("8c35d1ab", _find_inspired_password(INPUT));
+    assert_eq!("05ace8e3", find_inspired_password(SAMPLE));
+    assert_eq!("8c35d1ab", find_inspired_password(INPUT.as_bytes()));
 }
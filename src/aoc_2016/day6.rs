@@ -1,15 +1,34 @@
+use std::fmt::Display;
+
 use fxhash::FxHashMap;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_chars},
+    solution::Solution,
+};
+
 // Too simple for a Logos lexer
-fn parse_input(input: &str) -> Vec<String> {
-    input
-        .trim()
-        .split("\n")
-        .map(|segment| segment.to_owned())
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let messages = parse_chars(input, "a message of lowercase letters", |ch| {
+        ch.is_ascii_lowercase() || ch == '\n'
+    })?;
+    let messages: Vec<&str> = messages.split("\n").collect();
+    let len = messages[0].len();
+    for message in &messages {
+        if message.len() != len {
+            return Err(ParseError::at(
+                input,
+                message,
+                "a message as long as the first",
+            ));
+        }
+    }
+    Ok(messages.into_iter().map(String::from).collect())
 }
 
-fn create_heatmaps(messages: Vec<String>) -> Vec<FxHashMap<char, usize>> {
+fn create_heatmaps(messages: &[String]) -> Vec<FxHashMap<char, usize>> {
     let mut heatmaps: Vec<FxHashMap<char, usize>> = vec![];
     for _ in 0..messages.first().unwrap().len() {
         heatmaps.push(FxHashMap::default());
@@ -27,8 +46,8 @@ fn create_heatmaps(messages: Vec<String>) -> Vec<FxHashMap<char, usize>> {
     heatmaps
 }
 
-fn decode_signal(input: &str, most: bool) -> String {
-    let heatmaps = create_heatmaps(parse_input(input));
+fn decode_signal(messages: &[String], most: bool) -> String {
+    let heatmaps = create_heatmaps(messages);
     heatmaps
         .into_iter()
         .map(|heatmap| {
@@ -45,7 +64,26 @@ fn decode_signal(input: &str, most: bool) -> String {
         .collect()
 }
 
-const SAMPLE: &'static str = "eedadn
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        decode_signal(input, true)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        decode_signal(input, false)
+    }
+}
+
+#[cfg(test)]
+const SAMPLE: &str = "eedadn
 drvtee
 eandsr
 raavrd
@@ -63,17 +101,32 @@ dvrsen
 enarar
 ";
 
-const INPUT: &'static str = include_str!("./inputs/day6.txt");
-
 #[test]
 fn part_1() {
-    assert_eq!("easter", decode_signal(SAMPLE, true));
-    assert_eq!("agmwzecr", decode_signal(INPUT, true));
+    assert_eq!("easter", decode_signal(&parse_input(SAMPLE).unwrap(), true));
+
+    let Some(input) = test_input(2016, 6) else {
+        return;
+    };
+    assert_eq!(
+        "agmwzecr",
+        decode_signal(&parse_input(&input).unwrap(), true)
+    );
 }
 
 #[test]
 fn part_2() {
     // I actually found these answers first by accident before realizing I reversed the sort order
-    assert_eq!("advent", decode_signal(SAMPLE, false));
-    assert_eq!("owlaxqvq", decode_signal(INPUT, false));
+    assert_eq!(
+        "advent",
+        decode_signal(&parse_input(SAMPLE).unwrap(), false)
+    );
+
+    let Some(input) = test_input(2016, 6) else {
+        return;
+    };
+    assert_eq!(
+        "owlaxqvq",
+        decode_signal(&parse_input(&input).unwrap(), false)
+    );
 }
@@ -1,5 +1,14 @@
+use std::fmt::Display;
+
 use logos::{Lexer, Logos};
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, next_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos()]
 enum Token {
@@ -16,73 +25,69 @@ enum Token {
     Newline,
 }
 
-struct Address {
+pub struct Address {
     supernets: Vec<String>,
     hypernets: Vec<String>,
 }
 
-fn parse_address(tokens: &mut Lexer<Token>) -> Option<Address> {
+fn parse_address(tokens: &mut Lexer<Token>) -> Result<Option<Address>, ParseError> {
     let mut addr = Address {
         supernets: Vec::new(),
         hypernets: Vec::new(),
     };
 
-    for token in tokens {
-        match token.unwrap() {
+    while let Some(token) = next_token_or_end(tokens, "a supernet or `[hypernet]` sequence")? {
+        match token {
             Token::Supernet(value) => {
                 addr.supernets.push(value);
             }
             Token::Hypernet(value) => {
                 addr.hypernets.push(value);
             }
+            // Blank lines don't hold an address, so keep looking past them
+            Token::Newline if addr.supernets.is_empty() && addr.hypernets.is_empty() => {}
             Token::Newline => {
                 break;
             }
         }
     }
 
-    if addr.supernets.is_empty() || addr.hypernets.is_empty() {
-        None
+    if addr.supernets.is_empty() && addr.hypernets.is_empty() {
+        Ok(None)
     } else {
-        Some(addr)
+        Ok(Some(addr))
     }
 }
 
-fn parse_addresses(input: &str) -> Vec<Address> {
+fn parse_addresses(input: &str) -> Result<Vec<Address>, ParseError> {
     let mut addresses = vec![];
     let mut tokens = Token::lexer(input);
 
-    while let Some(addr) = parse_address(&mut tokens) {
+    while let Some(addr) = parse_address(&mut tokens)? {
         addresses.push(addr);
     }
 
-    addresses
+    non_empty(input, addresses, "an address")
 }
 
 fn has_abba(value: &[u8]) -> bool {
-    for i in 0..value.len() - 3 {
-        if value[i] != value[i + 1] && value[i] == value[i + 3] && value[i + 1] == value[i + 2] {
-            return true;
-        }
-    }
-    false
+    value
+        .windows(4)
+        .any(|window| window[0] != window[1] && window[0] == window[3] && window[1] == window[2])
 }
 
 fn find_abas(value: &[u8], abas: &mut Vec<(u8, u8)>) {
-    for i in 0..value.len() - 2 {
-        if value[i] != value[i + 1] && value[i] == value[i + 2] {
-            abas.push((value[i], value[i + 1]));
+    for window in value.windows(3) {
+        if window[0] != window[1] && window[0] == window[2] {
+            abas.push((window[0], window[1]));
         }
     }
 }
 
 fn has_bab(value: &[u8], aba: (u8, u8)) -> bool {
-    for i in 0..value.len() - 2 {
-        if value[i] == aba.1 && value[i + 1] == aba.0 && value[i + 2] == aba.1 {
-            return true;
-        }
-    }
-    false
+    value
+        .windows(3)
+        .any(|window| window[0] == aba.1 && window[1] == aba.0 && window[2] == aba.1)
 }
 
 fn is_tls_address(address: &Address) -> bool {
@@ -96,11 +101,8 @@ fn is_tls_address(address: &Address) -> bool {
             .all(|hnet| !has_abba(hnet.as_bytes()))
 }
 
-fn count_tls_addresses(input: &str) -> usize {
-    parse_addresses(input)
-        .iter()
-        .filter(|addr| is_tls_address(addr))
-        .count()
+fn count_tls_addresses(addresses: &[Address]) -> usize {
+    addresses.iter().filter(|addr| is_tls_address(addr)).count()
 }
 
 fn is_ssl_address(address: &Address) -> bool {
@@ -118,35 +120,58 @@ fn is_ssl_address(address: &Address) -> bool {
     false
 }
 
-fn count_ssl_addresses(input: &str) -> usize {
-    parse_addresses(input)
-        .iter()
-        .filter(|addr| is_ssl_address(addr))
-        .count()
+fn count_ssl_addresses(addresses: &[Address]) -> usize {
+    addresses.iter().filter(|addr| is_ssl_address(addr)).count()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Address>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_addresses(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_tls_addresses(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        count_ssl_addresses(input)
+    }
 }
 
-const SAMPLE_1: &'static str = "abba[mnop]qrst
+#[cfg(test)]
+const SAMPLE_1: &str = "abba[mnop]qrst
 abcd[bddb]xyyx
 aaaa[qwer]tyui
 ioxxoj[asdfgh]zxcvbn
 ";
 
-const SAMPLE_2: &'static str = "aba[bab]xyz
+#[cfg(test)]
+const SAMPLE_2: &str = "aba[bab]xyz
 xyx[xyx]xyx
 aaa[kek]eke
 zazbz[bzb]cdb
 ";
 
-const INPUT: &'static str = include_str!("./inputs/day7.txt");
-
 #[test]
 fn part_1() {
-    assert_eq!(2, count_tls_addresses(SAMPLE_1));
-    assert_eq!(105, count_tls_addresses(INPUT));
+    assert_eq!(2, count_tls_addresses(&parse_addresses(SAMPLE_1).unwrap()));
+
+    let Some(input) = test_input(2016, 7) else {
+        return;
+    };
+    assert_eq!(105, count_tls_addresses(&parse_addresses(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(3, count_ssl_addresses(SAMPLE_2));
-    assert_eq!(258, count_ssl_addresses(INPUT));
+    assert_eq!(3, count_ssl_addresses(&parse_addresses(SAMPLE_2).unwrap()));
+
+    let Some(input) = test_input(2016, 7) else {
+        return;
+    };
+    assert_eq!(258, count_ssl_addresses(&parse_addresses(&input).unwrap()));
 }
@@ -1,12 +1,24 @@
+use std::fmt::Display;
+
 use logos::{Lexer, Logos};
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Pair,
+    grid::Grid,
+    ocr::read_letters,
+    parse::{ParseError, expect_token, next_token_or_end, non_empty},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"[ x\n]", skip r"by")]
 enum Token {
     #[token("rect")]
     Rect,
 
-    #[regex(r"\d+", |lex| lex.slice().parse::<usize>().unwrap())]
+    #[regex(r"\d+", |lex| lex.slice().parse::<usize>().ok())]
     Number(usize),
 
     #[token("rotate row y=")]
@@ -17,103 +29,116 @@ enum Token {
 }
 
 impl Token {
-    fn get_number(self) -> usize {
+    fn take_number(self) -> Option<usize> {
         match self {
-            Self::Number(val) => val,
-            _ => panic!("NAN"),
+            Self::Number(val) => Some(val),
+            _ => None,
         }
     }
 }
 
+#[derive(Clone, Copy)]
 enum Instruction {
     Rect(usize, usize),
     RotateRow(usize, usize),
     RotateCol(usize, usize),
 }
 
-struct Screen([[bool; 50]; 6]);
+pub struct Screen(Grid<bool>);
 
 impl Screen {
     fn new() -> Self {
-        Self([[false; 50]; 6])
+        Self(Grid::new(WIDTH, HEIGHT, false))
     }
 
     fn rect(&mut self, width: usize, height: usize) {
         for y in 0..height {
             for x in 0..width {
-                self.0[y][x] = true;
+                self.0[Pair(x as i64, y as i64)] = true;
             }
         }
     }
 
     fn rotate_row(&mut self, row: usize, count: usize) {
-        self.0[row].rotate_right(count);
+        self.0.rotate_row(row, count);
     }
 
     fn rotate_col(&mut self, col: usize, count: usize) {
-        let mut column_values: Vec<bool> = self.0.iter().map(|row| row[col]).collect();
-        column_values.rotate_right(count);
-        for row in 0..6 {
-            self.0[row][col] = column_values[row];
-        }
+        self.0.rotate_col(col, count);
     }
 
-    fn _print(&self) {
-        for row in self.0 {
-            for col in row {
-                if col {
-                    print!("##");
-                } else {
-                    print!("  ");
-                }
-            }
-            println!("");
-        }
+    fn render(&self) -> String {
+        self.0.render(|&lit| if lit { "##" } else { "  " })
+    }
+
+    fn read(&self) -> Option<String> {
+        read_letters(&self.0)
     }
 
     fn voltage(&self) -> usize {
-        self.0
-            .iter()
-            .fold(0, |count, row| count + row.iter().filter(|x| **x).count())
+        self.0.values().filter(|&&lit| lit).count()
     }
 }
 
-fn parse_instruction(tokens: &mut Lexer<Token>) -> Option<Instruction> {
-    match tokens.next() {
-        None => None,
-        Some(Ok(Token::Rect)) => {
-            let x = tokens.next().unwrap().unwrap();
-            let y = tokens.next().unwrap().unwrap();
-            return Some(Instruction::Rect(x.get_number(), y.get_number()));
+const WIDTH: usize = 50;
+const HEIGHT: usize = 6;
+
+// Reads a number no larger than `max`, so out of range instructions can't index off the screen
+fn expect_number(
+    tokens: &mut Lexer<Token>,
+    expected: &'static str,
+    max: usize,
+) -> Result<usize, ParseError> {
+    expect_token(tokens, expected, |token| {
+        token.take_number().filter(|&val| val <= max)
+    })
+}
+
+fn parse_instruction(tokens: &mut Lexer<Token>) -> Result<Option<Instruction>, ParseError> {
+    let Some(token) = next_token_or_end(tokens, "an instruction")? else {
+        return Ok(None);
+    };
+    let instruction = match token {
+        Token::Rect => {
+            let x = expect_number(tokens, "a width of at most 50", WIDTH)?;
+            let y = expect_number(tokens, "a height of at most 6", HEIGHT)?;
+            Instruction::Rect(x, y)
         }
-        Some(Ok(Token::RotateRow)) => {
-            let row = tokens.next().unwrap().unwrap();
-            let count = tokens.next().unwrap().unwrap();
-            return Some(Instruction::RotateRow(row.get_number(), count.get_number()));
+        Token::RotateRow => {
+            let row = expect_number(tokens, "a row below 6", HEIGHT - 1)?;
+            let count = expect_number(tokens, "a rotation count", usize::MAX)?;
+            Instruction::RotateRow(row, count % WIDTH)
         }
-        Some(Ok(Token::RotateCol)) => {
-            let col = tokens.next().unwrap().unwrap();
-            let count = tokens.next().unwrap().unwrap();
-            return Some(Instruction::RotateCol(col.get_number(), count.get_number()));
+        Token::RotateCol => {
+            let col = expect_number(tokens, "a column below 50", WIDTH - 1)?;
+            let count = expect_number(tokens, "a rotation count", usize::MAX)?;
+            Instruction::RotateCol(col, count % HEIGHT)
         }
-        _ => unreachable!(),
-    }
+        Token::Number(_) => {
+            return Err(ParseError::new(
+                tokens.source(),
+                tokens.span(),
+                "an instruction",
+            ));
+        }
+    };
+    Ok(Some(instruction))
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
     let mut ins = vec![];
     let mut tokens = Token::lexer(input);
 
-    while let Some(instruction) = parse_instruction(&mut tokens) {
+    while let Some(instruction) = parse_instruction(&mut tokens)? {
         ins.push(instruction);
     }
 
-    ins
+    non_empty(input, ins, "an instruction")
 }
 
-fn execute_instructions(input: &str) -> Screen {
+fn execute_instructions(instructions: &[Instruction]) -> Screen {
     let mut screen = Screen::new();
-    for instruction in parse_instructions(input) {
+    for &instruction in instructions {
         match instruction {
             Instruction::Rect(width, height) => screen.rect(width, height),
             Instruction::RotateRow(row, count) => screen.rotate_row(row, count),
@@ -124,13 +149,41 @@ fn execute_instructions(input: &str) -> Screen {
     screen
 }
 
-const INPUT: &'static str = include_str!("./inputs/day8.txt");
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Screen;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(execute_instructions(&parse_instructions(input)?))
+    }
+
+    fn part_1(screen: &Self::Input) -> impl Display {
+        screen.voltage()
+    }
+
+    // Falls back to drawing the screen if the OCR doesn't know one of the letters
+    fn part_2(screen: &Self::Input) -> impl Display {
+        screen
+            .read()
+            .unwrap_or_else(|| format!("\n{}", screen.render()))
+    }
+}
 
 #[test]
 fn both_parts() {
-    let screen = execute_instructions(INPUT);
+    let Some(input) = test_input(2016, 8) else {
+        return;
+    };
+    let screen = execute_instructions(&parse_instructions(&input).unwrap());
     assert_eq!(115, screen.voltage());
+    assert_eq!(Some(String::from("EFEYKFRFIJ")), screen.read());
+}
 
-    // Should spell "EFEYKFRFIJ" in console
-    // screen._jsprint();
+#[test]
+fn bad_input() {
+    let err = parse_instructions("rect 3x2\nrotate row y=6 by 4\n")
+        .err()
+        .unwrap();
+    assert_eq!((2, 14), (err.line, err.column));
 }
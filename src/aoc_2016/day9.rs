@@ -1,5 +1,14 @@
+use std::fmt::Display;
+
 use logos::{Lexer, Logos};
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, next_token, next_token_or_end, parse_at},
+    solution::Solution,
+};
+
 #[derive(Logos)]
 #[logos(skip r"\n")]
 enum Token {
@@ -20,7 +29,7 @@ enum Token {
 }
 
 impl Token {
-    fn to_string(self) -> String {
+    fn take_string(self) -> String {
         match self {
             Self::OpenBrace => String::from("("),
             Self::CloseBrace => String::from(")"),
@@ -45,12 +54,72 @@ fn parse_marker(ts: &mut Lexer<Token>) -> String {
     let (char_count, _) = parse_number(ts);
     let (repeat_count, _) = parse_number(ts);
     let mut segment = Vec::with_capacity(char_count);
-    for _ in 0..char_count {
-        segment.push(ts.next().unwrap().unwrap().to_string())
+    for token in ts.take(char_count).map_while(Result::ok) {
+        segment.push(token.take_string())
     }
     segment.join("").repeat(repeat_count)
 }
 
+// Reads the digits of a marker up to and including the token that closes them
+fn check_marker_number(
+    ts: &mut Lexer<Token>,
+    expected: &'static str,
+    is_end: fn(&Token) -> bool,
+) -> Result<(), ParseError> {
+    expect_token(ts, expected, |token| {
+        matches!(token, Token::Number(_)).then_some(())
+    })?;
+    loop {
+        match next_token(ts, expected)? {
+            Token::Number(_) => {}
+            token if is_end(&token) => return Ok(()),
+            _ => return Err(ParseError::new(ts.source(), ts.span(), expected)),
+        }
+    }
+}
+
+// The decompressors walk the text themselves, so this only checks every marker is well formed,
+// and that the text it repeats fits inside the input and inside any marker around it
+fn check_markers(input: &str) -> Result<(), ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::end_of_input(
+            input,
+            "a capital letter or marker",
+        ));
+    }
+    let mut tokens = Token::lexer(input);
+    let mut enclosing_ends: Vec<usize> = Vec::new();
+    while let Some(token) = next_token_or_end(&mut tokens, "a capital letter or marker")? {
+        if let Token::OpenBrace = token {
+            let start = tokens.span().start;
+            check_marker_number(&mut tokens, "a character count then `x`", |token| {
+                matches!(token, Token::Delim)
+            })?;
+            check_marker_number(&mut tokens, "a repeat count then `)`", |token| {
+                matches!(token, Token::CloseBrace)
+            })?;
+
+            // Both numbers are known to be there, so the count is everything before the `x`
+            let marker_end = tokens.span().end;
+            let (count_text, _) = input[start + 1..marker_end - 1].split_once('x').unwrap();
+            let count: usize = parse_at(input, count_text, "a character count")?;
+            while enclosing_ends.last().is_some_and(|&end| end <= start) {
+                enclosing_ends.pop();
+            }
+            let limit = enclosing_ends.last().copied().unwrap_or(input.len());
+            if marker_end + count > limit {
+                return Err(ParseError::at(
+                    input,
+                    count_text,
+                    "a character count that stays inside the text being repeated",
+                ));
+            }
+            enclosing_ends.push(marker_end + count);
+        }
+    }
+    Ok(())
+}
+
 fn decompress(input: &str) -> String {
     let mut output_segments: Vec<String> = Vec::new();
     let mut tokens = Token::lexer(input);
@@ -60,26 +129,21 @@ fn decompress(input: &str) -> String {
             Token::OpenBrace => {
                 output_segments.push(parse_marker(&mut tokens));
             }
-            other => output_segments.push(other.to_string()),
+            other => output_segments.push(other.take_string()),
         }
     }
 
     output_segments.join("")
 }
 
-struct Segment {
+pub struct Segment {
     pure_segment_size: usize,
     markers: Vec<Marker>,
 }
 
 impl Segment {
-    fn size(self) -> usize {
-        self.pure_segment_size
-            + self
-                .markers
-                .into_iter()
-                .map(|marker| marker.size())
-                .sum::<usize>()
+    fn size(&self) -> usize {
+        self.pure_segment_size + self.markers.iter().map(Marker::size).sum::<usize>()
     }
 }
 
@@ -89,68 +153,136 @@ struct Marker {
 }
 
 impl Marker {
-    fn size(self) -> usize {
+    fn size(&self) -> usize {
         self.repeat * self.segment.size()
     }
 }
 
-fn parse_segment_v2(ts: &mut Lexer<Token>, length: usize) -> Segment {
+fn parse_segment_v2(ts: &mut Lexer<Token>, length: usize) -> Result<Segment, ParseError> {
     let mut remaining = length;
     let mut pure_segment_size: usize = 0;
     let mut markers: Vec<Marker> = Vec::new();
 
     while remaining > 0 {
-        let token = ts.next();
-        if token.is_none() {
+        let Some(token) = next_token_or_end(ts, "a capital letter or marker")? else {
             break;
-        }
-        let token = token.unwrap().unwrap();
-        match token {
+        };
+        let size = match token {
             Token::OpenBrace => {
-                let (marker, compressed_size): (Marker, usize) = parse_marker_v2(ts);
+                let (marker, compressed_size): (Marker, usize) = parse_marker_v2(ts)?;
                 markers.push(marker);
-                remaining -= compressed_size;
+                compressed_size
             }
             _ => {
                 pure_segment_size += 1;
-                remaining -= 1;
+                1
             }
-        }
+        };
+        remaining = remaining.checked_sub(size).ok_or_else(|| {
+            ParseError::new(
+                ts.source(),
+                ts.span(),
+                "a character count that stays inside the text being repeated",
+            )
+        })?;
     }
-    Segment {
+    Ok(Segment {
         pure_segment_size,
         markers,
-    }
+    })
 }
 
-fn parse_marker_v2(ts: &mut Lexer<Token>) -> (Marker, usize) {
+fn parse_marker_v2(ts: &mut Lexer<Token>) -> Result<(Marker, usize), ParseError> {
     let (char_count, char_len) = parse_number(ts);
     let (repeat, repeat_len) = parse_number(ts);
-    let segment = parse_segment_v2(ts, char_count);
+    let segment = parse_segment_v2(ts, char_count)?;
     let compressed_length = char_count + char_len + repeat_len + 3; // 3 for the parens and 'x' chars
-    (Marker { repeat, segment }, compressed_length)
+    Ok((Marker { repeat, segment }, compressed_length))
 }
 
-fn decompress_v2(input: &str) -> Segment {
+fn decompress_v2(input: &str) -> Result<Segment, ParseError> {
     let mut tokens = Token::lexer(input);
     parse_segment_v2(&mut tokens, input.len())
 }
 
+pub struct Compressed {
+    text: String,
+    // Part 2's markers nest, so they're read into a tree up front
+    segment: Segment,
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Compressed;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let input = input.trim_end();
+        check_markers(input)?;
+        Ok(Compressed {
+            text: input.to_owned(),
+            segment: decompress_v2(input)?,
+        })
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        decompress(&input.text).len()
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        input.segment.size()
+    }
+}
+
+#[cfg(test)]
 const SAMPLE_1: &str = "X(8x2)(3x3)ABCY";
+#[cfg(test)]
 const SAMPLE_2: &str = "(25x3)(3x3)ABC(2x3)XY(5x2)PQRSTX(18x9)(3x2)TWO(5x7)SEVEN";
+#[cfg(test)]
 const SAMPLE_3: &str = "(27x12)(20x12)(13x14)(7x10)(1x12)A";
-const INPUT: &str = include_str!("./inputs/day9.txt");
 
 #[test]
 fn part_1() {
     assert_eq!(18, decompress(SAMPLE_1).len());
-    assert_eq!(152851, decompress(INPUT).len());
+
+    let Some(input) = test_input(2016, 9) else {
+        return;
+    };
+    assert_eq!(152851, decompress(&input).len());
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(20, decompress_v2(SAMPLE_1).size());
-    assert_eq!(445, decompress_v2(SAMPLE_2).size());
-    assert_eq!(241920, decompress_v2(SAMPLE_3).size());
-    assert_eq!(11797310782, decompress_v2(INPUT).size());
+    assert_eq!(20, decompress_v2(SAMPLE_1).unwrap().size());
+    assert_eq!(445, decompress_v2(SAMPLE_2).unwrap().size());
+    assert_eq!(241920, decompress_v2(SAMPLE_3).unwrap().size());
+
+    let Some(input) = test_input(2016, 9) else {
+        return;
+    };
+    assert_eq!(11797310782, decompress_v2(&input).unwrap().size());
+}
+
+#[test]
+fn bad_input() {
+    assert!(check_markers(SAMPLE_2).is_ok());
+
+    let err = check_markers("A(2x3BC)").unwrap_err();
+    assert_eq!((1, 6), (err.line, err.column));
+    assert!(check_markers("A(2xb)").is_err());
+    assert!(check_markers("").is_err());
+
+    // The repeated text has to fit in the input, and inside the marker around it
+    let err = check_markers("A(3x2)BC").unwrap_err();
+    assert_eq!((1, 3), (err.line, err.column));
+    let err = check_markers("(7x2)(3x3)ABCD").unwrap_err();
+    assert_eq!((1, 7), (err.line, err.column));
+
+    // Part 2's parser reports the same problems itself rather than relying on the check above
+    let err = decompress_v2("(7x2)(3x3)ABCD").err().unwrap();
+    assert_eq!(
+        "a character count that stays inside the text being repeated",
+        err.expected
+    );
+    assert!(decompress_v2("A(2x3)B%").is_err());
 }
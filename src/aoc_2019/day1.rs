@@ -1,18 +1,25 @@
-fn parse_input(input: &str) -> Vec<i64> {
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
     input
         .trim()
         .split("\n")
-        .map(|x| x.parse().unwrap())
+        .map(|x| parse_at(input, x, "a module mass"))
         .collect()
 }
 
-const INPUT: &str = include_str!("./inputs/day1.txt");
-
-fn fuel_required(masses: &Vec<i64>) -> i64 {
+fn fuel_required(masses: &[i64]) -> i64 {
     masses.iter().map(|mass| mass / 3 - 2).sum()
 }
 
-fn real_fuel_required(masses: &Vec<i64>) -> i64 {
+fn real_fuel_required(masses: &[i64]) -> i64 {
     masses.iter().map(|&mass| fuel_for_mass(mass)).sum()
 }
 
@@ -25,9 +32,30 @@ fn fuel_for_mass(mass: i64) -> i64 {
     }
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(masses: &Self::Input) -> impl Display {
+        fuel_required(masses)
+    }
+
+    fn part_2(masses: &Self::Input) -> impl Display {
+        real_fuel_required(masses)
+    }
+}
+
 #[test]
 fn solutions() {
-    let module_masses = parse_input(INPUT);
+    let Some(input) = test_input(2019, 1) else {
+        return;
+    };
+    let module_masses = parse_input(&input).unwrap();
     assert_eq!(3390830, fuel_required(&module_masses));
     assert_eq!(5083370, real_fuel_required(&module_masses));
 }
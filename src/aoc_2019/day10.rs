@@ -1,9 +1,12 @@
-use std::f64::consts::TAU;
+use std::{f64::consts::TAU, fmt::Display};
 
 use fxhash::{FxHashMap, FxHashSet};
 
-use crate::util::{Pair, lowest_common_factor};
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{Pair, grid::Grid, math::gcd, parse::ParseError, solution::Solution};
 
+#[cfg(test)]
 const SAMPLE_1: &str = "
 .#..#
 .....
@@ -11,6 +14,7 @@ const SAMPLE_1: &str = "
 ....#
 ...##";
 
+#[cfg(test)]
 const SAMPLE_2: &str = "
 ......#.#.
 #..#.#....
@@ -23,6 +27,7 @@ const SAMPLE_2: &str = "
 ##...#..#.
 .#....####";
 
+#[cfg(test)]
 const SAMPLE_3: &str = "
 .#..##.###...#######
 ##.############..##.
@@ -45,30 +50,31 @@ const SAMPLE_3: &str = "
 #.#.#.#####.####.###
 ###.##.####.##.#..##";
 
-const INPUT: &str = include_str!("./inputs/day10.txt");
-
-fn parse_asteroids(input: &str) -> Vec<Pair> {
-    let mut asteroids = Vec::new();
-    for (y, line) in input.trim().split('\n').enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                asteroids.push(Pair(x as i64, y as i64));
-            }
-        }
+fn parse_asteroids(input: &str) -> Result<Vec<Pair>, ParseError> {
+    let map = Grid::parse(input, "`.` or `#`", |ch| match ch {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let asteroids: Vec<Pair> = map
+        .iter()
+        .filter(|&(_, &asteroid)| asteroid)
+        .map(|(pos, _)| pos)
+        .collect();
+    // The station has to be able to see something
+    if asteroids.len() < 2 {
+        return Err(ParseError::end_of_input(input, "at least two asteroids"));
     }
-    asteroids
+    Ok(asteroids)
 }
 
-fn check_first_sightlines(
-    factor_cache: &FactorCache,
-    asteroids: &Vec<Pair>,
-) -> FxHashSet<(Pair, Pair)> {
+fn check_first_sightlines(asteroids: &[Pair]) -> FxHashSet<(Pair, Pair)> {
     let asteroid_cache = asteroids.iter().copied().collect::<FxHashSet<Pair>>();
     let mut sightlines = FxHashSet::default();
 
     for (i, point) in asteroids.iter().enumerate() {
         for other in &asteroids[i + 1..] {
-            let seen = sightline_first(&factor_cache, &asteroid_cache, point, other);
+            let seen = sightline_first(&asteroid_cache, point, other);
 
             sightlines.insert((seen, *point));
             sightlines.insert((*point, seen));
@@ -84,19 +90,14 @@ fn clockwise_rads(x: i64, y: i64) -> f64 {
     if rads >= 0.0 { rads } else { rads + TAU }
 }
 
-fn nth_rotational_sightline(
-    from: &Pair,
-    factor_cache: &FactorCache,
-    asteroids: &Vec<Pair>,
-    n: usize,
-) -> Option<Pair> {
+fn nth_rotational_sightline(from: &Pair, asteroids: &[Pair], n: usize) -> Option<Pair> {
     let asteroid_cache = asteroids.iter().copied().collect::<FxHashSet<Pair>>();
     let mut visited = FxHashSet::<Pair>::default();
     let mut sorted_sightlines = Vec::<(f64, Pair)>::new();
 
     for other in asteroids.iter() {
-        if from != other && !visited.contains(&other) {
-            let seen_list = sightline_vec(&factor_cache, &asteroid_cache, from, other);
+        if from != other && !visited.contains(other) {
+            let seen_list = sightline_vec(&asteroid_cache, from, other);
             let diff_x = other.0 - from.0;
             let diff_y = other.1 - from.1;
             // All items in the seen list would have the exact same angle, so we only need to calculate it once
@@ -114,25 +115,23 @@ fn nth_rotational_sightline(
     }
 
     sorted_sightlines.sort_by(|this, other| this.0.total_cmp(&other.0));
-    sorted_sightlines.iter().nth(n).map(|(_, pair)| *pair)
+    sorted_sightlines.get(n).map(|(_, pair)| *pair)
 }
 
 fn sightline_first(
-    factor_cache: &FactorCache,
     asteroids: &FxHashSet<Pair>,
     &Pair(ax, ay): &Pair,
     &Pair(bx, by): &Pair,
 ) -> Pair {
     let x_diff = bx - ax;
     let y_diff = by - ay;
-    if let Some(factor) = factor_cache.get(&(x_diff.abs(), y_diff.abs())) {
-        let mut cur_coord = Pair(ax, ay);
-        let step = Pair(x_diff / factor, y_diff / factor);
-        for _ in 0..*factor - 1 {
-            cur_coord = cur_coord + step;
-            if asteroids.contains(&cur_coord) {
-                return cur_coord;
-            }
+    let factor = gcd(x_diff, y_diff);
+    let mut cur_coord = Pair(ax, ay);
+    let step = Pair(x_diff / factor, y_diff / factor);
+    for _ in 0..factor - 1 {
+        cur_coord += step;
+        if asteroids.contains(&cur_coord) {
+            return cur_coord;
         }
     }
 
@@ -140,7 +139,6 @@ fn sightline_first(
 }
 
 fn sightline_vec(
-    factor_cache: &FactorCache,
     asteroids: &FxHashSet<Pair>,
     &Pair(ax, ay): &Pair,
     &Pair(bx, by): &Pair,
@@ -148,14 +146,12 @@ fn sightline_vec(
     let x_diff = bx - ax;
     let y_diff = by - ay;
     let mut sightline_list = Vec::new();
-    let factor = factor_cache
-        .get(&(x_diff.abs(), y_diff.abs()))
-        .unwrap_or(&1);
+    let factor = gcd(x_diff, y_diff);
 
     let mut cur_coord = Pair(ax, ay);
     let step = Pair(x_diff / factor, y_diff / factor);
     loop {
-        cur_coord = cur_coord + step;
+        cur_coord += step;
         if cur_coord.0 < 0 || cur_coord.0 > 36 || cur_coord.1 < 0 || cur_coord.1 > 36 {
             break;
         }
@@ -183,60 +179,65 @@ fn count_sightlines(pairs: &FxHashSet<(Pair, Pair)>) -> (u64, Pair) {
         })
 }
 
-type FactorCache = FxHashMap<(i64, i64), i64>;
+pub struct Day10;
 
-fn pregen_lowest_common_factors() -> FactorCache {
-    let mut map = FxHashMap::default();
-    for x in 0..40 {
-        for y in x..40 {
-            if let Some(factor) = lowest_common_factor(x, y) {
-                map.insert((x, y), factor);
-                map.insert((y, x), factor);
-            }
-        }
+impl Solution for Day10 {
+    type Input = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_asteroids(input)
+    }
+
+    fn part_1(asteroids: &Self::Input) -> impl Display {
+        count_sightlines(&check_first_sightlines(asteroids)).0
+    }
+
+    fn part_2(asteroids: &Self::Input) -> impl Display {
+        let (_, station_coordinates) = count_sightlines(&check_first_sightlines(asteroids));
+        let Pair(x, y) = nth_rotational_sightline(&station_coordinates, asteroids, 199)
+            .expect("there are fewer than 200 asteroids to vaporize");
+        x * 100 + y
     }
-    map
 }
 
 #[test]
 fn samples() {
-    let factor_cache = pregen_lowest_common_factors();
-
     assert_eq!(
         8,
-        count_sightlines(&check_first_sightlines(
-            &factor_cache,
-            &parse_asteroids(SAMPLE_1)
-        ))
-        .0
+        count_sightlines(&check_first_sightlines(&parse_asteroids(SAMPLE_1).unwrap())).0
     );
     assert_eq!(
         33,
-        count_sightlines(&check_first_sightlines(
-            &factor_cache,
-            &parse_asteroids(SAMPLE_2)
-        ))
-        .0
+        count_sightlines(&check_first_sightlines(&parse_asteroids(SAMPLE_2).unwrap())).0
     );
 
-    let asteroids_3 = parse_asteroids(SAMPLE_3);
-    let (sightlines, station_coordinates) =
-        count_sightlines(&check_first_sightlines(&factor_cache, &asteroids_3));
+    let asteroids_3 = parse_asteroids(SAMPLE_3).unwrap();
+    let (sightlines, station_coordinates) = count_sightlines(&check_first_sightlines(&asteroids_3));
     assert_eq!(210, sightlines);
-    let two_hundreth =
-        nth_rotational_sightline(&station_coordinates, &factor_cache, &asteroids_3, 199);
+    let two_hundreth = nth_rotational_sightline(&station_coordinates, &asteroids_3, 199);
     assert_eq!(Some(802), two_hundreth.map(|Pair(x, y)| x * 100 + y));
 }
 
 #[test]
 fn solutions() {
-    let factor_cache = pregen_lowest_common_factors();
-
-    let asteroids = parse_asteroids(INPUT);
+    let Some(input) = test_input(2019, 10) else {
+        return;
+    };
+    let asteroids = parse_asteroids(&input).unwrap();
     let (max_sightlines, station_coordinates) =
-        count_sightlines(&check_first_sightlines(&factor_cache, &asteroids));
+        count_sightlines(&check_first_sightlines(&asteroids));
     assert_eq!(276, max_sightlines);
-    let two_hundreth =
-        nth_rotational_sightline(&station_coordinates, &factor_cache, &asteroids, 199);
+    let two_hundreth = nth_rotational_sightline(&station_coordinates, &asteroids, 199);
     assert_eq!(Some(1321), two_hundreth.map(|Pair(x, y)| x * 100 + y));
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_asteroids(".#..#\n..o..").err().unwrap();
+    assert_eq!((2, 3), (err.line, err.column));
+    let err = parse_asteroids(".#..#\n...").err().unwrap();
+    assert_eq!((2, 4), (err.line, err.column));
+    let err = parse_asteroids("..#..").err().unwrap();
+    assert_eq!("at least two asteroids", err.expected);
+    assert!(parse_asteroids("\n").is_err());
+}
@@ -1,6 +1,9 @@
-use crate::aoc_2019::intcode_cpu::{IntCPU, Program};
+use std::fmt::Display;
 
-const INPUT: &str = include_str!("./inputs/day2.txt");
+use crate::aoc_2019::intcode_cpu::{IntCPU, Program};
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
 
 fn find_noun_and_verb(program: &Program, search_value: i128) -> i128 {
     for noun in 0..100 {
@@ -16,9 +19,33 @@ fn find_noun_and_verb(program: &Program, search_value: i128) -> i128 {
     -1
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        IntCPU::parse_program(input)
+    }
+
+    fn part_1(program: &Self::Input) -> impl Display {
+        let mut cpu = IntCPU::new(program);
+        cpu.init(12, 2);
+        cpu.exec();
+        cpu.get(0)
+    }
+
+    fn part_2(program: &Self::Input) -> impl Display {
+        find_noun_and_verb(program, 19690720)
+    }
+}
+
 #[test]
 fn solutions() {
-    let program = IntCPU::parse_program(INPUT);
+    let Some(input) = test_input(2019, 2) else {
+        return;
+    };
+    let program = IntCPU::parse_program(&input).unwrap();
     let mut cpu = IntCPU::new(&program);
     cpu.init(12, 2);
     cpu.exec();
@@ -1,19 +1,30 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Direction, Pair,
+    parse::{ParseError, next_token_or_end},
+    solution::Solution,
+};
+
+#[cfg(test)]
 const SAMPLE_1: &str = "
 R8,U5,L5,D3
 U7,R6,D4,L4";
 
+#[cfg(test)]
 const SAMPLE_2: &str = "
 R75,D30,R83,U83,L12,D49,R71,U7,L72
 U62,R66,U55,R34,D71,R55,D58,R83";
 
+#[cfg(test)]
 const SAMPLE_3: &str = "
 R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
 U98,R91,D20,R16,D67,R40,U7,R15,U6,R7";
 
-const INPUT: &str = include_str!("./inputs/day3.txt");
-
 #[derive(Logos)]
 #[logos(skip r"[,]")]
 enum Token {
@@ -22,46 +33,43 @@ enum Token {
 
     #[regex("[RLUD][0-9]+", |lex| {
         let slice = lex.slice();
-        let value: i64 = slice[1..].parse().unwrap();
-        match slice.chars().nth(0).unwrap() {
-            'R' => Dir::Right(value),
-            'L' => Dir::Left(value),
-            'U' => Dir::Up(value),
-            'D' => Dir::Down(value),
-            _ => unreachable!()
-        }
+        let dir = Direction::from_char(slice.chars().next()?)?;
+        Some((dir, slice[1..].parse().ok()?))
     })]
-    Wire(Dir),
+    Wire(Segment),
 }
 
-#[derive(Debug)]
-enum Dir {
-    Right(i64),
-    Left(i64),
-    Up(i64),
-    Down(i64),
-}
+type Segment = (Direction, i64);
 
-impl Dir {
-    fn is_vertical(&self) -> bool {
-        match self {
-            Self::Up(_) | Self::Down(_) => true,
-            _ => false,
-        }
-    }
-}
+// (vertical lines, horizontal lines)
+type WireLines = (Vec<Line>, Vec<Line>);
 
-fn parse_wires(input: &str) -> ((Vec<Line>, Vec<Line>), (Vec<Line>, Vec<Line>)) {
+fn parse_wires(input: &str) -> Result<(WireLines, WireLines), ParseError> {
     let mut first_wire = Vec::new();
     let mut second_wire = Vec::new();
     let mut on_first = true;
-    let mut tokens = Token::lexer(input.trim());
-    while let Some(Ok(token)) = tokens.next() {
+    let mut finished = false;
+    let mut tokens = Token::lexer(input);
+    while let Some(token) = next_token_or_end(&mut tokens, "a wire segment like `R8`")? {
         match token {
+            // Blank lines before the first wire are skipped rather than trimmed, so error
+            // positions still line up with the input
             Token::Newline => {
-                on_first = false;
+                if !first_wire.is_empty() {
+                    on_first = false;
+                }
+                if !second_wire.is_empty() {
+                    finished = true;
+                }
             }
             Token::Wire(wire) => {
+                if finished {
+                    return Err(ParseError::new(
+                        input,
+                        tokens.span(),
+                        "the end of the input after two wires",
+                    ));
+                }
                 if on_first {
                     first_wire.push(wire);
                 } else {
@@ -70,14 +78,15 @@ fn parse_wires(input: &str) -> ((Vec<Line>, Vec<Line>), (Vec<Line>, Vec<Line>))
             }
         }
     }
-    (extract_lines(first_wire), extract_lines(second_wire))
+    if second_wire.is_empty() {
+        return Err(ParseError::end_of_input(input, "a wire segment like `R8`"));
+    }
+    Ok((extract_lines(first_wire), extract_lines(second_wire)))
 }
 
-type Coord = (i64, i64);
-
 #[derive(Debug)]
 struct Line {
-    start: Coord,
+    start: Pair,
     total_len: i64,
 
     // Which axes these are depends on the orientation
@@ -87,48 +96,37 @@ struct Line {
 
 impl Line {
     // Only works if self is vertical and other is horizontal
-    fn intersects(&self, other: &Line) -> Option<Coord> {
+    fn intersects(&self, other: &Line) -> Option<Pair> {
         if self.start.0 >= other.min
             && self.start.0 <= other.max
             && other.start.1 >= self.min
             && other.start.1 <= self.max
         {
-            Some((self.start.0, other.start.1))
+            Some(Pair(self.start.0, other.start.1))
         } else {
             None
         }
     }
 }
 
-fn get_next_coord(&(x, y): &Coord, dir: Dir) -> Coord {
-    match dir {
-        Dir::Right(dx) => (x + dx, y),
-        Dir::Left(dx) => (x - dx, y),
-        Dir::Up(dy) => (x, y + dy),
-        Dir::Down(dy) => (x, y - dy),
-    }
-}
-
-fn extract_lines(schematics: Vec<Dir>) -> (Vec<Line>, Vec<Line>) {
+fn extract_lines(schematics: Vec<Segment>) -> (Vec<Line>, Vec<Line>) {
     let mut vertical_lines = Vec::new();
     let mut horizontal_lines = Vec::new();
-    let mut last_coord: Coord = (0, 0);
+    let mut last_coord = Pair::ORIGIN;
     let mut dist_so_far: i64 = 0;
-    for dir in schematics {
-        let is_vertical = dir.is_vertical();
-        let next_coord = get_next_coord(&last_coord, dir);
+    for (dir, dist) in schematics {
+        let next_coord = last_coord + dir.step() * dist;
         let min_x = last_coord.0.min(next_coord.0);
         let min_y = last_coord.1.min(next_coord.1);
         let max_x = last_coord.0.max(next_coord.0);
         let max_y = last_coord.1.max(next_coord.1);
-        if is_vertical {
+        if dir.is_vertical() {
             vertical_lines.push(Line {
                 start: last_coord,
                 total_len: dist_so_far,
                 min: min_y,
                 max: max_y,
             });
-            dist_so_far += (last_coord.1 - next_coord.1).abs();
         } else {
             horizontal_lines.push(Line {
                 start: last_coord,
@@ -136,9 +134,9 @@ fn extract_lines(schematics: Vec<Dir>) -> (Vec<Line>, Vec<Line>) {
                 min: min_x,
                 max: max_x,
             });
-            dist_so_far += (last_coord.0 - next_coord.0).abs();
         }
 
+        dist_so_far += dist;
         last_coord = next_coord;
     }
     (vertical_lines, horizontal_lines)
@@ -149,34 +147,68 @@ fn intersections(verticals: Vec<Line>, horizontals: Vec<Line>) -> (i64, i64) {
     let mut intersection_with_least_delay = i64::MAX;
     for v in &verticals {
         for h in &horizontals {
-            if let Some((x, y)) = v.intersects(h) {
-                if x != 0 || y != 0 {
-                    closest_intersection = closest_intersection.min(x.abs() + y.abs());
-                    let delay =
-                        v.total_len + h.total_len + (v.start.1 - y).abs() + (h.start.0 - x).abs();
-                    intersection_with_least_delay = intersection_with_least_delay.min(delay);
-                }
+            if let Some(crossing) = v.intersects(h)
+                && crossing != Pair::ORIGIN
+            {
+                closest_intersection = closest_intersection.min(crossing.manhattan(Pair::ORIGIN));
+                let delay = v.total_len
+                    + h.total_len
+                    + v.start.manhattan(crossing)
+                    + h.start.manhattan(crossing);
+                intersection_with_least_delay = intersection_with_least_delay.min(delay);
             }
         }
     }
     (closest_intersection, intersection_with_least_delay)
 }
 
+// Returns the closest intersection and the one with the least combined delay
+fn closest_intersections(input: &str) -> Result<(i64, i64), ParseError> {
+    let ((a_v, a_h), (b_v, b_h)) = parse_wires(input)?;
+    let (close_x, delay_x) = intersections(a_v, b_h);
+    let (close_y, delay_y) = intersections(b_v, a_h);
+    if close_x.min(close_y) == i64::MAX {
+        return Err(ParseError::end_of_input(
+            input,
+            "wires that cross somewhere other than the origin",
+        ));
+    }
+    Ok((close_x.min(close_y), delay_x.min(delay_y)))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = (i64, i64);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        closest_intersections(input)
+    }
+
+    fn part_1(&(closest, _): &Self::Input) -> impl Display {
+        closest
+    }
+
+    fn part_2(&(_, least_delay): &Self::Input) -> impl Display {
+        least_delay
+    }
+}
+
 #[test]
 fn samples() {
-    let ((a_v, a_h), (b_v, b_h)) = parse_wires(SAMPLE_1);
+    let ((a_v, a_h), (b_v, b_h)) = parse_wires(SAMPLE_1).unwrap();
     let (close_x, delay_x) = intersections(a_v, b_h);
     let (close_y, delay_y) = intersections(b_v, a_h);
     assert_eq!(6, close_x.min(close_y));
     assert_eq!(30, delay_x.min(delay_y));
 
-    let ((a_v, a_h), (b_v, b_h)) = parse_wires(SAMPLE_2);
+    let ((a_v, a_h), (b_v, b_h)) = parse_wires(SAMPLE_2).unwrap();
     let (close_x, delay_x) = intersections(a_v, b_h);
     let (close_y, delay_y) = intersections(b_v, a_h);
     assert_eq!(159, close_x.min(close_y));
     assert_eq!(610, delay_x.min(delay_y));
 
-    let ((a_v, a_h), (b_v, b_h)) = parse_wires(SAMPLE_3);
+    let ((a_v, a_h), (b_v, b_h)) = parse_wires(SAMPLE_3).unwrap();
     let (close_x, delay_x) = intersections(a_v, b_h);
     let (close_y, delay_y) = intersections(b_v, a_h);
     assert_eq!(135, close_x.min(close_y));
@@ -185,9 +217,25 @@ fn samples() {
 
 #[test]
 fn solutions() {
-    let ((a_v, a_h), (b_v, b_h)) = parse_wires(INPUT);
+    let Some(input) = test_input(2019, 3) else {
+        return;
+    };
+    let ((a_v, a_h), (b_v, b_h)) = parse_wires(&input).unwrap();
     let (close_x, delay_x) = intersections(a_v, b_h);
     let (close_y, delay_y) = intersections(b_v, a_h);
     assert_eq!(865, close_x.min(close_y));
     assert_eq!(35038, delay_x.min(delay_y));
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_wires("R8,U5\n").err().unwrap();
+    assert_eq!("a wire segment like `R8`", err.expected);
+    let err = parse_wires("R8,U5\nU7,L6\nD1").err().unwrap();
+    assert_eq!((3, 1), (err.line, err.column));
+    let err = closest_intersections("R8\nL8").err().unwrap();
+    assert_eq!(
+        "wires that cross somewhere other than the origin",
+        err.expected
+    );
+}
@@ -1,12 +1,20 @@
-use std::ops::Range;
+use std::{fmt::Display, ops::Range};
 
-const INPUT: &str = include_str!("./inputs/day4.txt");
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
 
-fn parse_input(input: &str) -> Range<u64> {
-    let mut parts = input.trim().split("-");
-    let start = parts.next().unwrap().parse().unwrap();
-    let end = parts.next().unwrap().parse().unwrap();
-    start..end
+fn parse_input(input: &str) -> Result<Range<u64>, ParseError> {
+    let range = input.trim();
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(input, range, "a range like `100000-999999`"))?;
+    let start = parse_at(input, start, "the start of the range")?;
+    let end = parse_at(input, end, "the end of the range")?;
+    Ok(start..end)
 }
 
 fn dits(num: u64) -> (u64, u64, u64, u64, u64, u64) {
@@ -39,11 +47,39 @@ fn is_real_valid_password(num: u64) -> bool {
         || (x5 == x6 && x4 != x5)
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Range<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(passwords: &Self::Input) -> impl Display {
+        passwords
+            .clone()
+            .filter(|&password| is_valid_password(password))
+            .count()
+    }
+
+    fn part_2(passwords: &Self::Input) -> impl Display {
+        passwords
+            .clone()
+            .filter(|&password| is_valid_password(password) && is_real_valid_password(password))
+            .count()
+    }
+}
+
 #[test]
 fn solutions() {
     let mut part_1_passwords = 0;
     let mut part_2_passwords = 0;
-    for password in parse_input(INPUT) {
+
+    let Some(input) = test_input(2019, 4) else {
+        return;
+    };
+    for password in parse_input(&input).unwrap() {
         if is_valid_password(password) {
             part_1_passwords += 1;
             if is_real_valid_password(password) {
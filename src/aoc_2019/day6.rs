@@ -1,5 +1,12 @@
+use std::fmt::Display;
+
 use fxhash::FxHashMap;
 
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
+
+#[cfg(test)]
 const SAMPLE_1: &str = "
 COM)B
 B)C
@@ -13,6 +20,7 @@ E)J
 J)K
 K)L";
 
+#[cfg(test)]
 const SAMPLE_2: &str = "
 COM)B
 B)C
@@ -28,22 +36,18 @@ K)L
 K)YOU
 I)SAN";
 
-const INPUT: &str = include_str!("./inputs/day6.txt");
-
-type OrbitMap = FxHashMap<String, String>;
+pub type OrbitMap = FxHashMap<String, String>;
 type TransferCache = FxHashMap<String, u64>;
 
-fn parse_orbits(input: &str) -> OrbitMap {
-    input
-        .trim()
-        .split('\n')
-        .fold(FxHashMap::default(), |mut acc, line| {
-            let mut parts = line.split(')');
-            let parent = parts.next().unwrap().to_string();
-            let child = parts.next().unwrap().to_string();
-            acc.insert(child, parent);
-            acc
-        })
+fn parse_orbits(input: &str) -> Result<OrbitMap, ParseError> {
+    let mut orbits = FxHashMap::default();
+    for line in input.trim().split('\n') {
+        let (parent, child) = line
+            .split_once(')')
+            .ok_or_else(|| ParseError::at(input, line, "an orbit like `COM)B`"))?;
+        orbits.insert(child.to_string(), parent.to_string());
+    }
+    Ok(orbits)
 }
 
 fn calculate_com_orbit(body: &str, orbits: &OrbitMap, cache: &mut TransferCache) -> u64 {
@@ -84,7 +88,7 @@ fn shortest_transfer(orbits: &OrbitMap, cache: &TransferCache) -> u64 {
     };
     let mut equal_orbit = longer_orbit;
     for _ in 0..diff {
-        equal_orbit = &orbits.get(equal_orbit).unwrap();
+        equal_orbit = orbits.get(equal_orbit).unwrap();
     }
     let mut counter = diff;
     while shorter_orbit != equal_orbit {
@@ -96,20 +100,42 @@ fn shortest_transfer(orbits: &OrbitMap, cache: &TransferCache) -> u64 {
     counter - 2
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = OrbitMap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_orbits(input)
+    }
+
+    fn part_1(orbits: &Self::Input) -> impl Display {
+        tally_orbits(orbits).0
+    }
+
+    fn part_2(orbits: &Self::Input) -> impl Display {
+        let (_, cache) = tally_orbits(orbits);
+        shortest_transfer(orbits, &cache)
+    }
+}
+
 #[test]
 fn samples() {
-    let orbits = parse_orbits(SAMPLE_1);
+    let orbits = parse_orbits(SAMPLE_1).unwrap();
     let (count, _) = tally_orbits(&orbits);
     assert_eq!(42, count);
 
-    let orbits = parse_orbits(SAMPLE_2);
+    let orbits = parse_orbits(SAMPLE_2).unwrap();
     let (_, cache) = tally_orbits(&orbits);
     assert_eq!(4, shortest_transfer(&orbits, &cache));
 }
 
 #[test]
 fn solutions() {
-    let orbits = parse_orbits(INPUT);
+    let Some(input) = test_input(2019, 6) else {
+        return;
+    };
+    let orbits = parse_orbits(&input).unwrap();
     let (count, cache) = tally_orbits(&orbits);
     assert_eq!(162439, count);
     assert_eq!(367, shortest_transfer(&orbits, &cache));
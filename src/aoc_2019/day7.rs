@@ -1,14 +1,23 @@
+use std::fmt::Display;
+
 use itertools::Itertools;
 
 use crate::aoc_2019::intcode_cpu::{IntCPU, Program};
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
 
+#[cfg(test)]
 const SAMPLE_1: &str = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0";
+#[cfg(test)]
 const SAMPLE_2: &str = "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0";
+#[cfg(test)]
 const SAMPLE_3: &str = "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0";
+#[cfg(test)]
 const SAMPLE_4: &str =
     "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+#[cfg(test)]
 const SAMPLE_5: &str = "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10";
-const INPUT: &str = include_str!("./inputs/day7.txt");
 
 fn test_amplifier(program: &Program, config: Vec<i128>) -> i128 {
     let mut last_output = 0;
@@ -22,7 +31,7 @@ fn test_amplifier(program: &Program, config: Vec<i128>) -> i128 {
     last_output
 }
 
-fn test_amplifier_with_feedback(program: &Program, config: &Vec<i128>) -> i128 {
+fn test_amplifier_with_feedback(program: &Program, config: &[i128]) -> i128 {
     let mut amplifiers: Vec<IntCPU> = config
         .iter()
         .map(|&x| {
@@ -66,34 +75,55 @@ fn find_largest_amplification_with_feedback(program: &Program) -> i128 {
     largest_output
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        IntCPU::parse_program(input)
+    }
+
+    fn part_1(program: &Self::Input) -> impl Display {
+        find_largest_amplification(program)
+    }
+
+    fn part_2(program: &Self::Input) -> impl Display {
+        find_largest_amplification_with_feedback(program)
+    }
+}
+
 #[test]
 fn samples() {
     assert_eq!(
         43210,
-        find_largest_amplification(&mut IntCPU::parse_program(SAMPLE_1))
+        find_largest_amplification(&IntCPU::parse_program(SAMPLE_1).unwrap())
     );
     assert_eq!(
         54321,
-        find_largest_amplification(&mut IntCPU::parse_program(SAMPLE_2))
+        find_largest_amplification(&IntCPU::parse_program(SAMPLE_2).unwrap())
     );
     assert_eq!(
         65210,
-        find_largest_amplification(&mut IntCPU::parse_program(SAMPLE_3))
+        find_largest_amplification(&IntCPU::parse_program(SAMPLE_3).unwrap())
     );
 
     assert_eq!(
         139629729,
-        find_largest_amplification_with_feedback(&mut IntCPU::parse_program(SAMPLE_4))
+        find_largest_amplification_with_feedback(&IntCPU::parse_program(SAMPLE_4).unwrap())
     );
     assert_eq!(
         18216,
-        find_largest_amplification_with_feedback(&mut IntCPU::parse_program(SAMPLE_5))
+        find_largest_amplification_with_feedback(&IntCPU::parse_program(SAMPLE_5).unwrap())
     );
 }
 
 #[test]
 fn solutions() {
-    let program = IntCPU::parse_program(INPUT);
+    let Some(input) = test_input(2019, 7) else {
+        return;
+    };
+    let program = IntCPU::parse_program(&input).unwrap();
     assert_eq!(46248, find_largest_amplification(&program));
     assert_eq!(54163586, find_largest_amplification_with_feedback(&program));
 }
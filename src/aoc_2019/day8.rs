@@ -1,34 +1,44 @@
-const INPUT: &str = include_str!("./inputs/day8.txt");
+use std::fmt::Display;
 
-struct Layer {
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{Pair, grid::Grid, ocr::read_letters, parse::ParseError, solution::Solution};
+
+pub struct Layer {
     pixels: Vec<u8>,
     zero_count: u64,
 }
-type Image = Vec<Layer>;
+pub type Image = Vec<Layer>;
 
-fn parse_layers(input: &str, width: usize, height: usize) -> Image {
-    let input = input.trim();
+fn parse_layers(source: &str, width: usize, height: usize) -> Result<Image, ParseError> {
+    let input = source.trim();
     let img_size = width * height;
-    let layer_count = input.len() / img_size;
-    let mut layers = Vec::with_capacity(layer_count);
-    for cur_layer_idx in 0..layer_count {
-        let mut layer = Vec::new();
-        let start = img_size * cur_layer_idx;
-        let end = start + img_size;
-        let mut zero_count = 0;
-        for ch in input[start..end].chars() {
-            let cur_digit = ch.to_digit(10).unwrap() as u8;
-            if cur_digit == 0 {
-                zero_count += 1;
-            }
-            layer.push(cur_digit);
-        }
-        layers.push(Layer {
-            pixels: layer,
-            zero_count,
-        });
+    if input.is_empty() {
+        return Err(ParseError::end_of_input(source, "a layer of digits"));
     }
-    layers
+    if let Some((idx, ch)) = input.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+        let slice = &input[idx..idx + ch.len_utf8()];
+        return Err(ParseError::at(source, slice, "a digit"));
+    }
+    // A partial layer at the end means the image got cut off
+    if !input.len().is_multiple_of(img_size) {
+        return Err(ParseError::end_of_input(
+            source,
+            "the rest of the last layer",
+        ));
+    }
+
+    // Every character is an ASCII digit now, so each byte is one pixel
+    let layers = input
+        .as_bytes()
+        .chunks_exact(img_size)
+        .map(|chunk| {
+            let pixels: Vec<u8> = chunk.iter().map(|byte| byte - b'0').collect();
+            let zero_count = pixels.iter().filter(|&&pixel| pixel == 0).count() as u64;
+            Layer { pixels, zero_count }
+        })
+        .collect();
+    Ok(layers)
 }
 
 fn checksum(layers: &Image) -> u64 {
@@ -60,34 +70,99 @@ fn checksum(layers: &Image) -> u64 {
     ones * twos
 }
 
-fn _print_image(layers: &Image, width: usize, height: usize) {
-    for y in 0..height {
-        let y_offset = y * width;
-        for x in 0..width {
-            let idx = y_offset + x;
-            let mut pixel = "  ";
-            for l in layers {
-                match l.pixels[idx] {
-                    0 => {
-                        break;
-                    }
-                    1 => {
-                        pixel = "##";
-                        break;
-                    }
-                    _ => {}
-                }
-            }
-            print!("{pixel}");
-        }
-        println!("");
+// The first layer that isn't transparent decides each pixel
+fn decode_image(layers: &Image, width: usize, height: usize) -> Grid<bool> {
+    Grid::from_fn(width, height, |Pair(x, y)| {
+        let idx = y as usize * width + x as usize;
+        layers
+            .iter()
+            .map(|layer| layer.pixels[idx])
+            .find(|&pixel| pixel != 2)
+            == Some(1)
+    })
+}
+
+fn render_image(pixels: &Grid<bool>) -> String {
+    pixels.render(|&pixel| if pixel { "##" } else { "  " })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Image;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_layers(input, 25, 6)
+    }
+
+    fn part_1(layers: &Self::Input) -> impl Display {
+        checksum(layers)
+    }
+
+    // Falls back to drawing the image if the OCR doesn't know one of the letters
+    fn part_2(layers: &Self::Input) -> impl Display {
+        let pixels = decode_image(layers, 25, 6);
+        read_letters(&pixels).unwrap_or_else(|| format!("\n{}", render_image(&pixels)))
     }
 }
 
 #[test]
 fn solutions() {
-    let layers = parse_layers(INPUT, 25, 6);
+    let Some(input) = test_input(2019, 8) else {
+        return;
+    };
+    let layers = parse_layers(&input, 25, 6).unwrap();
     assert_eq!(2210, checksum(&layers));
-    // Prints image to console, not trivial to write assertion for
-    // print_image(&layers, 25, 6);
+
+    let pixels = decode_image(&layers, 25, 6);
+    let message = read_letters(&pixels);
+    assert_eq!(Some(5), message.map(|letters| letters.len()));
+}
+
+#[test]
+fn decoding() {
+    let picture = "\
+#..#.####.#....#.....##..
+#..#.#....#....#....#..#.
+####.###..#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.####.####.####..##..";
+    let pixels: Vec<char> = picture
+        .lines()
+        .flat_map(|line| line.chars())
+        .map(|ch| if ch == '#' { '1' } else { '0' })
+        .collect();
+    // The top layer only shows every other pixel, and the layer underneath fills in the rest
+    // above a layer of noise that should never show through
+    let top: String = pixels
+        .iter()
+        .enumerate()
+        .map(|(idx, &pixel)| if idx % 2 == 0 { pixel } else { '2' })
+        .collect();
+    let middle: String = pixels
+        .iter()
+        .enumerate()
+        .map(|(idx, &pixel)| if idx % 2 == 0 { '2' } else { pixel })
+        .collect();
+    let noise = "1".repeat(pixels.len());
+    let layers = parse_layers(&format!("{top}{middle}{noise}"), 25, 6).unwrap();
+    let image = decode_image(&layers, 25, 6);
+    assert_eq!(Some(String::from("HELLO")), read_letters(&image));
+
+    let layers = parse_layers("0222112222120000", 2, 2).unwrap();
+    let image = decode_image(&layers, 2, 2);
+    assert_eq!(
+        vec![false, true, true, false],
+        image.values().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_layers("022211222212000", 2, 2).err().unwrap();
+    assert_eq!("the rest of the last layer", err.expected);
+    assert!(parse_layers("\n", 2, 2).is_err());
+    let err = parse_layers("0222x122", 2, 2).err().unwrap();
+    assert_eq!((1, 5), (err.line, err.column));
 }
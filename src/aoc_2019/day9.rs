@@ -1,10 +1,41 @@
-use crate::aoc_2019::intcode_cpu::IntCPU;
+use std::fmt::Display;
 
-const INPUT: &str = include_str!("./inputs/day9.txt");
+use crate::aoc_2019::intcode_cpu::{IntCPU, Program};
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
+
+fn run_with_input(program: &Program, value: i128) -> i128 {
+    let mut cpu = IntCPU::new(program);
+    cpu.push_input(value);
+    cpu.exec();
+    cpu.last_output().unwrap()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        IntCPU::parse_program(input)
+    }
+
+    fn part_1(program: &Self::Input) -> impl Display {
+        run_with_input(program, 1)
+    }
+
+    fn part_2(program: &Self::Input) -> impl Display {
+        run_with_input(program, 2)
+    }
+}
 
 #[test]
 fn solutions() {
-    let program = IntCPU::parse_program(INPUT);
+    let Some(input) = test_input(2019, 9) else {
+        return;
+    };
+    let program = IntCPU::parse_program(&input).unwrap();
 
     let mut cpu = IntCPU::new(&program);
     cpu.push_input(1);
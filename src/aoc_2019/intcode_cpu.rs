@@ -2,13 +2,15 @@ use std::collections::VecDeque;
 
 use fxhash::FxHashMap;
 
+use crate::util::parse::{ParseError, parse_at};
+
 pub type Program = Vec<i128>;
 
-pub fn parse_program(input: &str) -> Program {
+pub fn parse_program(input: &str) -> Result<Program, ParseError> {
     input
         .trim()
         .split(",")
-        .map(|x| x.parse().unwrap())
+        .map(|x| parse_at(input, x, "an integer"))
         .collect()
 }
 
@@ -52,17 +54,14 @@ impl From<i128> for ParamMode {
 }
 
 impl IntCPU {
-    pub fn parse_program(input: &str) -> Program {
-        input
-            .trim()
-            .split(",")
-            .map(|x| x.parse().unwrap())
-            .collect()
+    pub fn parse_program(input: &str) -> Result<Program, ParseError> {
+        parse_program(input)
     }
 
-    pub fn from_str(input: &str) -> Self {
-        let program = Self::parse_program(input);
-        Self::new(&program)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(input: &str) -> Result<Self, ParseError> {
+        let program = Self::parse_program(input)?;
+        Ok(Self::new(&program))
     }
 
     pub fn new(program: &Program) -> Self {
@@ -98,6 +97,7 @@ impl IntCPU {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<i128> {
         loop {
             if self.step(true) {
@@ -111,7 +111,7 @@ impl IntCPU {
     }
 
     pub fn outputs(&self) -> Vec<i128> {
-        self.output_queue.iter().map(|x| *x).collect()
+        self.output_queue.iter().copied().collect()
     }
 
     fn step(&mut self, pause_on_output: bool) -> bool {
@@ -267,30 +267,31 @@ impl IntCPU {
 
 #[test]
 fn day_2_samples() {
-    let mut cpu = IntCPU::from_str("1,9,10,3,2,3,11,0,99,30,40,50");
+    let mut cpu = IntCPU::from_str("1,9,10,3,2,3,11,0,99,30,40,50").unwrap();
     cpu.exec();
     assert_eq!(3500, cpu.get(0));
 
-    let mut cpu = IntCPU::from_str("1,0,0,0,99");
+    let mut cpu = IntCPU::from_str("1,0,0,0,99").unwrap();
     cpu.exec();
     assert_eq!(2, cpu.get(0));
 
-    let mut cpu = IntCPU::from_str("2,3,0,3,99");
+    let mut cpu = IntCPU::from_str("2,3,0,3,99").unwrap();
     cpu.exec();
     assert_eq!(2, cpu.get(0));
 
-    let mut cpu = IntCPU::from_str("2,4,4,5,99,0");
+    let mut cpu = IntCPU::from_str("2,4,4,5,99,0").unwrap();
     cpu.exec();
     assert_eq!(2, cpu.get(0));
 
-    let mut cpu = IntCPU::from_str("1,1,1,4,99,5,6,0,99");
+    let mut cpu = IntCPU::from_str("1,1,1,4,99,5,6,0,99").unwrap();
     cpu.exec();
     assert_eq!(30, cpu.get(0));
 }
 
 #[test]
 fn day_9_samples() {
-    let mut cpu = IntCPU::from_str("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+    let mut cpu =
+        IntCPU::from_str("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99").unwrap();
     cpu.exec();
     assert_eq!(
         [
@@ -299,6 +300,6 @@ fn day_9_samples() {
         &cpu.outputs()[..]
     );
 
-    let mut cpu = IntCPU::from_str("1102,34915192,34915192,7,4,7,99,0");
+    let mut cpu = IntCPU::from_str("1102,34915192,34915192,7,4,7,99,0").unwrap();
     assert_eq!(Some(1_219_070_632_396_864), cpu.next());
 }
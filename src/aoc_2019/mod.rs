@@ -1,77 +1,26 @@
-#[cfg(test)]
-pub mod intcode_cpu;
-
-#[cfg(test)]
 pub mod day1;
-
-#[cfg(test)]
+pub mod day10;
 pub mod day2;
-
-#[cfg(test)]
 pub mod day3;
-
-#[cfg(test)]
 pub mod day4;
-
-#[cfg(test)]
 pub mod day5;
-
-#[cfg(test)]
 pub mod day6;
-
-#[cfg(test)]
 pub mod day7;
-
-#[cfg(test)]
 pub mod day8;
-
-#[cfg(test)]
 pub mod day9;
-
-#[cfg(test)]
-pub mod day10;
-
-// #[cfg(test)]
+pub mod intcode_cpu;
 // pub mod day11;
-
-// #[cfg(test)]
 // pub mod day12;
-
-// #[cfg(test)]
 // pub mod day13;
-
-// #[cfg(test)]
 // pub mod day14;
-
-// #[cfg(test)]
 // pub mod day15;
-
-// #[cfg(test)]
 // pub mod day16;
-
-// #[cfg(test)]
 // pub mod day17;
-
-// #[cfg(test)]
 // pub mod day18;
-
-// #[cfg(test)]
 // pub mod day19;
-
-// #[cfg(test)]
 // pub mod day20;
-
-// #[cfg(test)]
 // pub mod day21;
-
-// #[cfg(test)]
 // pub mod day22;
-
-// #[cfg(test)]
 // pub mod day23;
-
-// #[cfg(test)]
 // pub mod day24;
-
-// #[cfg(test)]
 // pub mod day25;
@@ -1,16 +1,26 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
-#[derive(Logos)]
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, tokenize},
+    solution::Solution,
+};
+
+#[derive(Logos, Clone, Copy)]
 #[logos(skip r"\n")]
-enum Token {
-    #[regex("R[0-9]+", |lex| lex.slice()[1..].parse::<isize>().unwrap())]
+pub enum Token {
+    #[regex("R[0-9]+", |lex| lex.slice()[1..].parse::<isize>().ok())]
     Right(isize),
 
-    #[regex("L[0-9]+", |lex| lex.slice()[1..].parse::<isize>().unwrap())]
+    #[regex("L[0-9]+", |lex| lex.slice()[1..].parse::<isize>().ok())]
     Left(isize),
 }
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+const SAMPLE: &str = "
 L68
 L30
 R48
@@ -23,14 +33,15 @@ R14
 L82
 ";
 
-const INPUT: &'static str = include_str!("inputs/day1.txt");
+fn parse_rotations(input: &str) -> Result<Vec<Token>, ParseError> {
+    tokenize(input, "a rotation like `R48` or `L5`")
+}
 
-fn count_zeros(input: &str) -> usize {
+fn count_zeros(rotations: &[Token]) -> usize {
     let mut count = 0;
     let mut dial: isize = 50;
 
-    let mut lex = Token::lexer(input);
-    while let Some(Ok(token)) = lex.next() {
+    for &token in rotations {
         match token {
             Token::Right(amt) => {
                 dial += amt;
@@ -49,12 +60,11 @@ fn count_zeros(input: &str) -> usize {
     count
 }
 
-fn count_clicks_at_zero(input: &str) -> isize {
+fn count_clicks_at_zero(rotations: &[Token]) -> isize {
     let mut count = 0;
     let mut dial: isize = 50;
 
-    let mut lex = Token::lexer(input);
-    while let Some(Ok(token)) = lex.next() {
+    for &token in rotations {
         match token {
             Token::Right(amt) => {
                 count += amt / 100;
@@ -85,14 +95,43 @@ fn count_clicks_at_zero(input: &str) -> isize {
     count
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Token>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rotations(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_zeros(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        count_clicks_at_zero(input)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(count_zeros(SAMPLE), 3);
-    assert_eq!(count_zeros(INPUT), 1036);
+    assert_eq!(count_zeros(&parse_rotations(SAMPLE).unwrap()), 3);
+
+    let Some(input) = test_input(2025, 1) else {
+        return;
+    };
+    assert_eq!(count_zeros(&parse_rotations(&input).unwrap()), 1036);
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(count_clicks_at_zero(SAMPLE), 6);
-    assert_eq!(count_clicks_at_zero(INPUT), 6228);
+    assert_eq!(count_clicks_at_zero(&parse_rotations(SAMPLE).unwrap()), 6);
+
+    let Some(input) = test_input(2025, 1) else {
+        return;
+    };
+    assert_eq!(
+        count_clicks_at_zero(&parse_rotations(&input).unwrap()),
+        6228
+    );
 }
@@ -1,34 +1,45 @@
+use std::fmt::Display;
+
 use itertools::Itertools;
 use logos::Logos;
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, next_token_or_end, non_empty},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
 ";
 
-const INPUT: &'static str = include_str!("./inputs/day10.txt");
-
-fn peel<'a>(s: &'a str) -> &'a str {
+fn peel(s: &str) -> &str {
     &s[1..s.len() - 1]
 }
 
-fn get_sequence(s: &str) -> Vec<u16> {
-    peel(s)
-        .split(',')
-        .map(|s| s.parse::<u16>().unwrap())
-        .collect()
+fn get_sequence(s: &str) -> Option<Vec<u16>> {
+    peel(s).split(',').map(|s| s.parse::<u16>().ok()).collect()
+}
+
+// Lights and buttons are packed into a u16, so there can't be more than 16 lights
+fn get_button(s: &str) -> Option<Vec<u16>> {
+    get_sequence(s).filter(|seq| seq.iter().all(|&light| light < 16))
 }
 
 #[derive(Logos)]
 #[logos(skip r"[\n ]")]
 enum Token {
     #[regex(r"\[[.#]+\]", |lex| {
-        peel(lex.slice()).chars().map(|ch| ch == '#').collect::<Vec<bool>>()
+        let lights = peel(lex.slice());
+        (lights.len() <= 16).then(|| lights.chars().map(|ch| ch == '#').collect::<Vec<bool>>())
     })]
     IndicatorLights(Vec<bool>),
 
-    #[regex(r"\([0-9]+(,[0-9]+)*\)", |lex| get_sequence(lex.slice()))]
+    #[regex(r"\([0-9]+(,[0-9]+)*\)", |lex| get_button(lex.slice()))]
     Button(Vec<u16>),
 
     #[regex(r"\{[0-9]+(,[0-9]+)*\}", |lex| get_sequence(lex.slice()))]
@@ -37,7 +48,7 @@ enum Token {
 
 type Button = (u16, Vec<u16>);
 
-struct Diagram {
+pub struct Diagram {
     indicators: u16,
     buttons: Vec<Button>,
     joltage_reqs: Vec<u16>,
@@ -53,41 +64,77 @@ impl Diagram {
     }
 }
 
-fn parse_diagrams(input: &str) -> Vec<Diagram> {
+// Each diagram is its lights, then its buttons, then its joltages
+fn parse_diagrams(input: &str) -> Result<Vec<Diagram>, ParseError> {
     let mut tokens = Token::lexer(input);
 
     let mut diagrams = Vec::new();
     let mut cur_diagram = Diagram::new();
+    // How many lights the current diagram has, once they've been read
+    let mut light_count = None;
 
-    while let Some(Ok(token)) = tokens.next() {
-        match token {
-            Token::IndicatorLights(indicator) => {
+    while let Some(token) = next_token_or_end(&mut tokens, "lights, a button or joltages")? {
+        match (token, light_count) {
+            (Token::IndicatorLights(indicator), None) => {
                 let indicator_value: u16 = indicator
                     .iter()
                     .enumerate()
                     .map(|(idx, on)| if *on { 1 << idx } else { 0 })
                     .sum();
                 cur_diagram.indicators = indicator_value;
+                light_count = Some(indicator.len());
             }
-            Token::Button(seq) => {
+            (Token::Button(seq), Some(count)) => {
+                if seq.iter().any(|&light| light as usize >= count) {
+                    return Err(ParseError::new(
+                        input,
+                        tokens.span(),
+                        "a button wired to the diagram's lights",
+                    ));
+                }
                 let sequence_value = seq.iter().map(|button| 1 << button).sum();
                 cur_diagram.buttons.push((sequence_value, seq));
             }
-            Token::JoltageReq(seq) => {
+            (Token::JoltageReq(seq), Some(count)) => {
+                if seq.len() != count {
+                    return Err(ParseError::new(
+                        input,
+                        tokens.span(),
+                        "a joltage for every light",
+                    ));
+                }
                 cur_diagram.joltage_reqs = seq;
                 diagrams.push(cur_diagram);
                 cur_diagram = Diagram::new();
+                light_count = None;
+            }
+            (_, None) => {
+                return Err(ParseError::new(
+                    input,
+                    tokens.span(),
+                    "lights like `[.##.]`",
+                ));
+            }
+            (_, Some(_)) => {
+                return Err(ParseError::new(
+                    input,
+                    tokens.span(),
+                    "a button or joltages",
+                ));
             }
         }
     }
 
-    diagrams
+    if light_count.is_some() {
+        return Err(ParseError::end_of_input(input, "joltages like `{3,5,4,7}`"));
+    }
+    non_empty(input, diagrams, "lights like `[.##.]`")
 }
 
 // Pressing a button twice will have the same effect as pressing it 0 times,
 // therefore part 1 requires a trivial number of permutations (which no
 // doubt will change for part 2).
-fn fewest_button_presses_to_initialize(diagram: &Diagram) -> usize {
+fn fewest_button_presses_to_initialize(diagram: &Diagram) -> Option<usize> {
     // Thank you Itertools, I did NOT want to implement a powerset
     for set in diagram.buttons.iter().powerset() {
         if set
@@ -95,26 +142,28 @@ fn fewest_button_presses_to_initialize(diagram: &Diagram) -> usize {
             .fold(0, |acc, &&(button_value, _)| acc ^ button_value)
             == diagram.indicators
         {
-            return set.len();
+            return Some(set.len());
         }
     }
 
-    0
+    None
 }
 
-fn buttons_to_initialize_machines(input: &str) -> usize {
-    let diagrams = parse_diagrams(input);
+// `None` if some machine can't be set up at all
+fn buttons_to_initialize_machines(diagrams: &[Diagram]) -> Option<usize> {
     diagrams
         .iter()
         .map(fewest_button_presses_to_initialize)
         .sum()
 }
 
-fn buttons_to_configure_machines(input: &str) -> usize {
-    let diagrams = parse_diagrams(input);
+fn buttons_to_configure_machines(diagrams: &[Diagram]) -> Option<usize> {
     diagrams
         .iter()
-        .map(|diagram| configure_joltages(&diagram.buttons, &diagram.joltage_reqs))
+        .map(|diagram| {
+            let presses = configure_joltages(&diagram.buttons, &diagram.joltage_reqs);
+            (presses != usize::MAX).then_some(presses)
+        })
         .sum()
 }
 
@@ -152,13 +201,12 @@ fn configure_joltages(buttons: &[Button], current_joltages: &[u16]) -> usize {
             .iter()
             .fold(0, |acc, &(button_value, ..)| acc ^ button_value)
             == odd_joltage_pattern
+            && let Some(next_joltages) = apply_joltages(&set[..], current_joltages)
         {
-            if let Some(next_joltages) = apply_joltages(&set[..], current_joltages) {
-                let sub_cost = configure_joltages(buttons, &next_joltages);
-                if sub_cost != usize::MAX {
-                    let solved = 2 * sub_cost + set.len();
-                    lowest = lowest.min(solved);
-                }
+            let sub_cost = configure_joltages(buttons, &next_joltages);
+            if sub_cost != usize::MAX {
+                let solved = 2 * sub_cost + set.len();
+                lowest = lowest.min(solved);
             }
         }
     }
@@ -166,15 +214,76 @@ fn configure_joltages(buttons: &[Button], current_joltages: &[u16]) -> usize {
     lowest
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Diagram>;
+
+    const SLOW_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_diagrams(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        buttons_to_initialize_machines(input).expect("no buttons light up some machine")
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        buttons_to_configure_machines(input).expect("no buttons reach some machine's joltages")
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(7, buttons_to_initialize_machines(SAMPLE));
-    assert_eq!(457, buttons_to_initialize_machines(INPUT));
+    assert_eq!(
+        Some(7),
+        buttons_to_initialize_machines(&parse_diagrams(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 10) else {
+        return;
+    };
+    assert_eq!(
+        Some(457),
+        buttons_to_initialize_machines(&parse_diagrams(&input).unwrap())
+    );
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(33, buttons_to_configure_machines(SAMPLE));
-    // Takes 4 seconds on release mode, about a minute on debug mode
-    // assert_eq!(17576, buttons_to_configure_machines(INPUT));
+    assert_eq!(
+        Some(33),
+        buttons_to_configure_machines(&parse_diagrams(SAMPLE).unwrap())
+    );
+}
+
+// Takes 4 seconds on release mode, about a minute on debug mode
+#[test]
+#[cfg_attr(not(feature = "slow"), ignore = "slow, run with `--features slow`")]
+fn part_2_input() {
+    let Some(input) = test_input(2025, 10) else {
+        return;
+    };
+    assert_eq!(
+        Some(17576),
+        buttons_to_configure_machines(&parse_diagrams(&input).unwrap())
+    );
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_diagrams("[.##.] (3) (1,4) {3,5,4,7}").err().unwrap();
+    assert_eq!((1, 12), (err.line, err.column));
+    let err = parse_diagrams("[.##.] (3) {3,5,4}").err().unwrap();
+    assert_eq!("a joltage for every light", err.expected);
+    let err = parse_diagrams("(3) {3,5,4,7}").err().unwrap();
+    assert_eq!("lights like `[.##.]`", err.expected);
+    let err = parse_diagrams("[.##.] (3) (1,3)\n").err().unwrap();
+    assert_eq!("joltages like `{3,5,4,7}`", err.expected);
+
+    // Nothing toggles the second light
+    let diagrams = parse_diagrams("[.#] (0) {1,1}").unwrap();
+    assert_eq!(None, buttons_to_initialize_machines(&diagrams));
+    assert_eq!(None, buttons_to_configure_machines(&diagrams));
 }
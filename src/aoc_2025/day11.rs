@@ -1,7 +1,17 @@
-use fxhash::FxHashMap;
+use std::fmt::Display;
+
+use fxhash::{FxHashMap, FxHashSet};
 use logos::Logos;
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, next_token_or_end},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 aaa: you hhh
 you: bbb ccc
 bbb: ddd eee
@@ -13,7 +23,8 @@ ggg: out
 hhh: ccc fff iii
 iii: out";
 
-const SAMPLE_2: &'static str = "
+#[cfg(test)]
+const SAMPLE_2: &str = "
 svr: aaa bbb
 aaa: fft
 fft: ccc
@@ -28,8 +39,6 @@ fff: ggg hhh
 ggg: out
 hhh: out";
 
-const INPUT: &'static str = include_str!("./inputs/day11.txt");
-
 #[derive(Logos)]
 #[logos(skip r"[ \n]")]
 enum Token {
@@ -40,42 +49,96 @@ enum Token {
     Output(String),
 }
 
-type Network = FxHashMap<String, Vec<String>>;
+pub type Network = FxHashMap<String, Vec<String>>;
 
 // This could be faster if I convert every key into a number by inserting into a hash
 // with an incrementing counter as a value. Then I would just need a Vec<Vec<u64>>
 // to record the graph structure for lightning-fast lookups, but that's premature
 // optimization, and we don't need that for a graph containing only ~600 nodes and
 // a few thousand connections (or maybe...)
-fn parse_network(input: &str) -> Network {
-    let mut network = FxHashMap::default();
+fn parse_network(input: &str) -> Result<Network, ParseError> {
+    let mut network: Network = FxHashMap::default();
+    // Where each device was listed, for pointing at it if it turns out to be part of a loop
+    let mut spans = FxHashMap::default();
 
-    let mut cur_input_node = String::from("");
-    let mut cur_outputs = Vec::new();
-    for token in Token::lexer(input) {
-        match token.unwrap() {
+    let mut cur_input_node = None;
+    let mut tokens = Token::lexer(input);
+    while let Some(token) = next_token_or_end(&mut tokens, "a device name")? {
+        match token {
             Token::Input(val) => {
-                if !cur_outputs.is_empty() {
-                    network.insert(cur_input_node, cur_outputs);
+                if network.contains_key(&val) {
+                    return Err(ParseError::new(
+                        input,
+                        tokens.span(),
+                        "a device that isn't listed yet",
+                    ));
                 }
-                cur_outputs = Vec::new();
-                cur_input_node = val;
-            }
-            Token::Output(val) => {
-                cur_outputs.push(val);
+                spans.insert(val.clone(), tokens.span());
+                network.insert(val.clone(), Vec::new());
+                cur_input_node = Some(val);
             }
+            Token::Output(val) => match &cur_input_node {
+                Some(node) => network.get_mut(node).unwrap().push(val),
+                None => {
+                    return Err(ParseError::new(
+                        input,
+                        tokens.span(),
+                        "a device like `aaa:`",
+                    ));
+                }
+            },
         }
     }
-    // the last node
-    network.insert(cur_input_node, cur_outputs);
+    if network.is_empty() {
+        return Err(ParseError::end_of_input(input, "a device like `aaa:`"));
+    }
+    if let Some(device) = find_loop(&network) {
+        return Err(ParseError::new(
+            input,
+            spans[&device].clone(),
+            "a device that doesn't lead back to itself",
+        ));
+    }
 
     // Since "out" is a terminator, we can give it an empty output to make iteration easier
     // network.insert(String::from("out"), Vec::new());
 
+    Ok(network)
+}
+
+// Finds a device that leads back to itself, if there is one
+fn find_loop(network: &Network) -> Option<String> {
+    fn visit<'a>(
+        device: &'a str,
+        network: &'a Network,
+        done: &mut FxHashSet<&'a str>,
+        path: &mut FxHashSet<&'a str>,
+    ) -> Option<String> {
+        if done.contains(device) {
+            return None;
+        }
+        if !path.insert(device) {
+            return Some(device.to_owned());
+        }
+        for output in network.get(device).into_iter().flatten() {
+            if let Some(found) = visit(output, network, done, path) {
+                return Some(found);
+            }
+        }
+        path.remove(device);
+        done.insert(device);
+        None
+    }
+
+    let mut done = FxHashSet::default();
+    let mut path = FxHashSet::default();
     network
+        .keys()
+        .find_map(|device| visit(device, network, &mut done, &mut path))
 }
 
-// This works as long as the network is not cyclical and that "out" is the only terminal, both of which I've verified
+// This works as long as the network is not cyclical, which parsing checks, and that "out" is the
+// only terminal, which I've verified
 fn count_unique_paths(
     node: &String,
     end: &String,
@@ -113,21 +176,41 @@ fn count_problem_paths(network: &Network) -> usize {
     let dac = String::from("dac");
     let out = String::from("out");
 
-    let fft_to_dac = count_unique_paths(&fft, &dac, &network, &mut FxHashMap::default());
+    let fft_to_dac = count_unique_paths(&fft, &dac, network, &mut FxHashMap::default());
     if fft_to_dac > 0 {
         // Path goes svr~>fft~>dac~>out
-        let start_path = count_unique_paths(&svr, &fft, &network, &mut FxHashMap::default());
-        let end_path = count_unique_paths(&dac, &out, &network, &mut FxHashMap::default());
+        let start_path = count_unique_paths(&svr, &fft, network, &mut FxHashMap::default());
+        let end_path = count_unique_paths(&dac, &out, network, &mut FxHashMap::default());
         start_path * fft_to_dac * end_path
     } else {
         // Path goes svr~>dac~>fft~>out
-        let start_path = count_unique_paths(&svr, &dac, &network, &mut FxHashMap::default());
-        let dac_to_fft = count_unique_paths(&dac, &fft, &network, &mut FxHashMap::default());
-        let end_path = count_unique_paths(&fft, &out, &network, &mut FxHashMap::default());
+        let start_path = count_unique_paths(&svr, &dac, network, &mut FxHashMap::default());
+        let dac_to_fft = count_unique_paths(&dac, &fft, network, &mut FxHashMap::default());
+        let end_path = count_unique_paths(&fft, &out, network, &mut FxHashMap::default());
         start_path * dac_to_fft * end_path
     }
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_network(input)
+    }
+
+    fn part_1(network: &Self::Input) -> impl Display {
+        let you = String::from("you");
+        let out = String::from("out");
+        count_unique_paths(&you, &out, network, &mut FxHashMap::default())
+    }
+
+    fn part_2(network: &Self::Input) -> impl Display {
+        count_problem_paths(network)
+    }
+}
+
 #[test]
 fn part_1() {
     let you = String::from("you");
@@ -135,19 +218,45 @@ fn part_1() {
     let paths = count_unique_paths(
         &you,
         &out,
-        &parse_network(SAMPLE),
+        &parse_network(SAMPLE).unwrap(),
         &mut FxHashMap::default(),
     );
     assert_eq!(5, paths);
-    let paths = count_unique_paths(&you, &out, &parse_network(INPUT), &mut FxHashMap::default());
+
+    let Some(input) = test_input(2025, 11) else {
+        return;
+    };
+    let paths = count_unique_paths(
+        &you,
+        &out,
+        &parse_network(&input).unwrap(),
+        &mut FxHashMap::default(),
+    );
     assert_eq!(749, paths);
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(2, count_problem_paths(&parse_network(SAMPLE_2)));
+    assert_eq!(2, count_problem_paths(&parse_network(SAMPLE_2).unwrap()));
+
+    let Some(input) = test_input(2025, 11) else {
+        return;
+    };
     assert_eq!(
         420_257_875_695_750,
-        count_problem_paths(&parse_network(INPUT))
+        count_problem_paths(&parse_network(&input).unwrap())
     );
 }
+
+#[test]
+fn bad_input() {
+    let err = parse_network("aaa: bbb\nbbb: ccc\naaa: out").err().unwrap();
+    assert_eq!((3, 1), (err.line, err.column));
+    let err = parse_network("out\naaa: out").err().unwrap();
+    assert_eq!("a device like `aaa:`", err.expected);
+    let err = parse_network("aaa: bbb\nbbb: ccc out\nccc: aaa")
+        .err()
+        .unwrap();
+    assert_eq!("a device that doesn't lead back to itself", err.expected);
+    assert!(parse_network("\n").is_err());
+}
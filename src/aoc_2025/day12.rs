@@ -1,18 +1,51 @@
-const INPUT: &'static str = include_str!("./inputs/day12.txt");
+use std::fmt::Display;
 
-struct Present {
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+pub struct Present {
     size: usize,
 }
 
-struct Tree {
+pub struct Tree {
     width: usize,
     height: usize,
-    requirements: [usize; 6],
+    requirements: Vec<usize>,
+}
+
+fn parse_tree(input: &str, line: &str, present_count: usize) -> Result<Tree, ParseError> {
+    let (shape, presents) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at(input, line, "a tree like `12x5: 1 0 1 0 2 2`"))?;
+    let (width, height) = shape
+        .split_once('x')
+        .ok_or_else(|| ParseError::at(input, shape, "a size like `12x5`"))?;
+    let requirements = presents
+        .split_whitespace()
+        .map(|x| parse_at(input, x, "a number of presents"))
+        .collect::<Result<Vec<usize>, _>>()?;
+    if requirements.len() != present_count {
+        return Err(ParseError::at(
+            input,
+            presents,
+            "one count for every present shape",
+        ));
+    }
+
+    Ok(Tree {
+        width: parse_at(input, width, "a tree width")?,
+        height: parse_at(input, height, "a tree height")?,
+        requirements,
+    })
 }
 
-fn parse_presents(input: &str) -> (Vec<Present>, Vec<Tree>) {
+fn parse_presents(input: &str) -> Result<(Vec<Present>, Vec<Tree>), ParseError> {
     let mut sections: Vec<&str> = input.trim().split("\n\n").collect();
-    let trees = sections.pop().unwrap();
+    let trees = sections.pop().unwrap_or_default();
 
     let mut presents = Vec::new();
 
@@ -24,35 +57,12 @@ fn parse_presents(input: &str) -> (Vec<Present>, Vec<Tree>) {
         });
     }
 
-    let trees: Vec<Tree> = trees
+    let trees = trees
         .split('\n')
-        .map(|line| {
-            let parts: Vec<&str> = line.split(':').map(|s| s.trim()).collect();
-            println!("Parts: {:?}", parts);
-            let shape: Vec<usize> = parts[0]
-                .split('x')
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect();
-            let presents: Vec<usize> = parts[1]
-                .split(' ')
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect();
-            Tree {
-                width: shape[0],
-                height: shape[1],
-                requirements: [
-                    presents[0],
-                    presents[1],
-                    presents[2],
-                    presents[3],
-                    presents[4],
-                    presents[5],
-                ],
-            }
-        })
-        .collect();
+        .map(|line| parse_tree(input, line, presents.len()))
+        .collect::<Result<Vec<Tree>, _>>()?;
 
-    (presents, trees)
+    Ok((presents, trees))
 }
 
 // This day is deceptive. It looks insanely challenging, but actually, the really basic optimization is all you need:
@@ -63,11 +73,8 @@ fn parse_presents(input: &str) -> (Vec<Present>, Vec<Tree>) {
 // It turns out the input was crafted such that all trees fit into one of the first 2 buckets, so this is solvable
 // by only counting how many trees have less space than the sum total of spaces in the presents. No fitting, packing,
 // no memoization. That's it. That's the whole thing.
-#[test]
-fn part_1() {
-    let (presents, trees) = parse_presents(INPUT);
-
-    let valid_tree_configs = trees
+fn count_valid_trees((presents, trees): &(Vec<Present>, Vec<Tree>)) -> usize {
+    trees
         .iter()
         .filter(|tree| {
             let tree_size = tree.height * tree.width;
@@ -78,7 +85,29 @@ fn part_1() {
                 .fold(0, |acc, (idx, req)| req * presents[idx].size + acc);
             tree_size >= presents_size
         })
-        .count();
+        .count()
+}
 
-    assert_eq!(555, valid_tree_configs);
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Vec<Present>, Vec<Tree>);
+
+    const HAS_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_presents(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_valid_trees(input)
+    }
+}
+
+#[test]
+fn part_1() {
+    let Some(input) = test_input(2025, 12) else {
+        return;
+    };
+    assert_eq!(555, count_valid_trees(&parse_presents(&input).unwrap()));
 }
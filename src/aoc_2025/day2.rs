@@ -1,21 +1,60 @@
+use std::fmt::Display;
+
 use fxhash::FxHashSet;
 use logos::Logos;
 
-const SAMPLE: &'static str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, expect_token, expect_token_or_end, non_empty},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
 1698522-1698528,446443-446449,38593856-38593862,565653-565659,
 824824821-824824827,2121212118-2121212124";
 
-const INPUT: &'static str = include_str!("./inputs/day2.txt");
-
 #[derive(Logos)]
 #[logos(skip r"[,\-\n]")]
 enum Token {
-    #[regex("[0-9]+", |lex| lex.slice().to_owned())]
+    // Kept as text since the digits get split up. At most 19 digits, so that it and the next power
+    // of 10 both fit in a usize
+    #[regex("[0-9]+", |lex| Some(lex.slice()).filter(|id| id.len() <= 19).map(str::to_owned))]
     Id(String),
 }
 
+impl Token {
+    fn take_id(self) -> Option<String> {
+        match self {
+            Self::Id(id) => Some(id),
+        }
+    }
+}
+
+pub type IdRange = (String, String);
+
+fn parse_ranges(input: &str) -> Result<Vec<IdRange>, ParseError> {
+    let mut tokens = Token::lexer(input);
+    let mut ranges = Vec::new();
+    // The ID halves are compared digit by digit, so leading zeros would throw them off
+    let take_id = |token: Token| token.take_id().filter(|id| !id.starts_with('0'));
+    while let Some(first) = expect_token_or_end(&mut tokens, "a range like `11-22`", take_id)? {
+        let second = expect_token(&mut tokens, "the end of the range", take_id)?;
+        if first.parse::<usize>().unwrap() > second.parse::<usize>().unwrap() {
+            return Err(ParseError::new(
+                input,
+                tokens.span(),
+                "an end no lower than the start",
+            ));
+        }
+        ranges.push((first, second));
+    }
+    non_empty(input, ranges, "a range like `11-22`")
+}
+
 fn parse_and_split(value: &str, up: bool) -> (usize, usize) {
-    let is_even = value.len() % 2 == 0;
+    let is_even = value.len().is_multiple_of(2);
     let len = value.len();
     if is_even {
         (
@@ -24,8 +63,8 @@ fn parse_and_split(value: &str, up: bool) -> (usize, usize) {
         )
     } else {
         let new_len = if up { len } else { len - 1 } as u32;
-        let full_value = (10 as usize).pow(new_len);
-        let half_value = (10 as usize).pow(new_len / 2);
+        let full_value = 10_usize.pow(new_len);
+        let half_value = 10_usize.pow(new_len / 2);
         if up {
             (full_value, half_value)
         } else {
@@ -42,7 +81,7 @@ fn find_simple_invalid_ids(first: &str, second: &str) -> Vec<usize> {
 
     for x in first_half_of_start..first_half_of_end + 1 {
         let x_len = x.checked_ilog10().unwrap_or(0) + 1;
-        let invalid_id = x * (10 as usize).pow(x_len) + x;
+        let invalid_id = x * 10_usize.pow(x_len) + x;
         if invalid_id >= first_value && invalid_id <= second_value {
             invalid_ids.push(invalid_id);
         }
@@ -51,14 +90,10 @@ fn find_simple_invalid_ids(first: &str, second: &str) -> Vec<usize> {
     invalid_ids
 }
 
-fn get_sum_of_invalids(input: &str) -> usize {
-    let mut input = Token::lexer(input);
+fn get_sum_of_invalids(ranges: &[IdRange]) -> usize {
     let mut sum: usize = 0;
-    while let Some(Ok(Token::Id(first))) = input.next() {
-        let Token::Id(second) = input.next().unwrap().unwrap();
-        sum += find_simple_invalid_ids(&first, &second)
-            .iter()
-            .sum::<usize>();
+    for (first, second) in ranges {
+        sum += find_simple_invalid_ids(first, second).iter().sum::<usize>();
     }
     sum
 }
@@ -66,7 +101,7 @@ fn get_sum_of_invalids(input: &str) -> usize {
 fn possible_repeat_values(start: &str, end: &str) -> Vec<usize> {
     let len = start.len();
     if len != end.len() {
-        let split_value = (10 as usize).pow(start.len() as u32);
+        let split_value = 10_usize.pow(start.len() as u32);
         let mut all = possible_repeat_values(start, &(split_value - 1).to_string());
         all.append(&mut possible_repeat_values(&split_value.to_string(), end));
         return all;
@@ -75,7 +110,7 @@ fn possible_repeat_values(start: &str, end: &str) -> Vec<usize> {
     let half_len = len / 2;
     let mut ids = Vec::new();
     for x in 1..half_len + 1 {
-        if len % x == 0 {
+        if len.is_multiple_of(x) {
             let range_start: usize = start[0..x].parse().unwrap();
             let range_end: usize = end[0..x].parse().unwrap();
             for segment in range_start..range_end + 1 {
@@ -102,24 +137,81 @@ fn complex_id_sum(start: &str, end: &str) -> usize {
     invalid_ids.iter().sum()
 }
 
-fn get_sum_of_complex_invalids(input: &str) -> usize {
-    let mut input = Token::lexer(input);
+fn get_sum_of_complex_invalids(ranges: &[IdRange]) -> usize {
     let mut sum: usize = 0;
-    while let Some(Ok(Token::Id(first))) = input.next() {
-        let Token::Id(second) = input.next().unwrap().unwrap();
-        sum += complex_id_sum(&first, &second);
+    for (first, second) in ranges {
+        sum += complex_id_sum(first, second);
     }
     sum
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<IdRange>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_ranges(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        get_sum_of_invalids(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        get_sum_of_complex_invalids(input)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(1_227_775_554, get_sum_of_invalids(SAMPLE));
-    assert_eq!(23_560_874_270, get_sum_of_invalids(INPUT));
+    assert_eq!(
+        1_227_775_554,
+        get_sum_of_invalids(&parse_ranges(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 2) else {
+        return;
+    };
+    assert_eq!(
+        23_560_874_270,
+        get_sum_of_invalids(&parse_ranges(&input).unwrap())
+    );
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(4_174_379_265, get_sum_of_complex_invalids(SAMPLE));
-    assert_eq!(44_143_124_633, get_sum_of_complex_invalids(INPUT));
+    assert_eq!(
+        4_174_379_265,
+        get_sum_of_complex_invalids(&parse_ranges(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 2) else {
+        return;
+    };
+    assert_eq!(
+        44_143_124_633,
+        get_sum_of_complex_invalids(&parse_ranges(&input).unwrap())
+    );
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_ranges("11-22,95-0115").err().unwrap();
+    assert_eq!((1, 10), (err.line, err.column));
+    let err = parse_ranges("22-11").err().unwrap();
+    assert_eq!("an end no lower than the start", err.expected);
+    let err = parse_ranges("11-10000000000000000000").err().unwrap();
+    assert_eq!("the end of the range", err.expected);
+
+    // The largest IDs still fit, even where the length changes partway through the range
+    let ranges = parse_ranges(
+        "999999999999999999-1000000000000000001,9999999999999999990-9999999999999999999",
+    )
+    .unwrap();
+    assert_eq!(999_999_999_999_999_999, get_sum_of_invalids(&ranges));
+    assert_eq!(
+        999_999_999_999_999_999 + 9_999_999_999_999_999_999,
+        get_sum_of_complex_invalids(&ranges)
+    );
 }
@@ -1,28 +1,40 @@
-const SAMPLE: &'static str = "
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 987654321111111
 811111111111119
 234234234234278
 818181911112111";
 
-const INPUT: &'static str = include_str!("./inputs/day3.txt");
-
-fn get_banks(input: &str) -> Vec<Vec<usize>> {
+// Part 2 turns on 12 batteries per bank, so shorter banks are rejected up front
+fn get_banks(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
     input
         .trim()
         .split("\n")
         .map(|s| {
-            s.chars()
-                .map(|ch| ch.to_digit(10).unwrap() as usize)
+            if s.len() < 12 {
+                return Err(ParseError::at(input, s, "a bank of at least 12 batteries"));
+            }
+            s.char_indices()
+                .map(|(idx, ch)| {
+                    ch.to_digit(10).map(|digit| digit as usize).ok_or_else(|| {
+                        ParseError::at(input, &s[idx..idx + ch.len_utf8()], "a digit")
+                    })
+                })
                 .collect()
         })
         .collect()
 }
 
-fn get_largest_joltage(bank: &Vec<usize>) -> usize {
+fn get_largest_joltage(bank: &[usize]) -> usize {
     let mut tens_place_idx = 0;
     let mut tens_place = bank[0];
-    for idx in 1..bank.len() - 1 {
-        let val = bank[idx];
+    for (idx, &val) in bank.iter().enumerate().take(bank.len() - 1).skip(1) {
         if val > tens_place {
             tens_place_idx = idx;
             tens_place = val;
@@ -31,8 +43,7 @@ fn get_largest_joltage(bank: &Vec<usize>) -> usize {
 
     let ones_place_idx = tens_place_idx + 1;
     let mut ones_place = bank[ones_place_idx];
-    for idx in ones_place_idx + 1..bank.len() {
-        let val = bank[idx];
+    for &val in &bank[ones_place_idx + 1..] {
         if val > ones_place {
             ones_place = val;
         }
@@ -51,37 +62,68 @@ fn get_largest_extreme_joltage(bank: &[usize], digits: u32) -> usize {
     let search_space = &bank[0..last_idx];
     let mut cur_high_idx = 0;
     let mut cur_high = search_space[0];
-    for idx in 1..search_space.len() {
-        let val = search_space[idx];
+    for (idx, &val) in search_space.iter().enumerate().skip(1) {
         if val > cur_high {
             cur_high_idx = idx;
             cur_high = val;
         }
     }
 
-    cur_high * (10 as usize).pow(digits - 1)
+    cur_high * 10_usize.pow(digits - 1)
         + get_largest_extreme_joltage(&bank[cur_high_idx + 1..], digits - 1)
 }
 
-fn total_joltages(input: &str) -> usize {
-    get_banks(input).iter().map(get_largest_joltage).sum()
+fn total_joltages(banks: &[Vec<usize>]) -> usize {
+    banks.iter().map(|bank| get_largest_joltage(bank)).sum()
 }
 
-fn total_extreme_joltages(input: &str) -> usize {
-    get_banks(input)
+fn total_extreme_joltages(banks: &[Vec<usize>]) -> usize {
+    banks
         .iter()
         .map(|bank| get_largest_extreme_joltage(&bank[..], 12))
         .sum()
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_banks(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        total_joltages(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        total_extreme_joltages(input)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(357, total_joltages(SAMPLE));
-    assert_eq!(17435, total_joltages(INPUT));
+    assert_eq!(357, total_joltages(&get_banks(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2025, 3) else {
+        return;
+    };
+    assert_eq!(17435, total_joltages(&get_banks(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(3_121_910_778_619, total_extreme_joltages(SAMPLE));
-    assert_eq!(172_886_048_065_379, total_extreme_joltages(INPUT));
+    assert_eq!(
+        3_121_910_778_619,
+        total_extreme_joltages(&get_banks(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 3) else {
+        return;
+    };
+    assert_eq!(
+        172_886_048_065_379,
+        total_extreme_joltages(&get_banks(&input).unwrap())
+    );
 }
@@ -1,4 +1,11 @@
-const SAMPLE: &'static str = "
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{grid::Grid, parse::ParseError, solution::Solution};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 ..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
@@ -10,88 +17,37 @@ const SAMPLE: &'static str = "
 .@@@@@@@@.
 @.@.@@@.@.";
 
-const INPUT: &'static str = include_str!("./inputs/day4.txt");
-
-struct Coor(isize, isize);
-struct PosCoor(usize, usize);
-type Grid = Vec<Vec<bool>>;
-
-fn parse_grid(input: &str) -> Grid {
-    input
-        .trim()
-        .split("\n")
-        .map(|row| row.chars().map(|ch| ch == '@').collect())
-        .collect()
-}
-
-fn neighbors(&Coor(x, y): &Coor) -> Vec<Coor> {
-    vec![
-        Coor(x - 1, y),
-        Coor(x + 1, y),
-        Coor(x, y - 1),
-        Coor(x, y + 1),
-        Coor(x - 1, y - 1),
-        Coor(x + 1, y - 1),
-        Coor(x - 1, y + 1),
-        Coor(x + 1, y + 1),
-    ]
-}
-
-fn is_roll(grid: &Grid, &Coor(x, y): &Coor, &PosCoor(max_x, max_y): &PosCoor) -> bool {
-    let x = usize::try_from(x);
-    let y = usize::try_from(y);
-    if let Ok(x) = x
-        && let Ok(y) = y
-    {
-        if x > max_x || y > max_y {
-            return false;
-        }
-        let row_opt = grid.get(y);
-        let col_opt = row_opt.map(|row| row.get(x).unwrap_or(&false));
-        return *col_opt.unwrap_or(&false);
-    }
+pub type Floor = Grid<bool>;
 
-    false
+fn parse_grid(input: &str) -> Result<Floor, ParseError> {
+    Grid::parse(input, "`@` or `.`", |ch| match ch {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
-fn count_liftables(input: &str) -> usize {
-    let grid = parse_grid(input);
-    let (_, removed) = clean_pass(grid);
+fn count_liftables(grid: &Floor) -> usize {
+    let (_, removed) = clean_pass(grid.clone());
     removed
 }
 
-fn clean_pass(grid: Grid) -> (Grid, usize) {
+fn clean_pass(grid: Floor) -> (Floor, usize) {
     let mut count = 0;
-    let max = PosCoor(grid.get(0).unwrap().len(), grid.len());
-
-    let new_grid: Grid = grid
-        .iter()
-        .enumerate()
-        .map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .map(|(x, val)| {
-                    if *val
-                        && neighbors(&Coor(x as isize, y as isize))
-                            .iter()
-                            .filter(|neighbor| is_roll(&grid, neighbor, &max))
-                            .count()
-                            < 4
-                    {
-                        count += 1;
-                        return false;
-                    }
-                    return *val;
-                })
-                .collect()
-        })
-        .collect();
+    let new_grid = Grid::from_fn(grid.width(), grid.height(), |pos| {
+        let val = grid[pos];
+        if val && grid.neighbors8(pos).filter(|&n| grid[n]).count() < 4 {
+            count += 1;
+            return false;
+        }
+        val
+    });
 
     (new_grid, count)
 }
 
-fn remove_liftables(input: &str) -> usize {
-    let mut grid = parse_grid(input);
+fn remove_liftables(grid: &Floor) -> usize {
+    let mut grid = grid.clone();
     let mut total_removed = 0;
     loop {
         let (new_grid, removed) = clean_pass(grid);
@@ -105,14 +61,40 @@ fn remove_liftables(input: &str) -> usize {
     total_removed
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Floor;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_liftables(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        remove_liftables(input)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(13, count_liftables(SAMPLE));
-    assert_eq!(1344, count_liftables(INPUT));
+    assert_eq!(13, count_liftables(&parse_grid(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2025, 4) else {
+        return;
+    };
+    assert_eq!(1344, count_liftables(&parse_grid(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(43, remove_liftables(SAMPLE));
-    assert_eq!(8112, remove_liftables(INPUT));
+    assert_eq!(43, remove_liftables(&parse_grid(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2025, 4) else {
+        return;
+    };
+    assert_eq!(8112, remove_liftables(&parse_grid(&input).unwrap()));
 }
@@ -1,4 +1,15 @@
-const SAMPLE: &'static str = "
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    intervals::IntervalSet,
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 3-5
 10-14
 16-20
@@ -11,114 +22,98 @@ const SAMPLE: &'static str = "
 17
 32";
 
-const INPUT: &'static str = include_str!("./inputs/day5.txt");
-
-#[derive(Clone)]
-struct Interval {
-    pub start: usize,
-    pub end: usize,
-}
-
-impl Interval {
-    fn new(start: usize, end: usize) -> Self {
-        Self { start, end }
-    }
-
-    fn grow_end(&mut self, other: &Self) -> bool {
-        if self.end >= other.start {
-            self.end = self.end.max(other.end);
-            true
-        } else {
-            false
-        }
-    }
-
-    fn size(&self) -> usize {
-        self.end - self.start + 1
-    }
-}
-
-struct Inventory {
-    intervals: Vec<Interval>,
+pub struct Inventory {
+    fresh: IntervalSet<usize>,
     ingredients: Vec<usize>,
 }
 
-fn parse_inventory(input: &str) -> Inventory {
-    let separator_idx = input.find("\n\n").unwrap();
+fn parse_inventory(input: &str) -> Result<Inventory, ParseError> {
+    let separator_idx = input
+        .find("\n\n")
+        .ok_or_else(|| ParseError::end_of_input(input, "a blank line before the ingredient IDs"))?;
     let (id_ranges, ingredient_ids) = input.split_at(separator_idx);
-    let intervals: Vec<Interval> = id_ranges
+    let fresh = id_ranges
         .trim()
         .split("\n")
         .map(|range| {
-            let dash_at = range.find("-").unwrap();
-            let (start, end) = range.split_at(dash_at);
-            Interval::new(start.parse().unwrap(), end[1..].parse().unwrap())
+            let (start, end) = range
+                .split_once('-')
+                .ok_or_else(|| ParseError::at(input, range, "a range like `3-5`"))?;
+            Ok((
+                parse_at(input, start, "the start of the range")?,
+                parse_at(input, end, "the end of the range")?,
+            ))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    Inventory {
-        intervals: merge_intervals(intervals),
+    Ok(Inventory {
+        fresh,
         ingredients: ingredient_ids
             .trim()
             .split("\n")
-            .map(|id| id.parse().unwrap())
-            .collect(),
-    }
+            .map(|id| parse_at(input, id, "an ingredient ID"))
+            .collect::<Result<_, _>>()?,
+    })
 }
 
-fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
-    // Both parts require the intervals to be pre-sorted by the interval start value
-    intervals.sort_by(|a, b| a.start.cmp(&b.start));
-    let mut merged_intervals: Vec<Interval> = Vec::new();
-    for interval in intervals {
-        match merged_intervals.last_mut() {
-            Some(last) => {
-                if !last.grow_end(&interval) {
-                    merged_intervals.push(interval);
-                }
-            }
-            None => {
-                merged_intervals.push(interval);
-            }
-        }
-    }
-    merged_intervals
+fn count_fresh_ingredients(inventory: &Inventory) -> usize {
+    inventory
+        .ingredients
+        .iter()
+        .filter(|&&ingredient| inventory.fresh.contains(ingredient))
+        .count()
 }
 
-fn count_fresh_ingredients(input: &str) -> usize {
-    let Inventory {
-        ingredients,
-        intervals,
-    } = parse_inventory(input);
-
-    let mut count = 0;
-    for ingredient in ingredients {
-        for interval in &intervals {
-            if ingredient < interval.start {
-                break;
-            } else if ingredient <= interval.end {
-                count += 1;
-                break;
-            }
-        }
+fn count_total_fresh_ingredients(inventory: &Inventory) -> u64 {
+    inventory.fresh.len()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Inventory;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_inventory(input)
     }
 
-    count
-}
+    fn part_1(input: &Self::Input) -> impl Display {
+        count_fresh_ingredients(input)
+    }
 
-fn count_total_fresh_ingredients(input: &str) -> usize {
-    let Inventory { intervals, .. } = parse_inventory(input);
-    intervals.iter().map(|interval| interval.size()).sum()
+    fn part_2(input: &Self::Input) -> impl Display {
+        count_total_fresh_ingredients(input)
+    }
 }
 
 #[test]
 fn part_1() {
-    assert_eq!(3, count_fresh_ingredients(SAMPLE));
-    assert_eq!(868, count_fresh_ingredients(INPUT));
+    assert_eq!(
+        3,
+        count_fresh_ingredients(&parse_inventory(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 5) else {
+        return;
+    };
+    assert_eq!(
+        868,
+        count_fresh_ingredients(&parse_inventory(&input).unwrap())
+    );
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(14, count_total_fresh_ingredients(SAMPLE));
-    assert_eq!(354_143_734_113_772, count_total_fresh_ingredients(INPUT));
+    assert_eq!(
+        14,
+        count_total_fresh_ingredients(&parse_inventory(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 5) else {
+        return;
+    };
+    assert_eq!(
+        354_143_734_113_772,
+        count_total_fresh_ingredients(&parse_inventory(&input).unwrap())
+    );
 }
@@ -1,14 +1,22 @@
+use std::fmt::Display;
+
 use logos::Logos;
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    parse::{ParseError, next_token_or_end, non_empty},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 123 328  51 64 
  45 64  387 23 
   6 98  215 314
 *   +   *   +  
 ";
 
-const INPUT: &'static str = include_str!("./inputs/day6.txt");
-
 #[derive(Logos)]
 #[logos(skip r" ")]
 enum Token {
@@ -21,7 +29,7 @@ enum Token {
     #[token("*")]
     Star,
 
-    #[regex("[0-9]+", |lex| lex.slice().parse::<usize>().unwrap())]
+    #[regex("[0-9]+", |lex| lex.slice().parse::<usize>().ok())]
     Number(usize),
 }
 
@@ -54,30 +62,48 @@ impl Op {
     }
 }
 
-struct Homework {
+pub struct Homework {
     rows: Vec<Vec<usize>>,
     operations: Vec<Op>,
 }
 
-fn parse_homework(input: &str) -> Homework {
+fn parse_homework(input: &str) -> Result<Homework, ParseError> {
     let mut homework = Homework {
         rows: Vec::new(),
         operations: Vec::new(),
     };
-    let mut lex = Token::lexer(input.trim());
+    let mut lex = Token::lexer(input);
     let mut cur_row = Vec::new();
+    let mut row_spans = Vec::new();
+    let mut row_start = 0;
     let mut ops = Vec::new();
-    while let Some(Ok(token)) = lex.next() {
+    while let Some(token) = next_token_or_end(&mut lex, "a number or operator")? {
         match token {
+            // The operators are the last row, so nothing else can come after them
+            Token::Number(_) if !ops.is_empty() => {
+                return Err(ParseError::new(input, lex.span(), "an operator"));
+            }
             Token::Number(val) => {
+                if cur_row.is_empty() {
+                    row_start = lex.span().start;
+                }
                 cur_row.push(val);
             }
+            // Blank lines are skipped rather than trimmed so error positions line up with the input
             Token::NewLine => {
-                if ops.is_empty() {
+                if ops.is_empty() && !cur_row.is_empty() {
                     homework.rows.push(cur_row);
+                    row_spans.push(row_start..lex.span().start);
                     cur_row = Vec::new();
                 }
             }
+            Token::Plus | Token::Star if !cur_row.is_empty() => {
+                return Err(ParseError::new(
+                    input,
+                    lex.span(),
+                    "a number or the end of the row",
+                ));
+            }
             Token::Plus => {
                 ops.push(Op::Add);
             }
@@ -86,31 +112,46 @@ fn parse_homework(input: &str) -> Homework {
             }
         }
     }
-    homework.operations = ops;
+    if homework.rows.is_empty() {
+        return Err(ParseError::end_of_input(input, "a row of numbers"));
+    }
+    homework.operations = non_empty(input, ops, "a row of operators")?;
 
-    for row in &homework.rows {
+    for (row, span) in homework.rows.iter().zip(row_spans) {
         if row.len() != homework.operations.len() {
-            panic!("Homework was misaligned");
+            return Err(ParseError::new(
+                input,
+                span,
+                "a row with one number per operator",
+            ));
         }
     }
 
-    homework
+    Ok(homework)
 }
 
-fn eval_cephalopod_homework(input: &str) -> usize {
-    let mut input_rows: Vec<Vec<char>> = input
-        .trim_matches('\n')
-        .split("\n")
-        .map(|row| row.chars().collect())
-        .collect();
-    let op_row = input_rows.pop().unwrap();
-
-    for row in &input_rows {
-        if row.len() != op_row.len() {
-            panic!("Cephalopod Homework input was misaligned");
+// The last row holds the operators, and every row has to be as wide as it
+fn parse_cephalopod_rows(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let lines: Vec<&str> = input.trim_matches('\n').split('\n').collect();
+    let width = lines.last().map_or(0, |line| line.len());
+    for line in &lines {
+        if line.len() != width {
+            return Err(ParseError::at(
+                input,
+                line,
+                "a row as wide as the operator row",
+            ));
         }
     }
 
+    Ok(lines.into_iter().map(|row| row.chars().collect()).collect())
+}
+
+fn eval_cephalopod_homework(rows: &[Vec<char>]) -> usize {
+    let Some((op_row, input_rows)) = rows.split_last() else {
+        return 0;
+    };
+
     let mut sum = 0;
     let mut cur_problem = 0;
     let mut cur_op = Op::Add; // Doesn't matter what it is, because it will be overwritten immedately
@@ -155,14 +196,67 @@ fn evaluate(homework: &Homework) -> usize {
         .sum()
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    // The two parts read the sheet differently, so both readings are kept
+    type Input = (Homework, Vec<Vec<char>>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((parse_homework(input)?, parse_cephalopod_rows(input)?))
+    }
+
+    fn part_1((homework, _): &Self::Input) -> impl Display {
+        evaluate(homework)
+    }
+
+    fn part_2((_, rows): &Self::Input) -> impl Display {
+        eval_cephalopod_homework(rows)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(4_277_556, evaluate(&parse_homework(SAMPLE)));
-    assert_eq!(6_171_290_547_579, evaluate(&parse_homework(INPUT)));
+    assert_eq!(4_277_556, evaluate(&parse_homework(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2025, 6) else {
+        return;
+    };
+    assert_eq!(
+        6_171_290_547_579,
+        evaluate(&parse_homework(&input).unwrap())
+    );
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(3_263_827, eval_cephalopod_homework(SAMPLE));
-    assert_eq!(8_811_937_976_367, eval_cephalopod_homework(INPUT));
+    assert_eq!(
+        3_263_827,
+        eval_cephalopod_homework(&parse_cephalopod_rows(SAMPLE).unwrap())
+    );
+
+    let Some(input) = test_input(2025, 6) else {
+        return;
+    };
+    assert_eq!(
+        8_811_937_976_367,
+        eval_cephalopod_homework(&parse_cephalopod_rows(&input).unwrap())
+    );
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_homework("1 2\n3\n+ *\n").err().unwrap();
+    assert_eq!((2, 1), (err.line, err.column));
+
+    let err = parse_homework("1 2\n3 4 +\n").err().unwrap();
+    assert_eq!((2, 5), (err.line, err.column));
+    let err = parse_homework("1 2\n3 4\n+ *\n5 6").err().unwrap();
+    assert_eq!((4, 1), (err.line, err.column));
+    let err = parse_homework("1 2\n3 4\n").err().unwrap();
+    assert_eq!("a row of operators", err.expected);
+    assert!(parse_homework("\n").is_err());
+
+    let err = parse_cephalopod_rows("12\n3\n+ ").unwrap_err();
+    assert_eq!((2, 1), (err.line, err.column));
 }
@@ -1,6 +1,13 @@
+use std::fmt::Display;
+
 use fxhash::{FxHashMap, FxHashSet};
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{parse::ParseError, solution::Solution};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 .......S.......
 ...............
 .......^.......
@@ -18,11 +25,9 @@ const SAMPLE: &'static str = "
 .^.^.^.^.^...^.
 ...............";
 
-const INPUT: &'static str = include_str!("./inputs/day7.txt");
-
 type Pair = (isize, isize);
 
-struct Manifold {
+pub struct Manifold {
     start: Pair,
     max_row: isize,
     splitters: FxHashSet<Pair>,
@@ -54,7 +59,7 @@ impl Manifold {
     // }
 }
 
-fn parse_manifold(input: &str) -> Manifold {
+fn parse_manifold(input: &str) -> Result<Manifold, ParseError> {
     let mut manifold = Manifold {
         start: (isize::MAX, isize::MAX),
         max_row: 0,
@@ -62,7 +67,7 @@ fn parse_manifold(input: &str) -> Manifold {
     };
 
     for (row_idx, row) in input.trim().split("\n").enumerate() {
-        for (col_idx, char) in row.chars().enumerate() {
+        for (col_idx, (byte_idx, char)) in row.char_indices().enumerate() {
             let coor = (col_idx as isize, row_idx as isize);
             match char {
                 'S' => {
@@ -71,18 +76,24 @@ fn parse_manifold(input: &str) -> Manifold {
                 '^' => {
                     manifold.splitters.insert(coor);
                 }
-                _ => {}
+                '.' => {}
+                _ => {
+                    let slice = &row[byte_idx..byte_idx + char.len_utf8()];
+                    return Err(ParseError::at(input, slice, "`S`, `^` or `.`"));
+                }
             }
         }
         manifold.max_row = row_idx as isize;
     }
 
-    manifold
-}
+    if manifold.start.0 == isize::MAX {
+        return Err(ParseError::end_of_input(input, "a starting point `S`"));
+    }
 
-fn count_splits(input: &str) -> (usize, usize) {
-    let manifold = parse_manifold(input);
+    Ok(manifold)
+}
 
+fn count_splits(manifold: &Manifold) -> (usize, usize) {
     let mut classical_splits = 0;
     let mut multiversal_splits = 0;
     let mut beams = FxHashMap::<Pair, usize>::default();
@@ -121,13 +132,34 @@ fn count_splits(input: &str) -> (usize, usize) {
     (classical_splits, multiversal_splits)
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Manifold;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_manifold(input)
+    }
+
+    fn part_1(manifold: &Self::Input) -> impl Display {
+        count_splits(manifold).0
+    }
+
+    fn part_2(manifold: &Self::Input) -> impl Display {
+        count_splits(manifold).1
+    }
+}
+
 #[test]
 fn both_parts() {
-    let (classical_splits, multiversal_splits) = count_splits(SAMPLE);
+    let (classical_splits, multiversal_splits) = count_splits(&parse_manifold(SAMPLE).unwrap());
     assert_eq!(21, classical_splits);
     assert_eq!(40, multiversal_splits);
 
-    let (classical_splits, multiversal_splits) = count_splits(INPUT);
+    let Some(input) = test_input(2025, 7) else {
+        return;
+    };
+    let (classical_splits, multiversal_splits) = count_splits(&parse_manifold(&input).unwrap());
     assert_eq!(1646, classical_splits);
     assert_eq!(32_451_134_474_991, multiversal_splits);
 }
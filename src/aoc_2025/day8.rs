@@ -1,8 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
-
-use fxhash::{FxHashMap, FxHashSet};
-
-const SAMPLE: &'static str = "
+use std::fmt::Display;
+
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    dsu::DisjointSet,
+    parse::{ParseError, parse_at},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 162,817,812
 57,618,57
 906,360,560
@@ -24,10 +31,8 @@ const SAMPLE: &'static str = "
 984,92,344
 425,690,689";
 
-const INPUT: &'static str = include_str!("./inputs/day8.txt");
-
 #[derive(Clone)]
-struct Coor3D(i64, i64, i64);
+pub struct Coor3D(i64, i64, i64);
 
 impl Coor3D {
     fn abs_dist_from(&self, other: &Self) -> i64 {
@@ -36,115 +41,87 @@ impl Coor3D {
     }
 }
 
-struct Point {
-    connections: FxHashSet<usize>,
-}
-
-fn parse_junctions(input: &str) -> Vec<Coor3D> {
+fn parse_junctions(input: &str) -> Result<Vec<Coor3D>, ParseError> {
     input
         .trim()
         .split("\n")
         .map(|line| {
             let mut parts = line.split(",");
-            Coor3D(
-                parts.next().unwrap().parse().unwrap(),
-                parts.next().unwrap().parse().unwrap(),
-                parts.next().unwrap().parse().unwrap(),
-            )
+            let mut next_part = |expected| match parts.next() {
+                Some(part) => parse_at(input, part, expected),
+                None => Err(ParseError::at(input, &line[line.len()..], expected)),
+            };
+            Ok(Coor3D(
+                next_part("an X coordinate")?,
+                next_part("a Y coordinate")?,
+                next_part("a Z coordinate")?,
+            ))
         })
         .collect()
 }
 
-fn circuit_size(
-    point: usize,
-    flooded: &mut FxHashSet<usize>,
-    circuits: &mut FxHashMap<usize, Rc<RefCell<Point>>>,
-) -> usize {
-    if flooded.contains(&point) {
-        return 0;
-    }
-    flooded.insert(point);
-    let point_obj = circuits.get(&point).unwrap().clone();
-    let others = &point_obj.borrow().connections;
-    let mut sum = 0;
-    for other in others {
-        sum += circuit_size(*other, flooded, circuits);
-    }
-    sum + 1
-}
-
-fn connect_circuits(input: &str, connections: usize) -> (usize, i64) {
-    let junctions = parse_junctions(input);
+// Joins junctions closest first, Kruskal style. Returns the product of the three biggest circuits
+// after `connections` pairs have been looked at, and the product of the X coordinates of the pair
+// that finally joins everything into one circuit
+fn connect_circuits(junctions: &[Coor3D], connections: usize) -> (usize, i64) {
     let num_points = junctions.len();
     let mut abs_pairs: Vec<(i64, usize, usize)> = Vec::with_capacity(num_points * num_points / 2);
-    for first_idx in 0..num_points {
-        let first = junctions.get(first_idx).unwrap();
-        for second_idx in first_idx + 1..num_points {
-            let second = junctions.get(second_idx).unwrap();
-            abs_pairs.push((first.abs_dist_from(&second), first_idx, second_idx));
+    for (first_idx, first) in junctions.iter().enumerate() {
+        for (second_idx, second) in junctions.iter().enumerate().skip(first_idx + 1) {
+            abs_pairs.push((first.abs_dist_from(second), first_idx, second_idx));
         }
     }
-    abs_pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    abs_pairs.sort_unstable();
 
-    let mut circuit_tracker = FxHashMap::<usize, Rc<RefCell<Point>>>::default();
+    let mut circuits = DisjointSet::new(num_points);
     let mut part1 = 0;
-    for (iteration, (_, a_idx, b_idx)) in abs_pairs.iter().enumerate() {
+    for (iteration, &(_, a_idx, b_idx)) in abs_pairs.iter().enumerate() {
         if iteration == connections {
-            let mut flooded = FxHashSet::<usize>::default();
-            let mut circuit_sizes = Vec::new();
-
-            let points: Vec<usize> = circuit_tracker.keys().cloned().collect();
-            for point in points {
-                circuit_sizes.push(circuit_size(point, &mut flooded, &mut circuit_tracker));
-            }
-            circuit_sizes.sort_by(|a, b| b.cmp(a)); // Reverse sort
-
-            part1 = circuit_sizes[0..3].iter().product()
+            let mut circuit_sizes = circuits.component_sizes();
+            circuit_sizes.sort_unstable_by(|a, b| b.cmp(a));
+            part1 = circuit_sizes.iter().take(3).product();
         }
 
-        let point_a = circuit_tracker
-            .entry(*a_idx)
-            .or_insert_with(|| {
-                Rc::new(RefCell::new(Point {
-                    connections: FxHashSet::default(),
-                }))
-            })
-            .clone();
-        let point_b = circuit_tracker.entry(*b_idx).or_insert_with(|| {
-            Rc::new(RefCell::new(Point {
-                connections: FxHashSet::default(),
-            }))
-        });
-        point_a.borrow_mut().connections.insert(*b_idx);
-        point_b.borrow_mut().connections.insert(*a_idx);
-
-        // Once the circuit tracker has added each point at least once, we can start looking for the full connection
-        if circuit_tracker.len() == num_points {
-            let (first_point, _) = circuit_tracker.iter().next().unwrap();
-            if num_points
-                == circuit_size(
-                    *first_point,
-                    &mut FxHashSet::<usize>::default(),
-                    &mut circuit_tracker,
-                )
-            {
-                // Connected all sub-graphs
-                let part2 = junctions.get(*a_idx).unwrap().0 * junctions.get(*b_idx).unwrap().0;
-                return (part1, part2);
-            }
+        if circuits.union(a_idx, b_idx) && circuits.component_count() == 1 {
+            // Connected all sub-graphs
+            let part2 = junctions[a_idx].0 * junctions[b_idx].0;
+            return (part1, part2);
         }
     }
 
-    (0, 0)
+    (part1, 0)
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Coor3D>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_junctions(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        connect_circuits(input, 1000).0
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        connect_circuits(input, 1000).1
+    }
 }
 
 #[test]
 fn part_1() {
-    let (three_largest_sample, first_total_connection_sample) = connect_circuits(SAMPLE, 10);
+    let (three_largest_sample, first_total_connection_sample) =
+        connect_circuits(&parse_junctions(SAMPLE).unwrap(), 10);
     assert_eq!(40, three_largest_sample);
     assert_eq!(25272, first_total_connection_sample);
 
-    let (three_largest_input, first_total_connection_input) = connect_circuits(INPUT, 1000);
+    let Some(input) = test_input(2025, 8) else {
+        return;
+    };
+    let (three_largest_input, first_total_connection_input) =
+        connect_circuits(&parse_junctions(&input).unwrap(), 1000);
     assert_eq!(83520, three_largest_input);
     assert_eq!(1_131_823_407, first_total_connection_input);
 }
@@ -1,8 +1,17 @@
-use std::isize;
+use std::fmt::Display;
 
 use logos::Logos;
 
-const SAMPLE: &'static str = "
+#[cfg(test)]
+use crate::util::input::test_input;
+use crate::util::{
+    Direction,
+    parse::{ParseError, next_token_or_end},
+    solution::Solution,
+};
+
+#[cfg(test)]
+const SAMPLE: &str = "
 7,1
 11,1
 11,7
@@ -13,33 +22,49 @@ const SAMPLE: &'static str = "
 7,3
 ";
 
-const INPUT: &'static str = include_str!("./inputs/day9.txt");
-
-struct Tile(isize, isize);
+pub struct Tile(isize, isize);
 
 #[derive(Logos)]
 #[logos(skip "\n")]
 enum Token {
     #[regex("[0-9]+,[0-9]+", |lex| {
-        let mut values = lex.slice().split(",");
-        Tile(values.next().unwrap().parse().unwrap(), values.next().unwrap().parse().unwrap())
+        let (x, y) = lex.slice().split_once(',')?;
+        Some(Tile(x.parse().ok()?, y.parse().ok()?))
     })]
     TileToken(Tile),
 }
 
-fn parse_tiles(input: &str) -> Vec<Tile> {
-    let tokens = Token::lexer(input);
-    tokens
-        .map(|t| {
-            let Token::TileToken(tile) = t.unwrap();
-            tile
-        })
-        .collect()
-}
+fn parse_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
+    let mut tiles = Vec::new();
+    let mut spans = Vec::new();
+    let mut tokens = Token::lexer(input);
+    while let Some(Token::TileToken(tile)) = next_token_or_end(&mut tokens, "a tile like `7,1`")? {
+        tiles.push(tile);
+        spans.push(tokens.span());
+    }
+    if tiles.len() < 4 {
+        return Err(ParseError::end_of_input(input, "a tile like `7,1`"));
+    }
 
-fn largest_rect(input: &str) -> isize {
-    let tiles = parse_tiles(input);
+    // The tiles have to trace a loop whose lines switch between horizontal and vertical
+    for idx in 0..tiles.len() {
+        let next = (idx + 1) % tiles.len();
+        let Tile(ax, ay) = tiles[idx];
+        let Tile(bx, by) = tiles[next];
+        let Tile(cx, _) = tiles[(idx + 2) % tiles.len()];
+        if (ax == bx) == (ay == by) || (ax == bx) == (bx == cx) {
+            return Err(ParseError::new(
+                input,
+                spans[next].clone(),
+                "a tile turning a corner in line with the tiles either side",
+            ));
+        }
+    }
+
+    Ok(tiles)
+}
 
+fn largest_rect(tiles: &[Tile]) -> isize {
     let mut largest_area = 0;
     for (idx, Tile(ax, ay)) in tiles.iter().enumerate() {
         for Tile(bx, by) in &tiles[idx + 1..] {
@@ -50,63 +75,16 @@ fn largest_rect(input: &str) -> isize {
     largest_area
 }
 
-#[derive(PartialEq, Eq, Clone, Copy)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Dir {
-    fn is_clockwise(&self, prev: Self) -> bool {
-        match self {
-            Self::Up => prev == Self::Left,
-            Self::Down => prev == Self::Right,
-            Self::Left => prev == Self::Down,
-            Self::Right => prev == Self::Up,
-        }
-    }
-
-    fn transform(&self, clockwise: bool) -> Self {
-        match self {
-            Self::Up => {
-                if clockwise {
-                    Self::Right
-                } else {
-                    Self::Left
-                }
-            }
-            Self::Down => {
-                if clockwise {
-                    Self::Left
-                } else {
-                    Self::Right
-                }
-            }
-            Self::Left => {
-                if clockwise {
-                    Self::Up
-                } else {
-                    Self::Down
-                }
-            }
-            Self::Right => {
-                if clockwise {
-                    Self::Down
-                } else {
-                    Self::Up
-                }
-            }
-        }
-    }
+// Whether turning from `prev` to `next` is a right turn
+fn is_clockwise(prev: Direction, next: Direction) -> bool {
+    prev.turn_right() == next
 }
 
 struct Line<'a> {
     start: &'a Tile,
     end: &'a Tile,
-    dir: Dir,
-    outside: Dir,
+    dir: Direction,
+    outside: Direction,
 }
 
 impl<'a> Line<'a> {
@@ -115,15 +93,23 @@ impl<'a> Line<'a> {
             start: t1,
             end: t2,
             // For now, these are dummy values
-            dir: Dir::Down,
-            outside: Dir::Down,
+            dir: Direction::Down,
+            outside: Direction::Down,
         };
 
         // These calculations consider pixels like a digital image where (0, 0) is the top left corner
         s.dir = if t1.0 == t2.0 {
-            if t1.1 < t2.1 { Dir::Down } else { Dir::Up }
+            if t1.1 < t2.1 {
+                Direction::Down
+            } else {
+                Direction::Up
+            }
         } else {
-            if t1.0 < t2.0 { Dir::Right } else { Dir::Left }
+            if t1.0 < t2.0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
         };
 
         // The `outside` attribute is still nonsense for now, it will be calculated later
@@ -132,35 +118,37 @@ impl<'a> Line<'a> {
 
     fn min_scalar(&self) -> isize {
         match self.dir {
-            Dir::Right => self.start.0,
-            Dir::Down => self.start.1,
-            Dir::Left => self.end.0,
-            Dir::Up => self.end.1,
+            Direction::Right => self.start.0,
+            Direction::Down => self.start.1,
+            Direction::Left => self.end.0,
+            Direction::Up => self.end.1,
         }
     }
 
     fn max_scalar(&self) -> isize {
         match self.dir {
-            Dir::Right => self.end.0,
-            Dir::Down => self.end.1,
-            Dir::Left => self.start.0,
-            Dir::Up => self.start.1,
+            Direction::Right => self.end.0,
+            Direction::Down => self.end.1,
+            Direction::Left => self.start.0,
+            Direction::Up => self.start.1,
         }
     }
 
     fn is_vertical(&self) -> bool {
-        match self.dir {
-            Dir::Up | Dir::Down => true,
-            _ => false,
-        }
+        matches!(self.dir, Direction::Up | Direction::Down)
     }
 
-    fn set_outside(&mut self, prev_line_dir: Dir, prev_line_outside: Dir) {
-        self.outside = prev_line_outside.transform(self.dir.is_clockwise(prev_line_dir));
+    // The outside turns the same way the line does
+    fn set_outside(&mut self, prev_line_dir: Direction, prev_line_outside: Direction) {
+        self.outside = if is_clockwise(prev_line_dir, self.dir) {
+            prev_line_outside.turn_right()
+        } else {
+            prev_line_outside.turn_left()
+        };
     }
 }
 
-fn to_lines<'a>(points: &'a Vec<Tile>) -> Vec<Line<'a>> {
+fn to_lines<'a>(points: &'a [Tile]) -> Vec<Line<'a>> {
     let mut lines = Vec::with_capacity(points.len());
     let first_tile = points.first().unwrap();
     let mut prev_tile = points.first().unwrap();
@@ -225,8 +213,8 @@ impl Rect {
         // so we do an "outside" check. If "outside" is to the right, then the line intersects as long as it's
         // not on the right edge. If "outside" is to the left, then the line intersects as long as it's not
         // on the left edge.
-        (line.outside == Dir::Right && line.start.0 != self.max_x)
-            || (line.outside == Dir::Left && line.start.0 != self.min_x)
+        (line.outside == Direction::Right && line.start.0 != self.max_x)
+            || (line.outside == Direction::Left && line.start.0 != self.min_x)
         // Now figure out the rest...
     }
 
@@ -240,8 +228,8 @@ impl Rect {
             return false;
         }
 
-        (line.outside == Dir::Down && line.start.1 != self.max_y)
-            || (line.outside == Dir::Up && line.start.1 != self.min_y)
+        (line.outside == Direction::Down && line.start.1 != self.max_y)
+            || (line.outside == Direction::Up && line.start.1 != self.min_y)
     }
 }
 
@@ -251,17 +239,14 @@ fn exclusive_range_overlap(start_1: isize, end_1: isize, start_2: isize, end_2:
 
 // I've made a few guarantees about the input.
 // - Lines always switch between horizontal and vertical, which is nice
-fn largest_internal_rect(input: &str) -> isize {
-    let tiles = parse_tiles(input);
-    let mut lines = to_lines(&tiles);
+fn largest_internal_rect(tiles: &[Tile]) -> isize {
+    let mut lines = to_lines(tiles);
     let mut leftmost_line_x = isize::MAX;
     let mut leftmost_line_idx = 0; // Looking for the leftmost vertical line
     for (idx, line) in lines.iter().enumerate() {
-        if line.is_vertical() {
-            if line.start.0 < leftmost_line_x {
-                leftmost_line_x = line.start.0;
-                leftmost_line_idx = idx;
-            }
+        if line.is_vertical() && line.start.0 < leftmost_line_x {
+            leftmost_line_x = line.start.0;
+            leftmost_line_idx = idx;
         }
     }
 
@@ -269,11 +254,11 @@ fn largest_internal_rect(input: &str) -> isize {
     // so that we can tell which side is outside
     let leftmost_line = lines.get_mut(leftmost_line_idx).unwrap();
     match leftmost_line.dir {
-        Dir::Up => {
-            leftmost_line.outside = Dir::Left;
+        Direction::Up => {
+            leftmost_line.outside = Direction::Left;
         }
-        Dir::Down => {
-            leftmost_line.outside = Dir::Right;
+        Direction::Down => {
+            leftmost_line.outside = Direction::Right;
         }
         _ => unreachable!(),
     }
@@ -304,18 +289,16 @@ fn largest_internal_rect(input: &str) -> isize {
         }
     }
 
-    vertical_lines.sort_by(|l1, l2| l1.start.0.cmp(&l2.start.0));
-    horizontal_lines.sort_by(|l1, l2| l1.start.1.cmp(&l2.start.1));
+    vertical_lines.sort_by_key(|l1| l1.start.0);
+    horizontal_lines.sort_by_key(|l1| l1.start.1);
 
     let mut largest_area = 0;
     for (idx, t1) in tiles.iter().enumerate() {
         for t2 in &tiles[idx + 1..] {
             let rect = Rect::new(t1, t2);
             let area = rect.area();
-            if area > largest_area {
-                if !rect.contains_lines(&vertical_lines, &horizontal_lines) {
-                    largest_area = area;
-                }
+            if area > largest_area && !rect.contains_lines(&vertical_lines, &horizontal_lines) {
+                largest_area = area;
             }
         }
     }
@@ -323,14 +306,54 @@ fn largest_internal_rect(input: &str) -> isize {
     largest_area
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Tile>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_tiles(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        largest_rect(input)
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        largest_internal_rect(input)
+    }
+}
+
 #[test]
 fn part_1() {
-    assert_eq!(50, largest_rect(SAMPLE));
-    assert_eq!(4_750_092_396, largest_rect(INPUT));
+    assert_eq!(50, largest_rect(&parse_tiles(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2025, 9) else {
+        return;
+    };
+    assert_eq!(4_750_092_396, largest_rect(&parse_tiles(&input).unwrap()));
 }
 
 #[test]
 fn part_2() {
-    assert_eq!(24, largest_internal_rect(SAMPLE));
-    assert_eq!(1_468_516_555, largest_internal_rect(INPUT));
+    assert_eq!(24, largest_internal_rect(&parse_tiles(SAMPLE).unwrap()));
+
+    let Some(input) = test_input(2025, 9) else {
+        return;
+    };
+    assert_eq!(
+        1_468_516_555,
+        largest_internal_rect(&parse_tiles(&input).unwrap())
+    );
+}
+
+#[test]
+fn bad_input() {
+    let err = parse_tiles("7,1\n11,1\n11,7").err().unwrap();
+    assert_eq!("a tile like `7,1`", err.expected);
+    let err = parse_tiles("7,1\n11,1\n11,7\n9,8\n").err().unwrap();
+    assert_eq!((4, 1), (err.line, err.column));
+    let err = parse_tiles("7,1\n9,1\n11,1\n11,7\n7,7").err().unwrap();
+    assert_eq!((2, 1), (err.line, err.column));
+    assert!(parse_tiles("\n").is_err());
 }
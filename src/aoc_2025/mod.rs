@@ -1,35 +1,12 @@
-#[cfg(test)]
 pub mod day1;
-
-#[cfg(test)]
+pub mod day10;
+pub mod day11;
+pub mod day12;
 pub mod day2;
-
-#[cfg(test)]
 pub mod day3;
-
-#[cfg(test)]
 pub mod day4;
-
-#[cfg(test)]
 pub mod day5;
-
-#[cfg(test)]
 pub mod day6;
-
-#[cfg(test)]
 pub mod day7;
-
-#[cfg(test)]
 pub mod day8;
-
-#[cfg(test)]
 pub mod day9;
-
-#[cfg(test)]
-pub mod day10;
-
-#[cfg(test)]
-pub mod day11;
-
-#[cfg(test)]
-pub mod day12;
@@ -0,0 +1,360 @@
+use std::{env, fmt::Write, fs, path::PathBuf, process::ExitCode};
+
+use aoc_2025::{
+    registry::{self, Day},
+    util::{
+        answers::{Answers, Verdict},
+        bench::{Stats, Timings},
+        input::{InputError, InputProvider},
+        solution::Answer,
+    },
+};
+
+const USAGE: &str = "Usage:
+  aoc run <year> [day] [--part <1|2>] [--input <path>] [--input-dir <dir>] [--answers <path>] [--slow]
+  aoc bench [year] [day] [--warmup <n>] [--runs <n>] [--json <path|->] [--input-dir <dir>] [--slow]";
+
+#[derive(PartialEq, Eq)]
+enum Command {
+    Run,
+    Bench,
+}
+
+struct Args {
+    command: Command,
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    input_dir: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    warmup: usize,
+    runs: usize,
+    json: Option<PathBuf>,
+    slow: bool,
+}
+
+#[derive(Default)]
+struct Tally {
+    pass: usize,
+    fail: usize,
+    unknown: usize,
+    skipped: usize,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("bench") => Command::Bench,
+        Some(cmd) => return Err(format!("Unknown command `{cmd}`")),
+        None => return Err(String::from("Missing command")),
+    };
+
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut input_dir = None;
+    let mut answers = None;
+    let mut warmup = 1;
+    let mut runs = 5;
+    let mut json = None;
+    let mut slow = cfg!(feature = "slow");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("Missing value for --part")?;
+                match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(format!("Invalid part `{value}`")),
+                }
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or("Missing value for --input")?;
+                input = Some(PathBuf::from(value));
+            }
+            "--input-dir" => {
+                let value = args.next().ok_or("Missing value for --input-dir")?;
+                input_dir = Some(PathBuf::from(value));
+            }
+            "--answers" | "-a" => {
+                let value = args.next().ok_or("Missing value for --answers")?;
+                answers = Some(PathBuf::from(value));
+            }
+            "--warmup" => {
+                let value = args.next().ok_or("Missing value for --warmup")?;
+                warmup = value
+                    .parse()
+                    .map_err(|_| format!("Invalid warmup `{value}`"))?;
+            }
+            "--runs" => {
+                let value = args.next().ok_or("Missing value for --runs")?;
+                runs = match value.parse() {
+                    Ok(0) | Err(_) => return Err(format!("Invalid runs `{value}`")),
+                    Ok(runs) => runs,
+                };
+            }
+            "--slow" => slow = true,
+            "--json" => {
+                let value = args.next().ok_or("Missing value for --json")?;
+                json = Some(PathBuf::from(value));
+            }
+            _ if year.is_none() => {
+                year = Some(arg.parse().map_err(|_| format!("Invalid year `{arg}`"))?);
+            }
+            _ if day.is_none() => {
+                day = Some(arg.parse().map_err(|_| format!("Invalid day `{arg}`"))?);
+            }
+            _ => return Err(format!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    // Benchmarks default to every registered day
+    if command == Command::Run && year.is_none() {
+        return Err(String::from("Missing year"));
+    }
+    if input.is_some() && day.is_none() {
+        return Err(String::from("--input requires a day"));
+    }
+
+    Ok(Args {
+        command,
+        year,
+        day,
+        part,
+        input,
+        input_dir,
+        answers,
+        warmup,
+        runs,
+        json,
+        slow,
+    })
+}
+
+fn select_days(args: &Args) -> Result<Vec<&'static Day>, String> {
+    let days: Vec<&Day> = match (args.year, args.day) {
+        (Some(year), Some(day)) => vec![
+            registry::find(year, day).ok_or_else(|| format!("No solution for {year} Day {day}"))?,
+        ],
+        (Some(year), None) => registry::DAYS
+            .iter()
+            .filter(|day| day.year == year)
+            .collect(),
+        (None, _) => registry::DAYS.iter().collect(),
+    };
+    match (days.is_empty(), args.year) {
+        (true, Some(year)) => Err(format!("No solutions for {year}")),
+        _ => Ok(days),
+    }
+}
+
+// The flag takes priority over the environment variable
+fn input_provider(args: &Args) -> InputProvider {
+    match &args.input_dir {
+        Some(dir) => InputProvider::new(Some(dir.clone())),
+        None => InputProvider::from_env(),
+    }
+}
+
+// An explicit path wins, then the inputs directory, then whatever is written in the source
+fn load_input(
+    day: &Day,
+    provider: &InputProvider,
+    path: Option<&PathBuf>,
+) -> Result<String, String> {
+    if let Some(path) = path {
+        return fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {err}", path.display()));
+    }
+
+    match (provider.load(day.year, day.day), day.input) {
+        (Ok(input), _) => Ok(input),
+        (Err(InputError::Missing(_)), Some(input)) => Ok(String::from(input)),
+        (Err(err), _) => Err(err.to_string()),
+    }
+}
+
+fn check_answer(day: &Day, part: u8, answer: Option<Answer>, answers: &Answers, tally: &mut Tally) {
+    let Some(Answer { value, elapsed }) = answer else {
+        return;
+    };
+
+    let verdict = answers.check(day.year, day.day, part, &value);
+    match verdict {
+        Verdict::Pass => tally.pass += 1,
+        Verdict::Fail(_) => tally.fail += 1,
+        Verdict::Unknown => tally.unknown += 1,
+    }
+    println!("  Part {part}: {value} ({elapsed:.2?}) [{verdict}]");
+}
+
+fn run_day(
+    day: &Day,
+    provider: &InputProvider,
+    answers: &Answers,
+    args: &Args,
+    tally: &mut Tally,
+) -> Result<(), String> {
+    if args.part == Some(2) && !day.has_part_2 {
+        return Err(format!("{} Day {} has no part 2", day.year, day.day));
+    }
+    let input = load_input(day, provider, args.input.as_ref())?;
+
+    let report = (day.run)(&input, args.part, args.slow).map_err(|err| err.to_string())?;
+    println!(
+        "{} Day {} (parsed in {:.2?})",
+        day.year, day.day, report.parse
+    );
+    check_answer(day, 1, report.part_1, answers, tally);
+    check_answer(day, 2, report.part_2, answers, tally);
+    if report.skipped_part_2 {
+        tally.skipped += 1;
+        println!("  Part 2: skipped as slow, use --slow to run it");
+    }
+
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let days = select_days(args)?;
+    let provider = input_provider(args);
+    let answers = match &args.answers {
+        Some(path) if !path.exists() => {
+            return Err(format!("No answers found at {}", path.display()));
+        }
+        Some(path) => Answers::load(path),
+        None => Answers::load(&provider.answers_path()),
+    }
+    .map_err(|err| err.to_string())?;
+
+    // When running a whole year, a missing input shouldn't stop the remaining days
+    let mut tally = Tally::default();
+    for day in days {
+        match run_day(day, &provider, &answers, args, &mut tally) {
+            Err(err) if args.day.is_none() => eprintln!("{} Day {}: {err}", day.year, day.day),
+            result => result?,
+        }
+    }
+
+    println!(
+        "{} passed, {} failed, {} unknown, {} skipped",
+        tally.pass, tally.fail, tally.unknown, tally.skipped
+    );
+    if tally.fail > 0 {
+        return Err(format!("{} answers did not match", tally.fail));
+    }
+    Ok(())
+}
+
+fn format_stats(stats: Option<&Stats>) -> String {
+    stats.map_or_else(|| String::from("-"), |s| format!("{:.2?}", s.median))
+}
+
+fn skipped_part_2(day: &Day, timings: &Timings) -> bool {
+    day.has_part_2 && timings.part_2.is_none()
+}
+
+fn print_table(results: &[(&Day, Timings)]) {
+    println!(
+        "{:<9} {:>12} {:>12} {:>12} {:>12}",
+        "Day", "Parse", "Part 1", "Part 2", "Total"
+    );
+    for (day, timings) in results {
+        println!(
+            "{:<9} {:>12} {:>12} {:>12} {:>12}",
+            format!("{} {}", day.year, day.day),
+            format_stats(Some(&timings.parse)),
+            format_stats(Some(&timings.part_1)),
+            match skipped_part_2(day, timings) {
+                true => String::from("skipped"),
+                false => format_stats(timings.part_2.as_ref()),
+            },
+            format!("{:.2?}", timings.total()),
+        );
+    }
+}
+
+fn stats_json(stats: Option<&Stats>) -> String {
+    match stats {
+        Some(s) => format!(
+            r#"{{"min_ns": {}, "median_ns": {}, "mean_ns": {}}}"#,
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.mean.as_nanos()
+        ),
+        None => String::from("null"),
+    }
+}
+
+fn to_json(results: &[(&Day, Timings)]) -> String {
+    let mut json = String::from("[\n");
+    for (idx, (day, timings)) in results.iter().enumerate() {
+        let separator = if idx + 1 < results.len() { "," } else { "" };
+        writeln!(
+            json,
+            r#"  {{"year": {}, "day": {}, "parse": {}, "part_1": {}, "part_2": {}, "skipped_part_2": {}, "total_ns": {}}}{separator}"#,
+            day.year,
+            day.day,
+            stats_json(Some(&timings.parse)),
+            stats_json(Some(&timings.part_1)),
+            stats_json(timings.part_2.as_ref()),
+            skipped_part_2(day, timings),
+            timings.total().as_nanos(),
+        )
+        .unwrap();
+    }
+    json.push(']');
+    json
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let days = select_days(args)?;
+    let provider = input_provider(args);
+
+    let mut results = Vec::new();
+    for day in days {
+        let timings = load_input(day, &provider, args.input.as_ref()).and_then(|input| {
+            (day.bench)(&input, args.warmup, args.runs, args.slow).map_err(|err| err.to_string())
+        });
+        match timings {
+            Ok(timings) => results.push((day, timings)),
+            Err(err) => eprintln!("Skipping {} Day {}: {err}", day.year, day.day),
+        }
+    }
+
+    // Slowest first, since those are the ones worth looking at
+    results.sort_by_key(|(_, timings)| std::cmp::Reverse(timings.total()));
+    print_table(&results);
+
+    match args.json.as_deref() {
+        Some(path) if path.as_os_str() == "-" => println!("{}", to_json(&results)),
+        Some(path) => fs::write(path, to_json(&results))
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))?,
+        None => {}
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match args.command {
+        Command::Run => run(&args),
+        Command::Bench => bench(&args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
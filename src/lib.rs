@@ -1,13 +1,8 @@
 pub mod util;
 
-#[cfg(test)]
 pub mod aoc_2015;
-
-#[cfg(test)]
 pub mod aoc_2016;
-
-#[cfg(test)]
 pub mod aoc_2019;
-
-#[cfg(test)]
 pub mod aoc_2025;
+
+pub mod registry;
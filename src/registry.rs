@@ -0,0 +1,157 @@
+use crate::{
+    aoc_2015, aoc_2016, aoc_2019, aoc_2025,
+    util::{
+        bench::{Timings, bench},
+        parse::ParseError,
+        solution::{Report, Solution, run},
+    },
+};
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub input: Option<&'static str>,
+    pub has_part_2: bool,
+    pub slow_part_2: bool,
+    // Parses the input without solving anything, to check it's well formed
+    pub validate: fn(&str) -> Result<(), ParseError>,
+    pub run: fn(&str, Option<u8>, bool) -> Result<Report, ParseError>,
+    pub bench: fn(&str, usize, usize, bool) -> Result<Timings, ParseError>,
+}
+
+fn validate<S: Solution>(input: &str) -> Result<(), ParseError> {
+    S::parse(input).map(drop)
+}
+
+const fn day<S: Solution>(year: u16, day: u8) -> Day {
+    Day {
+        year,
+        day,
+        input: S::INPUT,
+        has_part_2: S::HAS_PART_2,
+        slow_part_2: S::SLOW_PART_2,
+        validate: validate::<S>,
+        run: run::<S>,
+        bench: bench::<S>,
+    }
+}
+
+pub static DAYS: &[Day] = &[
+    day::<aoc_2015::day1::Day1>(2015, 1),
+    day::<aoc_2015::day2::Day2>(2015, 2),
+    day::<aoc_2015::day3::Day3>(2015, 3),
+    day::<aoc_2015::day4::Day4>(2015, 4),
+    day::<aoc_2015::day5::Day5>(2015, 5),
+    day::<aoc_2015::day6::Day6>(2015, 6),
+    day::<aoc_2015::day7::Day7>(2015, 7),
+    day::<aoc_2015::day8::Day8>(2015, 8),
+    day::<aoc_2015::day9::Day9>(2015, 9),
+    day::<aoc_2015::day10::Day10>(2015, 10),
+    day::<aoc_2015::day11::Day11>(2015, 11),
+    day::<aoc_2015::day12::Day12>(2015, 12),
+    day::<aoc_2015::day13::Day13>(2015, 13),
+    day::<aoc_2015::day14::Day14>(2015, 14),
+    day::<aoc_2015::day15::Day15>(2015, 15),
+    day::<aoc_2015::day16::Day16>(2015, 16),
+    day::<aoc_2015::day17::Day17>(2015, 17),
+    day::<aoc_2015::day18::Day18>(2015, 18),
+    day::<aoc_2015::day19::Day19>(2015, 19),
+    day::<aoc_2015::day20::Day20>(2015, 20),
+    day::<aoc_2015::day21::Day21>(2015, 21),
+    day::<aoc_2015::day22::Day22>(2015, 22),
+    day::<aoc_2015::day23::Day23>(2015, 23),
+    day::<aoc_2015::day24::Day24>(2015, 24),
+    day::<aoc_2015::day25::Day25>(2015, 25),
+    day::<aoc_2016::day1::Day1>(2016, 1),
+    day::<aoc_2016::day2::Day2>(2016, 2),
+    day::<aoc_2016::day3::Day3>(2016, 3),
+    day::<aoc_2016::day4::Day4>(2016, 4),
+    day::<aoc_2016::day5::Day5>(2016, 5),
+    day::<aoc_2016::day6::Day6>(2016, 6),
+    day::<aoc_2016::day7::Day7>(2016, 7),
+    day::<aoc_2016::day8::Day8>(2016, 8),
+    day::<aoc_2016::day9::Day9>(2016, 9),
+    day::<aoc_2016::day10::Day10>(2016, 10),
+    day::<aoc_2016::day11::Day11>(2016, 11),
+    day::<aoc_2016::day12::Day12>(2016, 12),
+    day::<aoc_2016::day13::Day13>(2016, 13),
+    day::<aoc_2016::day14::Day14>(2016, 14),
+    day::<aoc_2016::day15::Day15>(2016, 15),
+    day::<aoc_2016::day16::Day16>(2016, 16),
+    day::<aoc_2016::day17::Day17>(2016, 17),
+    day::<aoc_2016::day18::Day18>(2016, 18),
+    day::<aoc_2016::day19::Day19>(2016, 19),
+    day::<aoc_2016::day20::Day20>(2016, 20),
+    day::<aoc_2016::day21::Day21>(2016, 21),
+    day::<aoc_2016::day22::Day22>(2016, 22),
+    day::<aoc_2016::day23::Day23>(2016, 23),
+    day::<aoc_2016::day24::Day24>(2016, 24),
+    day::<aoc_2016::day25::Day25>(2016, 25),
+    day::<aoc_2019::day1::Day1>(2019, 1),
+    day::<aoc_2019::day2::Day2>(2019, 2),
+    day::<aoc_2019::day3::Day3>(2019, 3),
+    day::<aoc_2019::day4::Day4>(2019, 4),
+    day::<aoc_2019::day5::Day5>(2019, 5),
+    day::<aoc_2019::day6::Day6>(2019, 6),
+    day::<aoc_2019::day7::Day7>(2019, 7),
+    day::<aoc_2019::day8::Day8>(2019, 8),
+    day::<aoc_2019::day9::Day9>(2019, 9),
+    day::<aoc_2019::day10::Day10>(2019, 10),
+    day::<aoc_2025::day1::Day1>(2025, 1),
+    day::<aoc_2025::day2::Day2>(2025, 2),
+    day::<aoc_2025::day3::Day3>(2025, 3),
+    day::<aoc_2025::day4::Day4>(2025, 4),
+    day::<aoc_2025::day5::Day5>(2025, 5),
+    day::<aoc_2025::day6::Day6>(2025, 6),
+    day::<aoc_2025::day7::Day7>(2025, 7),
+    day::<aoc_2025::day8::Day8>(2025, 8),
+    day::<aoc_2025::day9::Day9>(2025, 9),
+    day::<aoc_2025::day10::Day10>(2025, 10),
+    day::<aoc_2025::day11::Day11>(2025, 11),
+    day::<aoc_2025::day12::Day12>(2025, 12),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+#[test]
+fn days_are_unique_and_in_order() {
+    for pair in DAYS.windows(2) {
+        assert!((pair[0].year, pair[0].day) < (pair[1].year, pair[1].day));
+    }
+    assert!(find(2016, 12).is_some());
+    assert!(find(2019, 11).is_none());
+}
+
+#[test]
+fn answers_match_registered_days() {
+    use crate::util::{answers::Answers, input::InputProvider};
+
+    let answers = Answers::load(&InputProvider::new(None).answers_path()).unwrap();
+    assert_eq!(Some("317993"), answers.expected(2016, 12, 1));
+    for day in DAYS {
+        assert!(day.has_part_2 || answers.expected(day.year, day.day, 2).is_none());
+    }
+}
+
+#[test]
+fn bad_input_is_rejected() {
+    for day in DAYS {
+        for input in ["", "\n", " \n\n"] {
+            assert!(
+                (day.validate)(input).is_err(),
+                "{} day {} accepted {input:?}",
+                day.year,
+                day.day
+            );
+        }
+        // A truncated input might still be well formed, but parsing it mustn't panic
+        let input = crate::util::input::test_input(day.year, day.day);
+        if let Some(input) = input.as_deref().or(day.input) {
+            for len in [1, input.len() / 3, input.len() / 2, input.len() - 1] {
+                let _ = (day.validate)(&input[..input.floor_char_boundary(len)]);
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use fxhash::FxHashMap;
+
+// The manifest is a small subset of TOML, one table per day:
+//
+// [2016.12]
+// part_1 = 317993
+// part_2 = "9227647"
+//
+// Values can be bare or quoted, and comments start with `#`
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Unreadable(PathBuf, io::Error),
+    Invalid { line: usize, reason: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable(path, err) => {
+                write!(f, "Could not read answers at {}: {err}", path.display())
+            }
+            Self::Invalid { line, reason } => write!(f, "Invalid answers on line {line}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail(expected) => write!(f, "FAIL, expected {expected}"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Answers {
+    expected: FxHashMap<(u16, u8, u8), String>,
+}
+
+fn parse_header(header: &str) -> Option<(u16, u8)> {
+    let (year, day) = header.split_once('.')?;
+    Some((year.trim().parse().ok()?, day.trim().parse().ok()?))
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    match value.strip_prefix('"') {
+        Some(quoted) => quoted.strip_suffix('"').map(String::from),
+        None => Some(value.replace('_', "")),
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, AnswersError> {
+        let mut expected = FxHashMap::default();
+        let mut current = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let invalid = |reason: &str| AnswersError::Invalid {
+                line: idx + 1,
+                reason: String::from(reason),
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| invalid("unclosed table"))?;
+                current = Some(parse_header(header).ok_or_else(|| invalid("expected [year.day]"))?);
+                continue;
+            }
+
+            let (year, day) =
+                current.ok_or_else(|| invalid("answer outside of a [year.day] table"))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected key = value"))?;
+            let part = match key.trim() {
+                "part_1" => 1,
+                "part_2" => 2,
+                key => return Err(invalid(&format!("unknown key `{key}`"))),
+            };
+            let value = parse_value(value.trim()).ok_or_else(|| invalid("unclosed string"))?;
+            expected.insert((year, day, part), value);
+        }
+
+        Ok(Self { expected })
+    }
+
+    // A missing manifest just means every answer is unknown
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(AnswersError::Unreadable(path.to_path_buf(), err)),
+        }
+    }
+
+    pub fn expected(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.expected.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, year: u16, day: u8, part: u8, value: &str) -> Verdict {
+        match self.expected(year, day, part) {
+            Some(expected) if expected == value => Verdict::Pass,
+            Some(expected) => Verdict::Fail(String::from(expected)),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[test]
+fn parse_and_check() {
+    let answers = Answers::parse(
+        "# Comment\n\n[2016.12]\npart_1 = 317_993\npart_2 = \"9227647\"\n\n[2016.2]\npart_2 = \"67BB9\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(Verdict::Pass, answers.check(2016, 12, 1, "317993"));
+    assert_eq!(Verdict::Pass, answers.check(2016, 12, 2, "9227647"));
+    assert_eq!(
+        Verdict::Fail(String::from("67BB9")),
+        answers.check(2016, 2, 2, "67BB8")
+    );
+    assert_eq!(Verdict::Unknown, answers.check(2016, 2, 1, "18843"));
+
+    assert!(matches!(
+        Answers::parse("part_1 = 3"),
+        Err(AnswersError::Invalid { line: 1, .. })
+    ));
+    assert!(matches!(
+        Answers::parse("[2016.1]\npart_3 = 3"),
+        Err(AnswersError::Invalid { line: 2, .. })
+    ));
+}
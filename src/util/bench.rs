@@ -0,0 +1,90 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::util::{parse::ParseError, solution::Solution};
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let total: Duration = samples.iter().sum();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+        }
+    }
+}
+
+pub struct Timings {
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Option<Stats>,
+}
+
+impl Timings {
+    // Medians are used so a single hiccup doesn't reorder the table
+    pub fn total(&self) -> Duration {
+        self.parse.median
+            + self.part_1.median
+            + self.part_2.as_ref().map_or(Duration::ZERO, |s| s.median)
+    }
+}
+
+// Outputs are dropped after the clock stops so cleanup isn't charged to the solution
+pub fn sample<T>(warmup: usize, runs: usize, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..warmup {
+        black_box(f());
+    }
+
+    let samples = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            let output = black_box(f());
+            let elapsed = start.elapsed();
+            drop(output);
+            elapsed
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+pub fn bench<S: Solution>(
+    input: &str,
+    warmup: usize,
+    runs: usize,
+    slow: bool,
+) -> Result<Timings, ParseError> {
+    // Checked up front so a bad input fails before any time is spent on it
+    let parsed = S::parse(input)?;
+    let parse = sample(warmup, runs, || S::parse(input));
+
+    let part_1 = sample(warmup, runs, || S::part_1(&parsed));
+    let part_2 = (S::HAS_PART_2 && (slow || !S::SLOW_PART_2))
+        .then(|| sample(warmup, runs, || S::part_2(&parsed)));
+
+    Ok(Timings {
+        parse,
+        part_1,
+        part_2,
+    })
+}
+
+#[test]
+fn stats_from_samples() {
+    let stats = Stats::from_samples(vec![
+        Duration::from_millis(5),
+        Duration::from_millis(1),
+        Duration::from_millis(3),
+    ]);
+    assert_eq!(Duration::from_millis(1), stats.min);
+    assert_eq!(Duration::from_millis(3), stats.median);
+    assert_eq!(Duration::from_millis(3), stats.mean);
+}
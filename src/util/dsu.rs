@@ -0,0 +1,106 @@
+// Disjoint sets over the indices `0..len`, for tracking which things have been joined together.
+// Union by size and path compression keep every operation close enough to constant time
+
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    // Each element's parent, with roots pointing at themselves
+    parents: Vec<usize>,
+    // The size of each root's set. Only meaningful for roots
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    // Every element starts off in a set of its own
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    // The root of the set holding `element`, pointing everything on the way straight at it
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while current != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    // Joins the sets holding `a` and `b`, returning false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // The smaller set goes under the bigger one so paths stay short
+        let (big, small) = if self.sizes[a] < self.sizes[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[small] = big;
+        self.sizes[big] += self.sizes[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // How many elements are in the same set as `element`, itself included
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    // How many separate sets there are
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    // The size of every set, in no particular order
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+}
+
+#[test]
+fn joining() {
+    let mut sets = DisjointSet::new(8);
+    assert_eq!(8, sets.component_count());
+    assert!(sets.union(0, 1));
+    assert!(sets.union(2, 3));
+    assert!(sets.union(1, 3));
+    assert!(!sets.union(0, 2));
+    assert!(sets.union(5, 6));
+
+    assert!(sets.same_set(0, 3));
+    assert!(!sets.same_set(0, 5));
+    assert_eq!(4, sets.size_of(2));
+    assert_eq!(2, sets.size_of(6));
+    assert_eq!(1, sets.size_of(7));
+    assert_eq!(4, sets.component_count());
+
+    let mut sizes = sets.component_sizes();
+    sizes.sort_unstable();
+    assert_eq!(vec![1, 1, 2, 4], sizes);
+}
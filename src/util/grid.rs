@@ -0,0 +1,277 @@
+// A rectangular grid addressed by `Pair(x, y)`, with `x` running along a row and `y` down the
+// columns. Cells are stored row by row in one `Vec`, so a whole row can be handed out as a slice
+
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use super::{Pair, parse::ParseError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Pair) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Pair(x as i64, y as i64)))
+            .map(&mut cell)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Reads a character map, one row per line. Blank lines around the map are ignored, but every
+    // row has to be as wide as the first one and every character has to map to a cell
+    pub fn parse(
+        input: &str,
+        expected: &'static str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = input.trim_matches(['\n', '\r']).lines().peekable();
+        let width = match rows.peek() {
+            Some(first) if !first.is_empty() => first.chars().count(),
+            _ => return Err(ParseError::end_of_input(input, "a grid")),
+        };
+
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for row in rows {
+            let mut row_width = 0;
+            for (idx, ch) in row.char_indices() {
+                let text = &row[idx..idx + ch.len_utf8()];
+                if row_width == width {
+                    return Err(ParseError::at(input, text, "the end of the row"));
+                }
+                cells.push(cell(ch).ok_or_else(|| ParseError::at(input, text, expected))?);
+                row_width += 1;
+            }
+            if row_width < width {
+                return Err(ParseError::at(input, &row[row.len()..], expected));
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, Pair(x, y): Pair) -> Option<usize> {
+        let x = usize::try_from(x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    pub fn contains(&self, pos: Pair) -> bool {
+        self.offset(pos).is_some()
+    }
+
+    pub fn get(&self, pos: Pair) -> Option<&T> {
+        self.offset(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pair) -> Option<&mut T> {
+        self.offset(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    // Every position in reading order
+    pub fn positions(&self) -> impl Iterator<Item = Pair> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pair(x as i64, y as i64)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pair, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, which an empty grid would have
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pair> {
+        self.iter()
+            .find_map(|(pos, value)| predicate(value).then_some(pos))
+    }
+
+    // The orthogonal neighbours of `pos` that are on the grid
+    pub fn neighbors4(&self, pos: Pair) -> impl Iterator<Item = Pair> + '_ {
+        pos.neighbors4()
+            .into_iter()
+            .filter(|&next| self.contains(next))
+    }
+
+    // The orthogonal and diagonal neighbours of `pos` that are on the grid
+    pub fn neighbors8(&self, pos: Pair) -> impl Iterator<Item = Pair> + '_ {
+        pos.neighbors8()
+            .into_iter()
+            .filter(|&next| self.contains(next))
+    }
+
+    // Shifts row `y` right by `count` cells, wrapping around
+    pub fn rotate_row(&mut self, y: usize, count: usize) {
+        let row = &mut self.cells[y * self.width..(y + 1) * self.width];
+        row.rotate_right(count % self.width);
+    }
+
+    // Shifts column `x` down by `count` cells, wrapping around
+    pub fn rotate_col(&mut self, x: usize, count: usize)
+    where
+        T: Clone,
+    {
+        let mut column: Vec<T> = (0..self.height)
+            .map(|y| self.cells[y * self.width + x].clone())
+            .collect();
+        column.rotate_right(count % self.height);
+        for (y, value) in column.into_iter().enumerate() {
+            self.cells[y * self.width + x] = value;
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |Pair(x, y)| {
+            self[Pair(y, x)].clone()
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Draws each cell with `draw`, one line per row
+    pub fn render<D: Display>(&self, mut draw: impl FnMut(&T) -> D) -> String {
+        let mut out = String::new();
+        for row in self.rows() {
+            for value in row {
+                out += &draw(value).to_string();
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<Pair> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: Pair) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Pair> for Grid<T> {
+    fn index_mut(&mut self, pos: Pair) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{value}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn lights(picture: &str) -> Grid<bool> {
+    Grid::parse(picture, "`.` or `#`", |ch| match ch {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn parsing() {
+    let grid = Grid::parse("\n123\n456\n", "a digit", |ch| ch.to_digit(10)).unwrap();
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(Some(&1), grid.get(Pair(0, 0)));
+    assert_eq!(Some(&6), grid.get(Pair(2, 1)));
+    assert_eq!(None, grid.get(Pair(3, 0)));
+    assert_eq!(None, grid.get(Pair(0, -1)));
+    assert_eq!("123\n456\n", grid.to_string());
+
+    let err = Grid::parse("123\n4x6", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+    assert_eq!((2, 2), (err.line, err.column));
+    let err = Grid::parse("123\n4567", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+    assert_eq!((2, 4), (err.line, err.column));
+    let err = Grid::parse("123\n45", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+    assert_eq!((2, 3), (err.line, err.column));
+    assert!(Grid::parse("\n", "a digit", |ch| ch.to_digit(10)).is_err());
+}
+
+#[test]
+fn neighbors() {
+    let grid = Grid::new(3, 3, ());
+    assert_eq!(2, grid.neighbors4(Pair(0, 0)).count());
+    assert_eq!(3, grid.neighbors8(Pair(0, 0)).count());
+    assert_eq!(3, grid.neighbors4(Pair(1, 0)).count());
+    assert_eq!(4, grid.neighbors4(Pair(1, 1)).count());
+    assert_eq!(8, grid.neighbors8(Pair(1, 1)).count());
+}
+
+#[test]
+fn rotation_and_transposition() {
+    let mut grid = lights("#..\n...");
+    grid.rotate_row(0, 4);
+    assert_eq!(lights(".#.\n..."), grid);
+    grid.rotate_col(1, 1);
+    assert_eq!(lights("...\n.#."), grid);
+    grid.rotate_col(1, 3);
+    assert_eq!(lights(".#.\n..."), grid);
+
+    let grid = lights("##.\n..#");
+    assert_eq!(lights("#.\n#.\n.#"), grid.transpose());
+    assert_eq!(grid, grid.transpose().transpose());
+    assert_eq!(
+        "##.\n..#\n",
+        grid.render(|&lit| if lit { '#' } else { '.' })
+    );
+}
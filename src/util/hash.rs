@@ -0,0 +1,156 @@
+// MD5 helpers for the puzzles that hash a secret with an increasing number on the end until
+// something interesting turns up. The work is spread over every core in ordered batches, so
+// results still come back in index order and a search can stop as soon as it has what it needs
+
+use std::{collections::VecDeque, num::NonZeroUsize, ops::Range, thread};
+
+use md5::Context;
+
+// Indices handed to each thread per batch. Big enough that spawning threads is noise next to the
+// hashing, small enough that not much is wasted past the last match a search needs
+const BATCH_PER_THREAD: usize = 1024;
+
+pub fn generate_hash(input: &[u8]) -> [u8; 16] {
+    let mut ctx = Context::new();
+    ctx.consume(input);
+    ctx.finalize().0
+}
+
+pub fn generate_padded_hash(base: &[u8], padding: usize) -> [u8; 16] {
+    let mut ctx = Context::new();
+    ctx.consume(base);
+    ctx.consume(padding.to_string().as_bytes());
+    ctx.finalize().0
+}
+
+// The lowercase hex digits of `hash`, which is what gets hashed again when stretching
+pub fn to_hex(hash: [u8; 16]) -> [u8; 32] {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = [0; 32];
+    for (byte, pair) in hash.into_iter().zip(hex.chunks_exact_mut(2)) {
+        pair[0] = DIGITS[(byte >> 4) as usize];
+        pair[1] = DIGITS[(byte & 0x0F) as usize];
+    }
+    hex
+}
+
+// A padded hash that's then hashed `rounds` more times, each time as its hex digits
+pub fn generate_stretched_hash(base: &[u8], padding: usize, rounds: usize) -> [u8; 16] {
+    let mut hash = generate_padded_hash(base, padding);
+    for _ in 0..rounds {
+        hash = generate_hash(&to_hex(hash));
+    }
+    hash
+}
+
+fn thread_count() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// How many indices `parallel_map` works through at once
+pub fn batch_size() -> usize {
+    thread_count() * BATCH_PER_THREAD
+}
+
+// Runs `f` over every index in `range`, split into one contiguous chunk per thread, and returns
+// the results in index order
+pub fn parallel_map<R: Send>(range: Range<usize>, f: impl Fn(usize) -> R + Sync) -> Vec<R> {
+    let chunk = range.len().div_ceil(thread_count()).max(1);
+    let f = &f;
+    thread::scope(|scope| {
+        let workers: Vec<_> = range
+            .clone()
+            .step_by(chunk)
+            .map(|start| {
+                let end = (start + chunk).min(range.end);
+                scope.spawn(move || (start..end).map(f).collect::<Vec<R>>())
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("hash worker panicked"))
+            .collect()
+    })
+}
+
+// Every padding from `start` upwards whose hash `filter` picks something out of, in order. Hashes
+// are worked out a batch at a time, so stopping early (with `next`, `find`, `take` and so on) only
+// wastes the rest of the current batch
+pub struct PaddedMatches<'a, T, F> {
+    base: &'a [u8],
+    next_padding: usize,
+    found: VecDeque<(usize, T)>,
+    filter: F,
+}
+
+pub fn padded_matches<T, F>(base: &[u8], start: usize, filter: F) -> PaddedMatches<'_, T, F>
+where
+    T: Send,
+    F: Fn(&[u8; 16]) -> Option<T> + Sync,
+{
+    PaddedMatches {
+        base,
+        next_padding: start,
+        found: VecDeque::new(),
+        filter,
+    }
+}
+
+impl<T, F> Iterator for PaddedMatches<'_, T, F>
+where
+    T: Send,
+    F: Fn(&[u8; 16]) -> Option<T> + Sync,
+{
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.found.is_empty() {
+            let range = self.next_padding..self.next_padding + batch_size();
+            self.next_padding = range.end;
+            let (base, filter) = (self.base, &self.filter);
+            let batch = parallel_map(range.clone(), |padding| {
+                filter(&generate_padded_hash(base, padding))
+            });
+            self.found.extend(
+                range
+                    .zip(batch)
+                    .filter_map(|(padding, found)| Some((padding, found?))),
+            );
+        }
+        self.found.pop_front()
+    }
+}
+
+#[test]
+fn stretching() {
+    assert_eq!(
+        b"577571be4de9dcce85a041ba0410f29f",
+        &to_hex(generate_padded_hash(b"abc", 0))
+    );
+    assert_eq!(
+        b"a107ff634856bb300138cac6568c0f24",
+        &to_hex(generate_stretched_hash(b"abc", 0, 2016))
+    );
+}
+
+#[test]
+fn ordered_batches() {
+    let squares = parallel_map(3..2000, |x| x * x);
+    assert_eq!(1997, squares.len());
+    assert!(squares.iter().zip(3..).all(|(&square, x)| square == x * x));
+    assert!(parallel_map(5..5, |x| x).is_empty());
+
+    // Matches come back in order and line up with the hashes worked out one at a time
+    let leading_zero = |hash: &[u8; 16]| (hash[0] == 0).then_some(hash[1]);
+    let matches: Vec<_> = padded_matches(b"abc", 0, leading_zero).take(20).collect();
+    let expected: Vec<_> = (0..)
+        .filter_map(|padding| {
+            Some((
+                padding,
+                leading_zero(&generate_padded_hash(b"abc", padding))?,
+            ))
+        })
+        .take(20)
+        .collect();
+    assert_eq!(expected, matches);
+}
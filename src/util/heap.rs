@@ -1,8 +1,26 @@
-use std::{cmp::Ordering, collections::BinaryHeap};
+use std::{cmp::Ordering, collections::BinaryHeap, hash::Hash};
+
+use fxhash::FxHashMap;
 
 // Borrowed from the https://github.com/maneatingape/advent-of-code-rust/blob/main/src/util/heap.rs
 // That repo runs all AofC problems from 2015 to 2025 in under 1 second, so I trust the implementation
 
+// The operations searches need from a min-priority queue, so they can swap one heap for another
+pub trait PriorityQueue<K, V>: Default {
+    fn push(&mut self, key: K, value: V);
+
+    // Takes out the entry with the smallest key
+    fn pop(&mut self) -> Option<(K, V)>;
+
+    fn peek(&self) -> Option<(&K, &V)>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 struct Wrapper<K: Ord, V> {
     key: K,
     value: V,
@@ -32,9 +50,14 @@ impl<K: Ord, V> Ord for Wrapper<K, V> {
     }
 }
 
-#[derive(Default)]
 pub struct MinHeap<K: Ord, V>(BinaryHeap<Wrapper<K, V>>);
 
+impl<K: Ord, V> Default for MinHeap<K, V> {
+    fn default() -> Self {
+        MinHeap(BinaryHeap::new())
+    }
+}
+
 impl<K: Ord, V> MinHeap<K, V> {
     pub fn with_capacity(capacity: usize) -> Self {
         MinHeap(BinaryHeap::with_capacity(capacity))
@@ -48,7 +71,349 @@ impl<K: Ord, V> MinHeap<K, V> {
         self.0.pop().map(|wrapper| (wrapper.key, wrapper.value))
     }
 
-    pub fn peak(&self) -> Option<(&K, &V)> {
+    pub fn peek(&self) -> Option<(&K, &V)> {
         self.0.peek().map(|wrapper| (&wrapper.key, &wrapper.value))
     }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K: Ord, V> PriorityQueue<K, V> for MinHeap<K, V> {
+    fn push(&mut self, key: K, value: V) {
+        self.push(key, value);
+    }
+
+    fn pop(&mut self) -> Option<(K, V)> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<(&K, &V)> {
+        self.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+// A min-heap that knows where each value sits, so a queued value's key can be lowered in place
+// instead of pushing a duplicate and skipping the stale copy when it's popped. Each value can only
+// be queued once
+pub struct IndexedMinHeap<K: Ord, V: Hash + Eq + Clone> {
+    entries: Vec<(K, V)>,
+    positions: FxHashMap<V, usize>,
+}
+
+impl<K: Ord, V: Hash + Eq + Clone> Default for IndexedMinHeap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            positions: FxHashMap::default(),
+        }
+    }
+}
+
+impl<K: Ord, V: Hash + Eq + Clone> IndexedMinHeap<K, V> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            positions: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    // Queues `value`, or lowers its key if it's already queued with a larger one. Returns false if
+    // the queue was left unchanged
+    pub fn push(&mut self, key: K, value: V) -> bool {
+        if self.positions.contains_key(&value) {
+            return self.decrease_key(&value, key);
+        }
+        let idx = self.entries.len();
+        self.positions.insert(value.clone(), idx);
+        self.entries.push((key, value));
+        self.sift_up(idx);
+        true
+    }
+
+    // Returns false if `value` isn't queued, or if `key` wouldn't lower it
+    pub fn decrease_key(&mut self, value: &V, key: K) -> bool {
+        let Some(&idx) = self.positions.get(value) else {
+            return false;
+        };
+        if key >= self.entries[idx].0 {
+            return false;
+        }
+        self.entries[idx].0 = key;
+        self.sift_up(idx);
+        true
+    }
+
+    pub fn contains(&self, value: &V) -> bool {
+        self.positions.contains_key(value)
+    }
+
+    pub fn key_of(&self, value: &V) -> Option<&K> {
+        self.positions.get(value).map(|&idx| &self.entries[idx].0)
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let (key, value) = self.entries.swap_remove(0);
+        self.positions.remove(&value);
+        if !self.entries.is_empty() {
+            self.positions.insert(self.entries[0].1.clone(), 0);
+            self.sift_down(0);
+        }
+        Some((key, value))
+    }
+
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.entries.first().map(|(key, value)| (key, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Empties the queue smallest key first
+    pub fn drain_sorted(&mut self) -> impl Iterator<Item = (K, V)> + '_ {
+        std::iter::from_fn(|| self.pop())
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.entries.swap(a, b);
+        *self.positions.get_mut(&self.entries[a].1).unwrap() = a;
+        *self.positions.get_mut(&self.entries[b].1).unwrap() = b;
+    }
+
+    fn sift_up(&mut self, mut idx: usize) {
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if self.entries[idx].0 >= self.entries[parent].0 {
+                break;
+            }
+            self.swap(idx, parent);
+            idx = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut idx: usize) {
+        loop {
+            let smallest = [2 * idx + 1, 2 * idx + 2]
+                .into_iter()
+                .filter(|&child| child < self.entries.len())
+                .fold(idx, |smallest, child| {
+                    if self.entries[child].0 < self.entries[smallest].0 {
+                        child
+                    } else {
+                        smallest
+                    }
+                });
+            if smallest == idx {
+                break;
+            }
+            self.swap(idx, smallest);
+            idx = smallest;
+        }
+    }
+}
+
+// Values that turn up more than once keep their smallest key
+impl<K: Ord, V: Hash + Eq + Clone, I: IntoIterator<Item = (K, V)>> From<I>
+    for IndexedMinHeap<K, V>
+{
+    fn from(items: I) -> Self {
+        let mut heap = Self::default();
+        for (key, value) in items {
+            heap.push(key, value);
+        }
+        heap
+    }
+}
+
+// Unsigned keys a `RadixHeap` can bucket by their highest bit that differs from the last key popped
+pub trait RadixKey: Copy + Ord + Default {
+    const BITS: u32;
+
+    // 0 for equal keys, otherwise one more than the index of the highest differing bit
+    fn bucket(self, last: Self) -> usize;
+}
+
+impl RadixKey for u32 {
+    const BITS: u32 = u32::BITS;
+
+    fn bucket(self, last: Self) -> usize {
+        (Self::BITS - (self ^ last).leading_zeros()) as usize
+    }
+}
+
+impl RadixKey for u64 {
+    const BITS: u32 = u64::BITS;
+
+    fn bucket(self, last: Self) -> usize {
+        (Self::BITS - (self ^ last).leading_zeros()) as usize
+    }
+}
+
+impl RadixKey for usize {
+    const BITS: u32 = usize::BITS;
+
+    fn bucket(self, last: Self) -> usize {
+        (Self::BITS - (self ^ last).leading_zeros()) as usize
+    }
+}
+
+// A monotone priority queue for small non-negative integer keys, like the step counts and costs
+// most searches use. Keys are bucketed by how far they are from the last key popped, so pushing
+// is constant time and each entry is only moved a handful of times before it comes back out.
+// The catch is that nothing smaller than the last popped key can be pushed, which Dijkstra never
+// does, nor A* with a heuristic that never drops by more than the cost of a step
+pub struct RadixHeap<K: RadixKey, V> {
+    last: K,
+    len: usize,
+    buckets: Vec<Vec<(K, V)>>,
+}
+
+impl<K: RadixKey, V> Default for RadixHeap<K, V> {
+    fn default() -> Self {
+        Self {
+            last: K::default(),
+            len: 0,
+            buckets: (0..=K::BITS).map(|_| Vec::new()).collect(),
+        }
+    }
+}
+
+impl<K: RadixKey, V> RadixHeap<K, V> {
+    pub fn push(&mut self, key: K, value: V) {
+        assert!(
+            key >= self.last,
+            "RadixHeap keys can't be smaller than the last key popped"
+        );
+        self.buckets[key.bucket(self.last)].push((key, value));
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            // Everything in the first non-empty bucket is closer to its own minimum than to the
+            // old `last`, so spreading it out again leaves the minimum in bucket 0
+            let idx = self.buckets.iter().position(|bucket| !bucket.is_empty())?;
+            let entries = std::mem::take(&mut self.buckets[idx]);
+            self.last = entries.iter().map(|(key, _)| *key).min()?;
+            for (key, value) in entries {
+                self.buckets[key.bucket(self.last)].push((key, value));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    // Popping spreads out the first non-empty bucket and takes the last of its smallest keys, since
+    // spreading keeps their order, so that's the entry to show without moving anything
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        let bucket = self.buckets.iter().find(|bucket| !bucket.is_empty())?;
+        bucket
+            .iter()
+            .rev()
+            .min_by_key(|(key, _)| *key)
+            .map(|(key, value)| (key, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: RadixKey, V> PriorityQueue<K, V> for RadixHeap<K, V> {
+    fn push(&mut self, key: K, value: V) {
+        self.push(key, value);
+    }
+
+    fn pop(&mut self) -> Option<(K, V)> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<(&K, &V)> {
+        self.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[test]
+fn indexed_min_heap() {
+    let mut heap = IndexedMinHeap::from([(5, 'a'), (3, 'b'), (8, 'c'), (1, 'd'), (9, 'a')]);
+    assert_eq!(4, heap.len());
+    assert_eq!(Some((&1, &'d')), heap.peek());
+    assert!(heap.contains(&'c'));
+    assert!(!heap.contains(&'e'));
+
+    assert!(heap.decrease_key(&'c', 2));
+    assert!(!heap.decrease_key(&'c', 4));
+    assert!(!heap.decrease_key(&'e', 0));
+    assert!(!heap.push(6, 'a'));
+    assert_eq!(Some(&5), heap.key_of(&'a'));
+
+    assert_eq!(Some((1, 'd')), heap.pop());
+    assert!(!heap.contains(&'d'));
+    assert_eq!(
+        vec![(2, 'c'), (3, 'b'), (5, 'a')],
+        heap.drain_sorted().collect::<Vec<_>>()
+    );
+    assert!(heap.is_empty());
+    assert_eq!(None, heap.pop());
+}
+
+#[test]
+fn radix_heap() {
+    let mut heap = RadixHeap::default();
+    for (key, value) in [(7u32, 'a'), (3, 'b'), (3, 'c'), (12, 'd'), (0, 'e')] {
+        heap.push(key, value);
+    }
+    assert_eq!(5, heap.len());
+    assert_eq!(Some((&0, &'e')), heap.peek());
+    assert_eq!(Some((0, 'e')), heap.pop());
+    assert_eq!(Some((&3, &'c')), heap.peek());
+    assert_eq!(Some((3, 'c')), heap.pop());
+    heap.push(4, 'f');
+    assert_eq!(Some(3), heap.pop().map(|(key, _)| key));
+    assert_eq!(Some((4, 'f')), heap.pop());
+    assert_eq!(Some((7, 'a')), heap.pop());
+    assert_eq!(Some((12, 'd')), heap.pop());
+    assert!(heap.is_empty());
+    assert_eq!(None, heap.peek());
+    assert_eq!(None, heap.pop());
+
+    let mut heap = RadixHeap::default();
+    heap.push(u64::MAX, ());
+    heap.push(1u64 << 40, ());
+    assert_eq!(Some((1 << 40, ())), heap.pop());
+    assert_eq!(Some((u64::MAX, ())), heap.pop());
+}
+
+#[test]
+#[should_panic]
+fn radix_heap_rejects_earlier_keys() {
+    let mut heap = RadixHeap::default();
+    heap.push(5u32, ());
+    heap.pop();
+    heap.push(4, ());
 }
@@ -0,0 +1,107 @@
+use std::{env, fmt, fs, io, path::PathBuf};
+
+// When set, inputs are read from `<dir>/<year>/day<N>.txt` instead of the repo
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(PathBuf),
+    Unreadable(PathBuf, io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(path) => write!(f, "Input missing, expected it at {}", path.display()),
+            Self::Unreadable(path, err) => {
+                write!(f, "Could not read input at {}: {err}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub struct InputProvider {
+    // Without a directory, inputs live next to the solutions in `src/aoc_<year>/inputs`
+    dir: Option<PathBuf>,
+}
+
+impl InputProvider {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(env::var_os(INPUT_DIR_VAR).map(PathBuf::from))
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir.join(year.to_string()).join(format!("day{day}.txt")),
+            None => PathBuf::from(format!(
+                "{}/src/aoc_{year}/inputs/day{day}.txt",
+                env!("CARGO_MANIFEST_DIR")
+            )),
+        }
+    }
+
+    // Answers belong to whoever's inputs they came from, so they're kept alongside them
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir.join("answers.toml"),
+            None => PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml")),
+        }
+    }
+
+    pub fn load(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let path = self.path(year, day);
+        fs::read_to_string(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => InputError::Missing(path),
+            _ => InputError::Unreadable(path, err),
+        })
+    }
+}
+
+// Inputs aren't checked in, so tests skip their input assertions rather than fail without them
+#[cfg(test)]
+pub fn test_input(year: u16, day: u8) -> Option<String> {
+    match InputProvider::from_env().load(year, day) {
+        Ok(input) => Some(input),
+        Err(err) => {
+            eprintln!("Skipping {year} day {day}: {err}");
+            None
+        }
+    }
+}
+
+#[test]
+fn input_paths() {
+    let provider = InputProvider::new(Some(PathBuf::from("/tmp/aoc")));
+    assert_eq!(
+        PathBuf::from("/tmp/aoc/2016/day12.txt"),
+        provider.path(2016, 12)
+    );
+
+    let provider = InputProvider::new(None);
+    assert!(
+        provider
+            .path(2025, 3)
+            .ends_with("src/aoc_2025/inputs/day3.txt")
+    );
+
+    let provider = InputProvider::new(Some(PathBuf::from("/tmp/aoc")));
+    assert_eq!(
+        PathBuf::from("/tmp/aoc/answers.toml"),
+        provider.answers_path()
+    );
+}
+
+#[test]
+fn missing_input() {
+    let provider = InputProvider::new(Some(PathBuf::from("/definitely/not/a/real/dir")));
+    assert!(matches!(
+        provider.load(2016, 12),
+        Err(InputError::Missing(_))
+    ));
+}
@@ -0,0 +1,61 @@
+// Hands out a small index for every distinct name, in the order they're first seen, so puzzles
+// that name things can keep their data in plain vectors and matrices
+
+use fxhash::FxHashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: Vec<String>,
+    indices: FxHashMap<String, usize>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The index for `name`, giving it the next one along if it hasn't been seen before
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        let index = self.names.len();
+        self.names.push(name.to_owned());
+        self.indices.insert(name.to_owned(), index);
+        index
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    pub fn name(&self, index: usize) -> &str {
+        &self.names[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    // Every name, indexed by what it was interned as
+    pub fn into_names(self) -> Vec<String> {
+        self.names
+    }
+}
+
+#[test]
+fn interning() {
+    let mut names = Interner::new();
+    assert_eq!(0, names.intern("Faerun"));
+    assert_eq!(1, names.intern("Tristram"));
+    assert_eq!(0, names.intern("Faerun"));
+    assert_eq!(Some(1), names.get("Tristram"));
+    assert_eq!(None, names.get("Arbre"));
+    assert_eq!("Tristram", names.name(1));
+    assert_eq!(2, names.len());
+    assert_eq!(vec!["Faerun", "Tristram"], names.into_names());
+}
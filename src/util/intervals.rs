@@ -0,0 +1,226 @@
+// Sets of integers stored as sorted, inclusive `(start, end)` ranges. Ranges that overlap or touch
+// are coalesced as they're inserted, so there's only ever one way to store a given set and
+// lookups can binary search on the ends
+
+// Integers with a well defined next and previous value
+pub trait Discrete: Copy + Ord {
+    fn next(self) -> Option<Self>;
+    fn prev(self) -> Option<Self>;
+    // How many values `start..=end` holds
+    fn width(start: Self, end: Self) -> u64;
+}
+
+macro_rules! impl_discrete {
+    ($($ty:ty),*) => {$(
+        impl Discrete for $ty {
+            fn next(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn prev(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn width(start: Self, end: Self) -> u64 {
+                end.abs_diff(start) as u64 + 1
+            }
+        }
+    )*};
+}
+
+impl_discrete!(u32, u64, usize, i32, i64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The index of the first range that ends at or after `value`
+    fn first_ending_from(&self, value: T) -> usize {
+        self.ranges.partition_point(|&(_, end)| end < value)
+    }
+
+    // Adds `start..=end`, merging it with any ranges it overlaps or touches. Does nothing if
+    // `start > end`
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+        // Ranges ending just before `start` or starting just after `end` are merged in too
+        let from = match start.prev() {
+            Some(before) => self.first_ending_from(before),
+            None => 0,
+        };
+        let to = match end.next() {
+            Some(after) => self.ranges.partition_point(|&(s, _)| s <= after),
+            None => self.ranges.len(),
+        };
+
+        let mut merged = (start, end);
+        if from < to {
+            merged.0 = merged.0.min(self.ranges[from].0);
+            merged.1 = merged.1.max(self.ranges[to - 1].1);
+        }
+        self.ranges.splice(from..to, [merged]);
+    }
+
+    // Takes `start..=end` out of the set, splitting any range it lands in the middle of
+    pub fn remove(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+        let from = self.first_ending_from(start);
+        let to = self.ranges.partition_point(|&(s, _)| s <= end);
+        if from >= to {
+            return;
+        }
+
+        let (first_start, _) = self.ranges[from];
+        let (_, last_end) = self.ranges[to - 1];
+        let before = start.prev().filter(|_| first_start < start);
+        let after = end.next().filter(|_| last_end > end);
+        let leftovers = before
+            .map(|before| (first_start, before))
+            .into_iter()
+            .chain(after.map(|after| (after, last_end)));
+        self.ranges.splice(from..to, leftovers);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .get(self.first_ending_from(value))
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    // Every value in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(&&(a_start, a_end)), Some(&&(b_start, b_end))) = (a.peek(), b.peek()) {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // Whichever range ends first can't overlap anything else in the other set
+            if a_end < b_end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    // Every value in `low..=high` that isn't in the set
+    pub fn complement(&self, low: T, high: T) -> Self {
+        let mut ranges = Vec::new();
+        let mut cursor = Some(low);
+        for &(start, end) in &self.ranges[self.first_ending_from(low)..] {
+            let Some(from) = cursor.filter(|&from| from <= high) else {
+                break;
+            };
+            if from < start {
+                // `start > from` so it has a previous value
+                ranges.push((from, start.prev().unwrap().min(high)));
+            }
+            cursor = end.next();
+        }
+        if let Some(from) = cursor.filter(|&from| from <= high) {
+            ranges.push((from, high));
+        }
+        Self { ranges }
+    }
+
+    // How many values are in the set. Overflows if it holds all 2^64 values of a 64 bit type
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| T::width(start, end))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // The disjoint ranges making up the set, in order
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges
+    }
+}
+
+impl<T: Discrete> FromIterator<(T, T)> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for (start, end) in ranges {
+            set.insert(start, end);
+        }
+        set
+    }
+}
+
+#[test]
+fn inserting_and_removing() {
+    let mut set: IntervalSet<u32> = [(5, 8), (0, 2), (4, 7), (10, 12)].into_iter().collect();
+    assert_eq!(&[(0, 2), (4, 8), (10, 12)], set.ranges());
+    // Touching ranges are merged as well as overlapping ones
+    set.insert(3, 3);
+    assert_eq!(&[(0, 8), (10, 12)], set.ranges());
+    set.insert(9, 20);
+    assert_eq!(&[(0, 20)], set.ranges());
+    assert_eq!(21, set.len());
+
+    set.remove(5, 6);
+    assert_eq!(&[(0, 4), (7, 20)], set.ranges());
+    set.remove(0, 0);
+    set.remove(15, 30);
+    assert_eq!(&[(1, 4), (7, 14)], set.ranges());
+    set.remove(2, 10);
+    assert_eq!(&[(1, 1), (11, 14)], set.ranges());
+
+    assert!(set.contains(1));
+    assert!(set.contains(14));
+    assert!(!set.contains(0));
+    assert!(!set.contains(5));
+    assert!(!set.contains(15));
+
+    let mut set = IntervalSet::new();
+    set.insert(u32::MAX - 1, u32::MAX);
+    set.insert(0, u32::MAX - 2);
+    assert_eq!(&[(0, u32::MAX)], set.ranges());
+    set.insert(7, 3);
+    assert_eq!(1, set.ranges().len());
+}
+
+#[test]
+fn combining() {
+    let a: IntervalSet<i64> = [(-5, 0), (3, 9), (20, 30)].into_iter().collect();
+    let b: IntervalSet<i64> = [(-2, 4), (8, 25)].into_iter().collect();
+    assert_eq!(
+        &[(-2, 0), (3, 4), (8, 9), (20, 25)],
+        a.intersection(&b).ranges()
+    );
+    assert_eq!(&[(1, 2), (10, 19)], a.complement(-3, 22).ranges());
+    assert_eq!(
+        &[(-10, -6), (1, 2), (10, 19), (31, 40)],
+        a.complement(-10, 40).ranges()
+    );
+    assert!(a.complement(-5, 0).is_empty());
+
+    let full: IntervalSet<u32> = [(0, 3), (8, u32::MAX)].into_iter().collect();
+    assert_eq!(&[(4, 7)], full.complement(0, u32::MAX).ranges());
+}
@@ -0,0 +1,110 @@
+// Number theory helpers. Intermediate products are done in `i128`/`u128`, so anything that fits in
+// 64 bits can be multiplied without overflowing first
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+// Returns `(g, x, y)` where `g` is the gcd of `a` and `b` and `a * x + b * y == g`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// The `x` in `0..modulus` with `a * x` congruent to 1, if `a` and `modulus` are coprime
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+// Solves `x ≡ residue (mod modulus)` for every `(residue, modulus)` at once, giving the smallest
+// non-negative `x` along with the lcm of the moduli, which every other solution differs from it
+// by a multiple of. The moduli don't have to be coprime, but `None` is returned if they disagree
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i64 = 0;
+    let mut step: i64 = 1;
+    for &(residue, modulus) in congruences {
+        // Looking for `k` with `x + step * k ≡ residue`, i.e. `step * k ≡ residue - x`
+        let (g, inverse, _) = extended_gcd(step, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let reduced = modulus / g;
+        let k = (diff / g) as i128 * inverse as i128 % reduced as i128;
+        let next_step = step as i128 * reduced as i128;
+        x = (x as i128 + step as i128 * k).rem_euclid(next_step) as i64;
+        step = next_step as i64;
+    }
+    Some((x, step))
+}
+
+// `base` raised to `exp`, modulo `modulus`, by repeated squaring
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+#[test]
+fn divisors() {
+    assert_eq!(1, gcd(5, 7));
+    assert_eq!(2, gcd(4, 6));
+    assert_eq!(4, gcd(4, 8));
+    assert_eq!(3, gcd(-6, 9));
+    assert_eq!(7, gcd(0, 7));
+    assert_eq!(12, lcm(4, 6));
+    assert_eq!(0, lcm(0, 6));
+
+    let (g, x, y) = extended_gcd(240, 46);
+    assert_eq!(2, g);
+    assert_eq!(g, 240 * x + 46 * y);
+
+    assert_eq!(Some(4), mod_inverse(3, 11));
+    assert_eq!(Some(10), mod_inverse(-1, 11));
+    assert_eq!(None, mod_inverse(4, 8));
+}
+
+#[test]
+fn remainders() {
+    assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+    // Moduli that share a factor still work as long as they agree
+    assert_eq!(Some((10, 12)), crt(&[(2, 4), (4, 6)]));
+    assert_eq!(None, crt(&[(1, 4), (2, 6)]));
+    assert_eq!(Some((0, 1)), crt(&[]));
+
+    assert_eq!(445, mod_pow(4, 13, 497));
+    assert_eq!(0, mod_pow(5, 0, 1));
+    assert_eq!(1, mod_pow(u64::MAX, 2, u64::MAX - 1));
+}
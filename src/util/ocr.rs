@@ -0,0 +1,246 @@
+// Reads the capital letters some puzzles draw on a pixel screen. Glyphs are stored with their
+// blank edge columns trimmed off, since letters like `I` sit at different offsets in their cell
+
+use super::grid::Grid;
+
+struct Font {
+    height: usize,
+    // Width of a letter plus the gap before the next one
+    stride: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    stride: 5,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+const LARGE: Font = Font {
+    height: 10,
+    stride: 8,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+fn matches(glyph: &[&str], rows: &[&[bool]], columns: &[usize]) -> bool {
+    glyph.iter().zip(rows).all(|(pattern, row)| {
+        pattern.len() == columns.len()
+            && pattern
+                .bytes()
+                .zip(columns)
+                .all(|(pixel, &x)| (pixel == b'#') == row[x])
+    })
+}
+
+// The font is picked from the height of the screen: 6 rows for the small font, 10 for the large
+// one. Returns `None` if any letter isn't one the font knows, so the caller can fall back to
+// drawing the screen instead
+pub fn read_letters(screen: &Grid<bool>) -> Option<String> {
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == screen.height())?;
+    let rows: Vec<&[bool]> = screen.rows().collect();
+    let width = screen.width();
+
+    let mut letters = String::new();
+    for start in (0..width).step_by(font.stride) {
+        let end = (start + font.stride).min(width);
+        let lit: Vec<usize> = (start..end)
+            .filter(|&x| rows.iter().any(|row| row[x]))
+            .collect();
+        // Blank cells are just padding past the last letter
+        let (Some(&first), Some(&last)) = (lit.first(), lit.last()) else {
+            continue;
+        };
+        let columns: Vec<usize> = (first..=last).collect();
+
+        let &(letter, _) = font
+            .glyphs
+            .iter()
+            .find(|(_, glyph)| matches(glyph, &rows, &columns))?;
+        letters.push(letter);
+    }
+
+    Some(letters)
+}
+
+#[cfg(test)]
+fn screen(picture: &str) -> Grid<bool> {
+    Grid::parse(picture, "`.` or `#`", |ch| Some(ch == '#')).unwrap()
+}
+
+#[test]
+fn small_font() {
+    let picture = "\
+#..#.####.#....#.....##..
+#..#.#....#....#....#..#.
+####.###..#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.#....#....#....#..#.
+#..#.####.####.####..##..";
+    assert_eq!(Some(String::from("HELLO")), read_letters(&screen(picture)));
+
+    // `I` and `Y` don't fill their cells the same way the other letters do
+    let picture = "\
+.###.#...#
+..#..#...#
+..#...#.#.
+..#....#..
+..#....#..
+.###...#..";
+    assert_eq!(Some(String::from("IY")), read_letters(&screen(picture)));
+}
+
+#[test]
+fn large_font() {
+    let picture = "\
+#....#..#####.
+#....#..#....#
+.#..#...#....#
+.#..#...#....#
+..##....#####.
+..##....#..#..
+.#..#...#...#.
+.#..#...#...#.
+#....#..#....#
+#....#..#....#";
+    assert_eq!(Some(String::from("XR")), read_letters(&screen(picture)));
+}
+
+#[test]
+fn unknown_letters() {
+    let picture = "\
+#...#
+##.##
+#.#.#
+#...#
+#...#
+#...#";
+    assert_eq!(None, read_letters(&screen(picture)));
+    assert_eq!(None, read_letters(&screen("#\n#\n#")));
+}
@@ -0,0 +1,168 @@
+// Points and steps on a 2D plane. Like `Grid`, `y` grows downwards, so `Up` is `Pair(0, -1)` and
+// clockwise means clockwise as drawn on a screen. Puzzles that only care about distances can
+// ignore which way is up
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Pair(pub i64, pub i64);
+
+impl Pair {
+    pub const ORIGIN: Pair = Pair(0, 0);
+
+    pub fn manhattan(self, other: Pair) -> i64 {
+        (self.0 - other.0).abs() + (self.1 - other.1).abs()
+    }
+
+    pub fn chebyshev(self, other: Pair) -> i64 {
+        (self.0 - other.0).abs().max((self.1 - other.1).abs())
+    }
+
+    // Up, right, down, left
+    pub fn neighbors4(self) -> [Pair; 4] {
+        Direction::ALL.map(|dir| self + dir.step())
+    }
+
+    // Clockwise from the top left
+    pub fn neighbors8(self) -> [Pair; 8] {
+        let Pair(x, y) = self;
+        [
+            Pair(x - 1, y - 1),
+            Pair(x, y - 1),
+            Pair(x + 1, y - 1),
+            Pair(x + 1, y),
+            Pair(x + 1, y + 1),
+            Pair(x, y + 1),
+            Pair(x - 1, y + 1),
+            Pair(x - 1, y),
+        ]
+    }
+
+    // Turns about the origin, clockwise for positive `quarter_turns` and anticlockwise otherwise
+    pub fn rotate(self, quarter_turns: i64) -> Pair {
+        let Pair(x, y) = self;
+        match quarter_turns.rem_euclid(4) {
+            0 => Pair(x, y),
+            1 => Pair(-y, x),
+            2 => Pair(-x, -y),
+            _ => Pair(y, -x),
+        }
+    }
+}
+
+impl Add<Pair> for Pair {
+    type Output = Self;
+    fn add(self, rhs: Pair) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl AddAssign<Pair> for Pair {
+    fn add_assign(&mut self, rhs: Pair) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Pair> for Pair {
+    type Output = Self;
+    fn sub(self, rhs: Pair) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl SubAssign<Pair> for Pair {
+    fn sub_assign(&mut self, rhs: Pair) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Pair {
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Neg for Pair {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+// Listed clockwise, starting from `Up`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    // Understands arrows (`^v<>`), letters (`UDLR`) and compass points (`NESW`)
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '^' | 'U' | 'N' => Some(Self::Up),
+            '>' | 'R' | 'E' => Some(Self::Right),
+            'v' | 'D' | 'S' => Some(Self::Down),
+            '<' | 'L' | 'W' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub fn step(self) -> Pair {
+        match self {
+            Self::Up => Pair(0, -1),
+            Self::Right => Pair(1, 0),
+            Self::Down => Pair(0, 1),
+            Self::Left => Pair(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let a = Pair(3, -4);
+    assert_eq!(Pair(4, -2), a + Pair(1, 2));
+    assert_eq!(Pair(2, -6), a - Pair(1, 2));
+    assert_eq!(Pair(-9, 12), a * -3);
+    assert_eq!(7, a.manhattan(Pair::ORIGIN));
+    assert_eq!(4, a.chebyshev(Pair::ORIGIN));
+    assert_eq!(Pair(4, 3), a.rotate(1));
+    assert_eq!(Pair(-4, -3), a.rotate(-1));
+    assert_eq!(a, a.rotate(8));
+    assert!(a.neighbors8().iter().all(|&n| a.chebyshev(n) == 1));
+}
+
+#[test]
+fn directions() {
+    assert_eq!(Some(Direction::Up), Direction::from_char('N'));
+    assert_eq!(Some(Direction::Left), Direction::from_char('<'));
+    assert_eq!(None, Direction::from_char('x'));
+    assert_eq!(Direction::Right, Direction::Up.turn_right());
+    assert_eq!(Direction::Left, Direction::Up.turn_left());
+    assert_eq!(Direction::Down, Direction::Up.reverse());
+    for dir in Direction::ALL {
+        assert_eq!(dir.turn_right().step(), dir.step().rotate(1));
+        assert_eq!(Pair::ORIGIN, dir.step() + dir.reverse().step());
+    }
+}
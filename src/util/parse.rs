@@ -0,0 +1,251 @@
+use std::{fmt, ops::Range, str::FromStr};
+
+use logos::{Lexer, Logos};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub span: Range<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: &'static str,
+    // `None` when the input ran out before the expected token
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(source: &str, span: Range<usize>, expected: &'static str) -> Self {
+        let before = &source[..span.start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&ch| ch != '\n').count() + 1;
+        let found = (span.start < source.len()).then(|| source[span.clone()].to_owned());
+        Self {
+            span,
+            line,
+            column,
+            expected,
+            found,
+        }
+    }
+
+    pub fn end_of_input(source: &str, expected: &'static str) -> Self {
+        Self::new(source, source.len()..source.len(), expected)
+    }
+
+    // For parsers that slice up the input themselves rather than lexing it. The slice must
+    // have been taken from the source, since its position is worked out from its address
+    pub fn at(source: &str, slice: &str, expected: &'static str) -> Self {
+        let start = slice.as_ptr() as usize - source.as_ptr() as usize;
+        Self::new(source, start..start + slice.len(), expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected {} at line {}, column {}, ",
+            self.expected, self.line, self.column
+        )?;
+        match &self.found {
+            Some(found) => write!(f, "found `{found}`"),
+            None => write!(f, "found end of input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Pulls the next token, treating both the end of the input and unlexable text as errors
+pub fn next_token<'s, T>(lex: &mut Lexer<'s, T>, expected: &'static str) -> Result<T, ParseError>
+where
+    T: Logos<'s, Source = str>,
+{
+    match lex.next() {
+        Some(Ok(token)) => Ok(token),
+        Some(Err(_)) => Err(ParseError::new(lex.source(), lex.span(), expected)),
+        None => Err(ParseError::end_of_input(lex.source(), expected)),
+    }
+}
+
+// Like `next_token`, but `take` also rejects tokens of the wrong kind by returning `None`
+pub fn expect_token<'s, T, U>(
+    lex: &mut Lexer<'s, T>,
+    expected: &'static str,
+    take: impl FnOnce(T) -> Option<U>,
+) -> Result<U, ParseError>
+where
+    T: Logos<'s, Source = str>,
+{
+    let token = next_token(lex, expected)?;
+    take(token).ok_or_else(|| ParseError::new(lex.source(), lex.span(), expected))
+}
+
+// For the first token of a line or record, where running out of input just means we're done
+pub fn next_token_or_end<'s, T>(
+    lex: &mut Lexer<'s, T>,
+    expected: &'static str,
+) -> Result<Option<T>, ParseError>
+where
+    T: Logos<'s, Source = str>,
+{
+    match lex.next() {
+        Some(Ok(token)) => Ok(Some(token)),
+        Some(Err(_)) => Err(ParseError::new(lex.source(), lex.span(), expected)),
+        None => Ok(None),
+    }
+}
+
+pub fn expect_token_or_end<'s, T, U>(
+    lex: &mut Lexer<'s, T>,
+    expected: &'static str,
+    take: impl FnOnce(T) -> Option<U>,
+) -> Result<Option<U>, ParseError>
+where
+    T: Logos<'s, Source = str>,
+{
+    match next_token_or_end(lex, expected)? {
+        Some(token) => take(token)
+            .map(Some)
+            .ok_or_else(|| ParseError::new(lex.source(), lex.span(), expected)),
+        None => Ok(None),
+    }
+}
+
+// Lexes the whole input up front, for solutions that walk the tokens more than once. An input
+// with no tokens in it at all is an error
+pub fn tokenize<'s, T>(source: &'s str, expected: &'static str) -> Result<Vec<T>, ParseError>
+where
+    T: Logos<'s, Source = str>,
+    T::Extras: Default,
+{
+    let mut lex = T::lexer(source);
+    let mut tokens = Vec::new();
+    while let Some(token) = next_token_or_end(&mut lex, expected)? {
+        tokens.push(token);
+    }
+    non_empty(source, tokens, expected)
+}
+
+pub fn parse_at<T: FromStr>(
+    source: &str,
+    slice: &str,
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    slice
+        .parse()
+        .map_err(|_| ParseError::at(source, slice, expected))
+}
+
+// Parsers that read items until the input runs out find nothing at all in an empty input, which
+// would otherwise be solved as a puzzle with nothing in it
+pub fn non_empty<T>(
+    source: &str,
+    items: Vec<T>,
+    expected: &'static str,
+) -> Result<Vec<T>, ParseError> {
+    if items.is_empty() {
+        Err(ParseError::end_of_input(source, expected))
+    } else {
+        Ok(items)
+    }
+}
+
+// For inputs that are a single word or line of symbols. Trims the input and checks there's
+// something left and that every character of it is `allowed`
+pub fn parse_chars<'s>(
+    source: &'s str,
+    expected: &'static str,
+    allowed: impl Fn(char) -> bool,
+) -> Result<&'s str, ParseError> {
+    let text = source.trim();
+    if text.is_empty() {
+        return Err(ParseError::end_of_input(source, expected));
+    }
+    match text.char_indices().find(|&(_, ch)| !allowed(ch)) {
+        Some((idx, ch)) => Err(ParseError::at(
+            source,
+            &text[idx..idx + ch.len_utf8()],
+            expected,
+        )),
+        None => Ok(text),
+    }
+}
+
+#[cfg(test)]
+#[derive(Logos, Debug, PartialEq)]
+#[logos(skip r"\s+")]
+enum Token {
+    #[regex("[0-9]+", |lex| lex.slice().parse::<u8>().ok())]
+    Num(u8),
+
+    #[regex("[a-z]+")]
+    Word,
+}
+
+#[test]
+fn lexer_errors() {
+    let source = "12 ab\n  300 x";
+    let mut lex = Token::lexer(source);
+
+    let num = |token| match token {
+        Token::Num(n) => Some(n),
+        _ => None,
+    };
+    assert_eq!(Ok(12), expect_token(&mut lex, "a number", num));
+
+    let err = expect_token(&mut lex, "a number", num).unwrap_err();
+    assert_eq!((3..5, 1, 4), (err.span, err.line, err.column));
+    assert_eq!(Some(String::from("ab")), err.found);
+
+    // Too big for a u8, so the callback rejects it
+    let err = next_token(&mut lex, "a number").unwrap_err();
+    assert_eq!((2, 3), (err.line, err.column));
+    assert_eq!(
+        "Expected a number at line 2, column 3, found `300`",
+        err.to_string()
+    );
+
+    assert_eq!(Ok(Some(Token::Word)), next_token_or_end(&mut lex, "a word"));
+    assert_eq!(Ok(None), next_token_or_end(&mut lex, "a word"));
+    let err = next_token(&mut lex, "a word").unwrap_err();
+    assert_eq!(None, err.found);
+    assert_eq!(
+        "Expected a word at line 2, column 8, found end of input",
+        err.to_string()
+    );
+}
+
+#[test]
+fn tokenize_test() {
+    assert_eq!(
+        Ok(vec![Token::Num(1), Token::Word]),
+        tokenize("1 a", "a token")
+    );
+    let err = tokenize::<Token>("1 a\n!", "a token").unwrap_err();
+    assert_eq!((2, 1), (err.line, err.column));
+    let err = tokenize::<Token>(" \n", "a token").unwrap_err();
+    assert_eq!((2, 1, None), (err.line, err.column, err.found));
+}
+
+#[test]
+fn slice_errors() {
+    let source = "1,2\n3,x";
+    let value = source.lines().nth(1).unwrap().split(',').nth(1).unwrap();
+    let err = parse_at::<u32>(source, value, "a number").unwrap_err();
+    assert_eq!((6..7, 2, 3), (err.span, err.line, err.column));
+}
+
+#[test]
+fn whole_input_checks() {
+    assert_eq!(Ok(vec![1]), non_empty("1", vec![1], "a number"));
+    let err = non_empty::<u8>("\n\n", Vec::new(), "a number").unwrap_err();
+    assert_eq!((3, 1, None), (err.line, err.column, err.found));
+
+    assert_eq!(
+        Ok("0110"),
+        parse_chars("\n0110\n", "a bit", |ch| "01".contains(ch))
+    );
+    let err = parse_chars("01a1", "a bit", |ch| "01".contains(ch)).unwrap_err();
+    assert_eq!((2..3, Some(String::from("a"))), (err.span, err.found));
+    assert!(parse_chars("  \n", "a bit", |ch| "01".contains(ch)).is_err());
+}
@@ -0,0 +1,288 @@
+// Shortest path searches over any state that can be hashed. Each one takes a start state, a
+// closure listing the states reachable from a given one and a goal predicate, and returns the
+// cost of the cheapest route along with every state on it, start and goal included. The weighted
+// searches queue states in a `MinHeap` unless told to use another `PriorityQueue`
+
+use std::{
+    collections::{VecDeque, hash_map::Entry},
+    hash::Hash,
+    ops::Add,
+};
+
+use fxhash::FxHashMap;
+
+use super::heap::{MinHeap, PriorityQueue};
+
+// Every state seen so far, with the index of the state it was reached from
+struct Visited<S> {
+    states: Vec<(S, usize)>,
+    index: FxHashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> {
+    fn new(start: S) -> Self {
+        let mut index = FxHashMap::default();
+        index.insert(start.clone(), 0);
+        Self {
+            states: vec![(start, 0)],
+            index,
+        }
+    }
+
+    fn push(&mut self, state: S, parent: usize) -> usize {
+        let idx = self.states.len();
+        self.index.insert(state.clone(), idx);
+        self.states.push((state, parent));
+        idx
+    }
+
+    fn path_to(&self, mut idx: usize) -> Vec<S> {
+        let mut path = vec![self.states[idx].0.clone()];
+        while idx != 0 {
+            idx = self.states[idx].1;
+            path.push(self.states[idx].0.clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+// Every step costs 1, so the cost is the number of steps taken
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    if is_goal(&start) {
+        return Some((0, vec![start]));
+    }
+
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some((steps, idx)) = queue.pop_front() {
+        for next in neighbors(&visited.states[idx].0) {
+            if visited.index.contains_key(&next) {
+                continue;
+            }
+            // Checking on discovery rather than when popped saves expanding a whole layer
+            let found = is_goal(&next);
+            let next_idx = visited.push(next, idx);
+            if found {
+                return Some((steps + 1, visited.path_to(next_idx)));
+            }
+            queue.push_back((steps + 1, next_idx));
+        }
+    }
+
+    None
+}
+
+// How many steps it takes to reach every state no more than `max_steps` away from `start`
+pub fn bfs_within<S, I>(
+    start: S,
+    max_steps: usize,
+    mut neighbors: impl FnMut(&S) -> I,
+) -> FxHashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut steps = FxHashMap::default();
+    steps.insert(start.clone(), 0);
+    let mut queue = VecDeque::from([(0, start)]);
+    while let Some((taken, state)) = queue.pop_front() {
+        if taken == max_steps {
+            continue;
+        }
+        for next in neighbors(&state) {
+            if let Entry::Vacant(entry) = steps.entry(next.clone()) {
+                entry.insert(taken + 1);
+                queue.push_back((taken + 1, next));
+            }
+        }
+    }
+
+    steps
+}
+
+// `neighbors` gives each next state along with the cost of moving to it
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    dijkstra_with::<MinHeap<_, _>, _, _, _>(start, neighbors, is_goal)
+}
+
+pub fn dijkstra_with<Q, S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    Q: PriorityQueue<C, (C, usize)>,
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with::<Q, _, _, _>(start, neighbors, |_| C::default(), is_goal)
+}
+
+// `heuristic` must never overestimate the remaining cost to a goal, or a cheaper route might be
+// missed
+pub fn astar<S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with::<MinHeap<_, _>, _, _, _>(start, neighbors, heuristic, is_goal)
+}
+
+// A `RadixHeap` also needs the heuristic to never drop by more than the cost of a step, so that
+// nothing is queued with a smaller estimate than the state it was reached from
+pub fn astar_with<Q, S, C, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    Q: PriorityQueue<C, (C, usize)>,
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut queue = Q::default();
+    queue.push(heuristic(&start), (C::default(), 0));
+    let mut visited = Visited::new(start);
+    let mut best = vec![C::default()];
+
+    while let Some((_, (cost, idx))) = queue.pop() {
+        // A cheaper way here was found after this entry was queued
+        if cost > best[idx] {
+            continue;
+        }
+        if is_goal(&visited.states[idx].0) {
+            return Some((cost, visited.path_to(idx)));
+        }
+
+        for (next, step) in neighbors(&visited.states[idx].0) {
+            let next_cost = cost + step;
+            let next_idx = match visited.index.get(&next) {
+                Some(&next_idx) if next_cost >= best[next_idx] => continue,
+                Some(&next_idx) => {
+                    best[next_idx] = next_cost;
+                    visited.states[next_idx].1 = idx;
+                    next_idx
+                }
+                None => {
+                    best.push(next_cost);
+                    visited.push(next, idx)
+                }
+            };
+            let estimate = heuristic(&visited.states[next_idx].0);
+            queue.push(next_cost + estimate, (next_cost, next_idx));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+const MAZE: &str = "
+.....#
+.###.#
+...#..
+##.#.#
+......";
+
+#[cfg(test)]
+fn maze_neighbors(grid: &super::grid::Grid<bool>, pos: super::Pair) -> Vec<super::Pair> {
+    grid.neighbors4(pos).filter(|&next| grid[next]).collect()
+}
+
+#[test]
+fn unweighted() {
+    use super::{Pair, grid::Grid};
+
+    let grid = Grid::parse(MAZE, "`.` or `#`", |ch| Some(ch == '.')).unwrap();
+    let goal = Pair(5, 4);
+    let (steps, path) = bfs(
+        Pair(0, 0),
+        |&pos| maze_neighbors(&grid, pos),
+        |&pos| pos == goal,
+    )
+    .unwrap();
+    assert_eq!(9, steps);
+    assert_eq!(10, path.len());
+    assert_eq!(
+        (Some(&Pair(0, 0)), Some(&goal)),
+        (path.first(), path.last())
+    );
+    assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+
+    let (cost, astar_path) = astar(
+        Pair(0, 0),
+        |&pos| maze_neighbors(&grid, pos).into_iter().map(|next| (next, 1)),
+        |&pos| pos.manhattan(goal),
+        |&pos| pos == goal,
+    )
+    .unwrap();
+    assert_eq!(9, cost);
+    assert_eq!(10, astar_path.len());
+
+    assert_eq!(
+        None,
+        bfs(
+            Pair(0, 0),
+            |&pos| maze_neighbors(&grid, pos),
+            |&pos| pos == Pair(5, 0)
+        )
+    );
+    let within = bfs_within(Pair(0, 0), 2, |&pos| maze_neighbors(&grid, pos));
+    assert_eq!(5, within.len());
+    assert_eq!(Some(&2), within.get(&Pair(0, 2)));
+}
+
+#[test]
+fn weighted() {
+    use super::heap::RadixHeap;
+
+    // The direct edge is more expensive than going the long way round
+    let edges = |&node: &u8| match node {
+        0 => vec![(1, 1), (3, 10)],
+        1 => vec![(2, 2)],
+        2 => vec![(3, 3)],
+        _ => vec![],
+    };
+    assert_eq!(
+        Some((6, vec![0, 1, 2, 3])),
+        dijkstra(0, edges, |&node| node == 3)
+    );
+    assert_eq!(None, dijkstra(1, edges, |&node| node == 0));
+
+    let edges = |&node: &u8| {
+        edges(&node)
+            .into_iter()
+            .map(|(next, cost)| (next, cost as u32))
+    };
+    assert_eq!(
+        Some((6, vec![0, 1, 2, 3])),
+        dijkstra_with::<RadixHeap<_, _>, _, _, _>(0, edges, |&node| node == 3)
+    );
+}
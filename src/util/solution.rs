@@ -0,0 +1,142 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::util::parse::ParseError;
+
+pub trait Solution {
+    type Input;
+
+    // Some puzzles have such a short input that it was written straight into the source
+    const INPUT: Option<&'static str> = None;
+    // Every day 25 only has the one part
+    const HAS_PART_2: bool = true;
+    // Parts that take seconds or more are skipped unless asked for
+    const SLOW_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_1(input: &Self::Input) -> impl Display;
+
+    fn part_2(_input: &Self::Input) -> impl Display {
+        ""
+    }
+}
+
+pub struct Answer {
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+pub struct Report {
+    pub parse: Duration,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+    pub skipped_part_2: bool,
+}
+
+fn timed<T: Display>(f: impl FnOnce() -> T) -> Answer {
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    Answer {
+        value: value.to_string(),
+        elapsed,
+    }
+}
+
+// Parses the input once, then solves either the one requested part or both of them.
+// A slow part 2 only runs when slow parts are allowed or it was asked for by itself
+pub fn run<S: Solution>(input: &str, part: Option<u8>, slow: bool) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let skipped_part_2 = S::HAS_PART_2 && S::SLOW_PART_2 && !slow && part.is_none();
+    let part_1 = (part != Some(2)).then(|| timed(|| S::part_1(&parsed)));
+    let part_2 =
+        (S::HAS_PART_2 && part != Some(1) && !skipped_part_2).then(|| timed(|| S::part_2(&parsed)));
+
+    Ok(Report {
+        parse,
+        part_1,
+        part_2,
+        skipped_part_2,
+    })
+}
+
+#[cfg(test)]
+use crate::util::parse::parse_at;
+
+#[cfg(test)]
+struct Sample;
+
+#[cfg(test)]
+impl Solution for Sample {
+    type Input = Vec<i32>;
+
+    const HAS_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .split(',')
+            .map(|x| parse_at(input, x, "a number"))
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        input.iter().sum::<i32>()
+    }
+}
+
+#[cfg(test)]
+struct SlowSample;
+
+#[cfg(test)]
+impl Solution for SlowSample {
+    type Input = Vec<i32>;
+
+    const SLOW_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Sample::parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> impl Display {
+        input.iter().sum::<i32>()
+    }
+
+    fn part_2(input: &Self::Input) -> impl Display {
+        input.iter().product::<i32>()
+    }
+}
+
+#[test]
+fn run_test() {
+    let report = run::<Sample>("1,2,3", None, false).unwrap();
+    assert_eq!("6", report.part_1.unwrap().value);
+    assert!(report.part_2.is_none());
+    assert!(!report.skipped_part_2);
+
+    let report = run::<Sample>("1,2,3", Some(2), false).unwrap();
+    assert!(report.part_1.is_none());
+
+    let err = run::<Sample>("1,x", None, false).err().unwrap();
+    assert_eq!((1, 3), (err.line, err.column));
+}
+
+#[test]
+fn slow_parts() {
+    let report = run::<SlowSample>("2,3", None, false).unwrap();
+    assert_eq!("5", report.part_1.unwrap().value);
+    assert!(report.part_2.is_none());
+    assert!(report.skipped_part_2);
+
+    let report = run::<SlowSample>("2,3", None, true).unwrap();
+    assert_eq!("6", report.part_2.unwrap().value);
+
+    // Asking for the part by itself counts as asking for it to run
+    let report = run::<SlowSample>("2,3", Some(2), false).unwrap();
+    assert_eq!("6", report.part_2.unwrap().value);
+}
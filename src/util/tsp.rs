@@ -0,0 +1,143 @@
+// Travelling salesman over a distance matrix, using the Held-Karp bitmask DP. It's `O(2^n * n^2)`
+// rather than `O(n!)`, which is plenty for the dozen or so places puzzles tend to have. The matrix
+// doesn't have to be symmetric: `distances[from][to]` is the cost of going from `from` to `to`
+
+use std::ops::Add;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    // Visits every place once, ending wherever is best
+    Path,
+    // Visits every place once, then goes back to where it started
+    Cycle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Shortest,
+    Longest,
+}
+
+// The tables below hold an entry for every subset of places paired with every place, so this
+// many already takes a couple of million entries
+pub const MAX_PLACES: usize = 16;
+
+// The best cost of visiting every place along with the order to visit them in. `start` fixes the
+// first place, and defaults to place 0 for cycles since they cost the same wherever they start.
+// A cycle's order doesn't repeat its start at the end, but its cost includes the trip back there
+pub fn solve<C>(
+    distances: &[Vec<C>],
+    route: Route,
+    goal: Goal,
+    start: Option<usize>,
+) -> Option<(C, Vec<usize>)>
+where
+    C: Copy + Ord + Add<Output = C> + Default,
+{
+    let n = distances.len();
+    assert!(
+        n <= MAX_PLACES,
+        "{n} places is too many to route, at most {MAX_PLACES} are supported"
+    );
+    if n == 0 {
+        return None;
+    }
+    let start = start.or((route == Route::Cycle).then_some(0));
+    let better = |new: C, old: Option<C>| {
+        old.is_none_or(|old| match goal {
+            Goal::Shortest => new < old,
+            Goal::Longest => new > old,
+        })
+    };
+
+    // `best[mask * n + last]` is the best cost of visiting the places in `mask`, ending at `last`,
+    // and `previous` is the place visited just before `last` on that route
+    let full = (1 << n) - 1;
+    let mut best: Vec<Option<C>> = vec![None; n << n];
+    let mut previous = vec![0; n << n];
+    for first in (0..n).filter(|&first| start.is_none_or(|start| start == first)) {
+        best[(1 << first) * n + first] = Some(C::default());
+    }
+
+    for mask in 1..full {
+        for last in 0..n {
+            let Some(cost) = best[mask * n + last] else {
+                continue;
+            };
+            for next in (0..n).filter(|next| mask & 1 << next == 0) {
+                let idx = (mask | 1 << next) * n + next;
+                let next_cost = cost + distances[last][next];
+                if better(next_cost, best[idx]) {
+                    best[idx] = Some(next_cost);
+                    previous[idx] = last;
+                }
+            }
+        }
+    }
+
+    let (cost, last) = (0..n)
+        .filter_map(|last| {
+            let cost = best[full * n + last]?;
+            match (route, start) {
+                (Route::Cycle, Some(start)) => Some((cost + distances[last][start], last)),
+                _ => Some((cost, last)),
+            }
+        })
+        .reduce(|a, b| if better(b.0, Some(a.0)) { b } else { a })?;
+
+    let mut order = vec![last];
+    let (mut mask, mut current) = (full, last);
+    while mask != 1 << current {
+        let before = previous[mask * n + current];
+        mask ^= 1 << current;
+        current = before;
+        order.push(current);
+    }
+    order.reverse();
+    Some((cost, order))
+}
+
+#[test]
+fn routes() {
+    // London, Dublin and Belfast from 2015 Day 9
+    let distances = vec![vec![0, 464, 518], vec![464, 0, 141], vec![518, 141, 0]];
+    // Either direction is as good as the other
+    let (shortest, order) = solve(&distances, Route::Path, Goal::Shortest, None).unwrap();
+    assert_eq!(605, shortest);
+    assert_eq!(1, order[1]);
+    let (longest, order) = solve(&distances, Route::Path, Goal::Longest, None).unwrap();
+    assert_eq!(982, longest);
+    assert_eq!(0, order[1]);
+    assert_eq!(
+        Some((659, vec![1, 2, 0])),
+        solve(&distances, Route::Path, Goal::Shortest, Some(1))
+    );
+    assert_eq!(
+        Some(1123),
+        solve(&distances, Route::Cycle, Goal::Shortest, None).map(|(cost, _)| cost)
+    );
+
+    // Going round one way is cheaper than the other
+    let one_way = vec![
+        vec![0, 1, 9, 9],
+        vec![9, 0, 1, 9],
+        vec![9, 9, 0, 1],
+        vec![1, 9, 9, 0],
+    ];
+    assert_eq!(
+        Some((4, vec![2, 3, 0, 1])),
+        solve(&one_way, Route::Cycle, Goal::Shortest, Some(2))
+    );
+    assert_eq!(
+        Some((0, vec![0])),
+        solve(&[vec![0]], Route::Cycle, Goal::Longest, None)
+    );
+    assert_eq!(None, solve::<u32>(&[], Route::Path, Goal::Shortest, None));
+}
+
+#[test]
+#[should_panic(expected = "too many to route")]
+fn too_many_places() {
+    let distances = vec![vec![1u32; MAX_PLACES + 1]; MAX_PLACES + 1];
+    solve(&distances, Route::Path, Goal::Shortest, None);
+}